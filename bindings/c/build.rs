@@ -0,0 +1,18 @@
+use std::env;
+
+/// Writes the C header of the library in `$OUT_DIR/address.h`, and in
+/// `$ADDRESS_FFI_INCLUDE_DIR/address.h` when it is set, e.g. by `make header`
+fn main() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set");
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is not set");
+
+    let bindings = cbindgen::generate(&crate_dir).expect("Cannot generate the C header");
+    bindings.write_to_file(format!("{out_dir}/address.h"));
+    if let Ok(include_dir) = env::var("ADDRESS_FFI_INCLUDE_DIR") {
+        bindings.write_to_file(format!("{include_dir}/address.h"));
+    }
+
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=ADDRESS_FFI_INCLUDE_DIR");
+}
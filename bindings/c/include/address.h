@@ -0,0 +1,142 @@
+/*
+ * C API of the address conversion library
+ *
+ * Strings are NUL-terminated UTF-8. Input strings are borrowed for the call only.
+ * Addresses returned by `address_new` or `address_parse` are owned by the caller
+ * and freed with `address_free`. Strings returned through `out` or `error` are owned
+ * by the caller and freed with `address_string_free`.
+ */
+
+#ifndef ADDRESS_H
+#define ADDRESS_H
+
+/* Generated by cbindgen from src/lib.rs, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Formats of the parsed and rendered strings, passed to the functions as an `int`
+typedef enum AddressFormat {
+  // JSON object of the `Address` fields
+  ADDRESS_FORMAT_JSON = 0,
+  // ISO 20022 `PstlAdr` XML element
+  ADDRESS_FORMAT_ISO20022 = 1,
+  // NF Z10-011 lines of an individual, separated by `\n`
+  ADDRESS_FORMAT_NF = 2,
+  // NF Z10-011 lines of a company, separated by `\n`
+  ADDRESS_FORMAT_NF_ENTERPRISE = 3,
+} AddressFormat;
+
+// Result of every function, `ADDRESS_OK` on success
+typedef enum AddressStatus {
+  ADDRESS_OK = 0,
+  // A required pointer is NULL
+  ADDRESS_NULL_POINTER = 1,
+  // A string is not valid UTF-8
+  ADDRESS_INVALID_UTF8 = 2,
+  // The input is not in the expected format
+  ADDRESS_PARSE_ERROR = 3,
+  // The address cannot be written in the format
+  ADDRESS_RENDER_ERROR = 4,
+  // The address breaks a rule of the format
+  ADDRESS_VALIDATION_ERROR = 5,
+  // The field name is not one of the `Address` fields
+  ADDRESS_UNKNOWN_FIELD = 6,
+  // An unexpected error, the library panicked
+  ADDRESS_INTERNAL_ERROR = 7,
+  // The format is not one of `AddressFormat`
+  ADDRESS_UNKNOWN_FORMAT = 8,
+} AddressStatus;
+
+// An address, created by `address_new` or `address_parse` and freed by
+// `address_free`
+typedef struct Address Address;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates an empty address, to be freed with `address_free`
+struct Address *address_new(void);
+
+// Frees an address, NULL is ignored
+//
+// # Safety
+//
+// `address` must be NULL or come from `address_new` or `address_parse`, and must
+// not be used afterwards.
+void address_free(struct Address *address);
+
+// Frees a string returned by the library, NULL is ignored
+//
+// # Safety
+//
+// `s` must be NULL or come from this library, and must not be used afterwards.
+void address_string_free(char *s);
+
+// Parses `input` in `format`, one of `AddressFormat`, and stores a new address in `out`
+//
+// On failure `out` is left untouched, and the message is stored in `error` unless it
+// is NULL, to be freed with `address_string_free`.
+//
+// # Safety
+//
+// `input` must be a NUL-terminated string, `out` must point to a writable
+// `Address *`, and `error` must be NULL or point to a writable `char *`.
+enum AddressStatus address_parse(const char *input, int format, struct Address **out, char **error);
+
+// Renders `address` in `format`, and stores the new string in `out`, to be freed
+// with `address_string_free`
+//
+// # Safety
+//
+// `address` must come from `address_new` or `address_parse`, `out` must point to a
+// writable `char *`, and `error` must be NULL or point to a writable `char *`.
+enum AddressStatus address_render(const struct Address *address,
+                                  int format,
+                                  char **out,
+                                  char **error);
+
+// Checks `address` against the rules of `format`: the field lengths of ISO 20022
+// for JSON and ISO 20022, the line lengths for NF Z10-011
+//
+// Returns `ADDRESS_VALIDATION_ERROR` with the broken rule in `error`.
+//
+// # Safety
+//
+// `address` must come from `address_new` or `address_parse`, and `error` must be
+// NULL or point to a writable `char *`.
+enum AddressStatus address_validate(const struct Address *address, int format, char **error);
+
+// Reads a field, e.g. `"town_name"`, and stores a new string in `out`, or NULL when
+// the field is not set
+//
+// # Safety
+//
+// `address` must come from `address_new` or `address_parse`, `name` must be a
+// NUL-terminated string, `out` must point to a writable `char *`, and `error` must
+// be NULL or point to a writable `char *`.
+enum AddressStatus address_get_field(const struct Address *address,
+                                     const char *name,
+                                     char **out,
+                                     char **error);
+
+// Sets a field, a NULL `value` clearing it
+//
+// # Safety
+//
+// `address` must come from `address_new` or `address_parse`, `name` must be a
+// NUL-terminated string, `value` must be NULL or a NUL-terminated string, and
+// `error` must be NULL or point to a writable `char *`.
+enum AddressStatus address_set_field(struct Address *address,
+                                     const char *name,
+                                     const char *value,
+                                     char **error);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ADDRESS_H */
@@ -0,0 +1,394 @@
+use std::error::Error;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use address::models::address::Address as Model;
+use address::models::address_iso_20022::ISO_20022;
+use address::models::address_nf_z10_011_enterprise::NF_Z10_011_Enterprise;
+use address::models::address_nf_z10_011_individual::NF_Z10_011_Individual;
+use address::models::validate::Validate;
+
+/// An address, created by `address_new` or `address_parse` and freed by
+/// `address_free`
+pub struct Address(Model);
+
+/// Formats of the parsed and rendered strings, passed to the functions as an `int`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFormat {
+    /// JSON object of the `Address` fields
+    ADDRESS_FORMAT_JSON = 0,
+    /// ISO 20022 `PstlAdr` XML element
+    ADDRESS_FORMAT_ISO20022 = 1,
+    /// NF Z10-011 lines of an individual, separated by `\n`
+    ADDRESS_FORMAT_NF = 2,
+    /// NF Z10-011 lines of a company, separated by `\n`
+    ADDRESS_FORMAT_NF_ENTERPRISE = 3,
+}
+
+/// Result of every function, `ADDRESS_OK` on success
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressStatus {
+    ADDRESS_OK = 0,
+    /// A required pointer is NULL
+    ADDRESS_NULL_POINTER = 1,
+    /// A string is not valid UTF-8
+    ADDRESS_INVALID_UTF8 = 2,
+    /// The input is not in the expected format
+    ADDRESS_PARSE_ERROR = 3,
+    /// The address cannot be written in the format
+    ADDRESS_RENDER_ERROR = 4,
+    /// The address breaks a rule of the format
+    ADDRESS_VALIDATION_ERROR = 5,
+    /// The field name is not one of the `Address` fields
+    ADDRESS_UNKNOWN_FIELD = 6,
+    /// An unexpected error, the library panicked
+    ADDRESS_INTERNAL_ERROR = 7,
+    /// The format is not one of `AddressFormat`
+    ADDRESS_UNKNOWN_FORMAT = 8,
+}
+
+use AddressFormat::*;
+use AddressStatus::*;
+
+/// Reads a format from C, where any `int` may be passed as an enum
+fn to_format(value: c_int) -> Result<AddressFormat, Failure> {
+    match value {
+        0 => Ok(ADDRESS_FORMAT_JSON),
+        1 => Ok(ADDRESS_FORMAT_ISO20022),
+        2 => Ok(ADDRESS_FORMAT_NF),
+        3 => Ok(ADDRESS_FORMAT_NF_ENTERPRISE),
+        _ => Err(Failure(
+            ADDRESS_UNKNOWN_FORMAT,
+            format!("Unknown format {value}"),
+        )),
+    }
+}
+
+/// An error of a function, with the message reported to the caller
+struct Failure(AddressStatus, String);
+
+impl Failure {
+    fn new(status: AddressStatus) -> impl Fn(Box<dyn Error>) -> Failure {
+        move |e| Failure(status, e.to_string())
+    }
+}
+
+/// Converts a string to a C string owned by the caller, NUL bytes being dropped
+fn to_c_string(s: String) -> *mut c_char {
+    CString::new(s.replace('\0', ""))
+        .unwrap_or_default()
+        .into_raw()
+}
+
+/// Borrows a NUL-terminated UTF-8 string
+///
+/// # Safety
+///
+/// `s` must be NULL or point to a NUL-terminated string.
+unsafe fn from_c_str<'a>(s: *const c_char) -> Result<&'a str, Failure> {
+    if s.is_null() {
+        return Err(Failure(ADDRESS_NULL_POINTER, "A string is NULL".into()));
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|e| Failure(ADDRESS_INVALID_UTF8, e.to_string()))
+}
+
+/// Runs `f`, stores its error message in `error` when it is not NULL, and returns the
+/// status
+///
+/// # Safety
+///
+/// `error` must be NULL or point to a writable `char *`.
+unsafe fn run(error: *mut *mut c_char, f: impl FnOnce() -> Result<(), Failure>) -> AddressStatus {
+    if !error.is_null() {
+        *error = ptr::null_mut();
+    }
+    let Failure(status, message) = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => return ADDRESS_OK,
+        Ok(Err(failure)) => failure,
+        Err(_) => Failure(ADDRESS_INTERNAL_ERROR, "The library panicked".into()),
+    };
+    if !error.is_null() {
+        *error = to_c_string(message);
+    }
+    status
+}
+
+fn parse(input: &str, format: AddressFormat) -> Result<Model, Box<dyn Error>> {
+    Ok(match format {
+        ADDRESS_FORMAT_JSON => serde_json::from_str(input)?,
+        ADDRESS_FORMAT_ISO20022 => input.parse::<ISO_20022>()?.try_into()?,
+        ADDRESS_FORMAT_NF => input.parse::<NF_Z10_011_Individual>()?.try_into()?,
+        ADDRESS_FORMAT_NF_ENTERPRISE => input.parse::<NF_Z10_011_Enterprise>()?.try_into()?,
+    })
+}
+
+fn render(address: &Model, format: AddressFormat) -> Result<String, Box<dyn Error>> {
+    let address = address.clone();
+    Ok(match format {
+        ADDRESS_FORMAT_JSON => serde_json::to_string_pretty(&address)?,
+        ADDRESS_FORMAT_ISO20022 => ISO_20022::try_from(address)?.to_xml()?,
+        ADDRESS_FORMAT_NF => NF_Z10_011_Individual::try_from(address)?.lines.join("\n"),
+        ADDRESS_FORMAT_NF_ENTERPRISE => NF_Z10_011_Enterprise::try_from(address)?.lines.join("\n"),
+    })
+}
+
+fn validate(address: &Model, format: AddressFormat) -> Result<(), Box<dyn Error>> {
+    let address = address.clone();
+    match format {
+        ADDRESS_FORMAT_JSON | ADDRESS_FORMAT_ISO20022 => ISO_20022::try_from(address)?.validate(),
+        ADDRESS_FORMAT_NF => NF_Z10_011_Individual::try_from(address)?.validate(),
+        ADDRESS_FORMAT_NF_ENTERPRISE => NF_Z10_011_Enterprise::try_from(address)?.validate(),
+    }
+}
+
+/// Creates an empty address, to be freed with `address_free`
+#[no_mangle]
+pub extern "C" fn address_new() -> *mut Address {
+    Box::into_raw(Box::new(Address(Model::default())))
+}
+
+/// Frees an address, NULL is ignored
+///
+/// # Safety
+///
+/// `address` must be NULL or come from `address_new` or `address_parse`, and must
+/// not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn address_free(address: *mut Address) {
+    if !address.is_null() {
+        drop(Box::from_raw(address));
+    }
+}
+
+/// Frees a string returned by the library, NULL is ignored
+///
+/// # Safety
+///
+/// `s` must be NULL or come from this library, and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn address_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Parses `input` in `format`, one of `AddressFormat`, and stores a new address in `out`
+///
+/// On failure `out` is left untouched, and the message is stored in `error` unless it
+/// is NULL, to be freed with `address_string_free`.
+///
+/// # Safety
+///
+/// `input` must be a NUL-terminated string, `out` must point to a writable
+/// `Address *`, and `error` must be NULL or point to a writable `char *`.
+#[no_mangle]
+pub unsafe extern "C" fn address_parse(
+    input: *const c_char,
+    format: c_int,
+    out: *mut *mut Address,
+    error: *mut *mut c_char,
+) -> AddressStatus {
+    run(error, || {
+        let input = from_c_str(input)?;
+        if out.is_null() {
+            return Err(Failure(ADDRESS_NULL_POINTER, "`out` is NULL".into()));
+        }
+        let address =
+            parse(input, to_format(format)?).map_err(Failure::new(ADDRESS_PARSE_ERROR))?;
+        *out = Box::into_raw(Box::new(Address(address)));
+        Ok(())
+    })
+}
+
+/// Renders `address` in `format`, and stores the new string in `out`, to be freed
+/// with `address_string_free`
+///
+/// # Safety
+///
+/// `address` must come from `address_new` or `address_parse`, `out` must point to a
+/// writable `char *`, and `error` must be NULL or point to a writable `char *`.
+#[no_mangle]
+pub unsafe extern "C" fn address_render(
+    address: *const Address,
+    format: c_int,
+    out: *mut *mut c_char,
+    error: *mut *mut c_char,
+) -> AddressStatus {
+    run(error, || {
+        let Some(address) = address.as_ref() else {
+            return Err(Failure(ADDRESS_NULL_POINTER, "`address` is NULL".into()));
+        };
+        if out.is_null() {
+            return Err(Failure(ADDRESS_NULL_POINTER, "`out` is NULL".into()));
+        }
+        let s =
+            render(&address.0, to_format(format)?).map_err(Failure::new(ADDRESS_RENDER_ERROR))?;
+        *out = to_c_string(s);
+        Ok(())
+    })
+}
+
+/// Checks `address` against the rules of `format`: the field lengths of ISO 20022
+/// for JSON and ISO 20022, the line lengths for NF Z10-011
+///
+/// Returns `ADDRESS_VALIDATION_ERROR` with the broken rule in `error`.
+///
+/// # Safety
+///
+/// `address` must come from `address_new` or `address_parse`, and `error` must be
+/// NULL or point to a writable `char *`.
+#[no_mangle]
+pub unsafe extern "C" fn address_validate(
+    address: *const Address,
+    format: c_int,
+    error: *mut *mut c_char,
+) -> AddressStatus {
+    run(error, || {
+        let Some(address) = address.as_ref() else {
+            return Err(Failure(ADDRESS_NULL_POINTER, "`address` is NULL".into()));
+        };
+        validate(&address.0, to_format(format)?).map_err(Failure::new(ADDRESS_VALIDATION_ERROR))
+    })
+}
+
+/// Reads a field, e.g. `"town_name"`, and stores a new string in `out`, or NULL when
+/// the field is not set
+///
+/// # Safety
+///
+/// `address` must come from `address_new` or `address_parse`, `name` must be a
+/// NUL-terminated string, `out` must point to a writable `char *`, and `error` must
+/// be NULL or point to a writable `char *`.
+#[no_mangle]
+pub unsafe extern "C" fn address_get_field(
+    address: *const Address,
+    name: *const c_char,
+    out: *mut *mut c_char,
+    error: *mut *mut c_char,
+) -> AddressStatus {
+    run(error, || {
+        let Some(address) = address.as_ref() else {
+            return Err(Failure(ADDRESS_NULL_POINTER, "`address` is NULL".into()));
+        };
+        let name = from_c_str(name)?;
+        if out.is_null() {
+            return Err(Failure(ADDRESS_NULL_POINTER, "`out` is NULL".into()));
+        }
+        let value = address
+            .0
+            .field(name)
+            .map_err(Failure::new(ADDRESS_UNKNOWN_FIELD))?;
+        *out = value.map_or(ptr::null_mut(), |x| to_c_string(x.to_string()));
+        Ok(())
+    })
+}
+
+/// Sets a field, a NULL `value` clearing it
+///
+/// # Safety
+///
+/// `address` must come from `address_new` or `address_parse`, `name` must be a
+/// NUL-terminated string, `value` must be NULL or a NUL-terminated string, and
+/// `error` must be NULL or point to a writable `char *`.
+#[no_mangle]
+pub unsafe extern "C" fn address_set_field(
+    address: *mut Address,
+    name: *const c_char,
+    value: *const c_char,
+    error: *mut *mut c_char,
+) -> AddressStatus {
+    run(error, || {
+        let Some(address) = address.as_mut() else {
+            return Err(Failure(ADDRESS_NULL_POINTER, "`address` is NULL".into()));
+        };
+        let name = from_c_str(name)?;
+        let value = match value.is_null() {
+            true => None,
+            false => Some(from_c_str(value)?.to_string()),
+        };
+        address
+            .0
+            .set_field(name, value)
+            .map_err(Failure::new(ADDRESS_UNKNOWN_FIELD))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_render() {
+        let input =
+            CString::new("Monsieur Jean DURAND\n\n\n25 RUE DES FLEURS\n\n33500 LIBOURNE\nFRANCE")
+                .expect("error");
+        let mut address = ptr::null_mut();
+        let mut out = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        unsafe {
+            let status = address_parse(
+                input.as_ptr(),
+                ADDRESS_FORMAT_NF as c_int,
+                &mut address,
+                &mut error,
+            );
+            assert_eq!(status, ADDRESS_OK);
+            assert!(error.is_null());
+
+            let status = address_render(
+                address,
+                ADDRESS_FORMAT_ISO20022 as c_int,
+                &mut out,
+                &mut error,
+            );
+            assert_eq!(status, ADDRESS_OK);
+            let xml = CStr::from_ptr(out).to_str().expect("error");
+            assert!(xml.contains("<TwnNm>LIBOURNE</TwnNm>"));
+
+            address_string_free(out);
+            address_free(address);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = CString::new("<PstlAdr>").expect("error");
+        let name = CString::new("city").expect("error");
+        let mut address = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        unsafe {
+            let status = address_parse(
+                input.as_ptr(),
+                ADDRESS_FORMAT_ISO20022 as c_int,
+                &mut address,
+                &mut error,
+            );
+            assert_eq!(status, ADDRESS_PARSE_ERROR);
+            assert!(address.is_null());
+            assert!(!error.is_null());
+            address_string_free(error);
+
+            let address = address_new();
+            let status = address_set_field(address, name.as_ptr(), ptr::null(), &mut error);
+            assert_eq!(status, ADDRESS_UNKNOWN_FIELD);
+            assert_eq!(CStr::from_ptr(error).to_str(), Ok("Unknown field `city`"));
+            address_string_free(error);
+
+            let status = address_validate(ptr::null(), ADDRESS_FORMAT_NF as c_int, ptr::null_mut());
+            assert_eq!(status, ADDRESS_NULL_POINTER);
+
+            let status = address_validate(address, 42, &mut error);
+            assert_eq!(status, ADDRESS_UNKNOWN_FORMAT);
+            assert_eq!(CStr::from_ptr(error).to_str(), Ok("Unknown format 42"));
+            address_string_free(error);
+            address_free(address);
+        }
+    }
+}
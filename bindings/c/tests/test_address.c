@@ -0,0 +1,118 @@
+/* Exercises the C API, run with `make test` */
+
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "address.h"
+
+static const char *NF =
+    "Monsieur Jean DURAND\n"
+    "Chez Mireille COPEAU Appartement 2\n"
+    "Entrée A Bâtiment Jonquille\n"
+    "25 RUE DE L'EGLISE\n"
+    "CAUDOS\n"
+    "33380 MIOS\n"
+    "FRANCE";
+
+static void test_nf_to_iso(void) {
+    Address *address = NULL;
+    char *xml = NULL;
+    char *error = NULL;
+
+    assert(address_parse(NF, ADDRESS_FORMAT_NF, &address, &error) == ADDRESS_OK);
+    assert(error == NULL);
+
+    assert(address_render(address, ADDRESS_FORMAT_ISO20022, &xml, &error) == ADDRESS_OK);
+    assert(strstr(xml, "<PstCd>33380</PstCd>") != NULL);
+    assert(strstr(xml, "<TwnNm>MIOS</TwnNm>") != NULL);
+
+    /* Accents are kept as UTF-8 */
+    char *floor = NULL;
+    assert(address_get_field(address, "floor", &floor, NULL) == ADDRESS_OK);
+    assert(strcmp(floor, "Entrée A Bâtiment Jonquille") == 0);
+
+    address_string_free(floor);
+    address_string_free(xml);
+    address_free(address);
+}
+
+static void test_iso_to_nf(void) {
+    Address *address = NULL;
+    char *nf = NULL;
+
+    const char *xml =
+        "<PstlAdr><Dept/><SubDept/><StrtNm>25D RUE DES FLEURS</StrtNm><BldgNb/><BldgNm/>"
+        "<Flr/><PstBx/><Room/><PstCd>33500</PstCd><TwnNm>LIBOURNE</TwnNm><TwnLctnNm/>"
+        "<DstrctNm/><CtrySubDvsn/><Ctry>FR</Ctry></PstlAdr>";
+    assert(address_parse(xml, ADDRESS_FORMAT_ISO20022, &address, NULL) == ADDRESS_OK);
+    assert(address_set_field(address, "name", "Monsieur Jean DURAND", NULL) == ADDRESS_OK);
+
+    assert(address_render(address, ADDRESS_FORMAT_NF, &nf, NULL) == ADDRESS_OK);
+    assert(strncmp(nf, "Monsieur Jean DURAND\n", 21) == 0);
+    assert(strstr(nf, "33500 LIBOURNE") != NULL);
+
+    address_string_free(nf);
+    address_free(address);
+}
+
+static void test_validate(void) {
+    Address *address = address_new();
+    char *error = NULL;
+    char *value = NULL;
+
+    assert(address_set_field(address, "post_code", "33500", NULL) == ADDRESS_OK);
+    assert(address_set_field(address, "country", "FR", NULL) == ADDRESS_OK);
+    assert(address_validate(address, ADDRESS_FORMAT_ISO20022, &error) == ADDRESS_OK);
+    assert(error == NULL);
+
+    assert(address_set_field(address, "building_number", "12345678901234567", NULL) == ADDRESS_OK);
+    assert(address_validate(address, ADDRESS_FORMAT_ISO20022, &error) == ADDRESS_VALIDATION_ERROR);
+    assert(strstr(error, "BldgNb") != NULL);
+    address_string_free(error);
+
+    /* A NULL value clears the field */
+    assert(address_set_field(address, "building_number", NULL, NULL) == ADDRESS_OK);
+    assert(address_get_field(address, "building_number", &value, NULL) == ADDRESS_OK);
+    assert(value == NULL);
+
+    address_free(address);
+}
+
+static void test_errors(void) {
+    Address *address = NULL;
+    char *error = NULL;
+
+    assert(address_parse("<PstlAdr>", ADDRESS_FORMAT_ISO20022, &address, &error) == ADDRESS_PARSE_ERROR);
+    assert(address == NULL);
+    assert(error != NULL && strlen(error) > 0);
+    address_string_free(error);
+
+    assert(address_parse("\xff\xfe", ADDRESS_FORMAT_NF, &address, NULL) == ADDRESS_INVALID_UTF8);
+    assert(address_parse(NULL, ADDRESS_FORMAT_NF, &address, NULL) == ADDRESS_NULL_POINTER);
+
+    address = address_new();
+    assert(address_set_field(address, "city", "MIOS", &error) == ADDRESS_UNKNOWN_FIELD);
+    assert(strcmp(error, "Unknown field `city`") == 0);
+    address_string_free(error);
+
+    /* Formats outside of `AddressFormat` are reported, not undefined behaviour */
+    assert(address_validate(address, 42, &error) == ADDRESS_UNKNOWN_FORMAT);
+    assert(strcmp(error, "Unknown format 42") == 0);
+    address_string_free(error);
+    assert(address_validate(address, -1, NULL) == ADDRESS_UNKNOWN_FORMAT);
+    address_free(address);
+
+    /* Freeing NULL is allowed */
+    address_free(NULL);
+    address_string_free(NULL);
+}
+
+int main(void) {
+    test_nf_to_iso();
+    test_iso_to_nf();
+    test_validate();
+    test_errors();
+    printf("All tests passed\n");
+    return 0;
+}
@@ -0,0 +1,438 @@
+use std::error::Error;
+
+use address::models::address::{Address, Coordinates};
+use address::models::address_iso_20022::ISO_20022;
+use address::models::address_nf_z10_011_enterprise::NF_Z10_011_Enterprise;
+use address::models::address_nf_z10_011_individual::NF_Z10_011_Individual;
+use address::models::address_patch::AddressPatch;
+use address::models::validate::Validate;
+use address::repositories::address_repository::AddressRepository;
+use address::repositories::in_memory_repository::InMemoryRepository;
+use address::repositories::json_repository::JsonFileRepository;
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyString};
+use uuid::Uuid;
+
+create_exception!(
+    address,
+    AddressError,
+    PyException,
+    "Base class of the errors of `address`"
+);
+create_exception!(
+    address,
+    ParseError,
+    AddressError,
+    "The input is not in the expected format"
+);
+create_exception!(
+    address,
+    ValidationError,
+    AddressError,
+    "The address breaks a rule of the format"
+);
+create_exception!(
+    address,
+    RenderError,
+    AddressError,
+    "The address cannot be written in the format"
+);
+create_exception!(
+    address,
+    NotFoundError,
+    AddressError,
+    "No address is saved with this id"
+);
+create_exception!(address, RepositoryError, AddressError, "The storage failed");
+
+fn parse_error(e: Box<dyn Error>) -> PyErr {
+    ParseError::new_err(e.to_string())
+}
+
+fn render_error(e: Box<dyn Error>) -> PyErr {
+    RenderError::new_err(e.to_string())
+}
+
+fn validation_error(e: Box<dyn Error>) -> PyErr {
+    ValidationError::new_err(e.to_string())
+}
+
+fn repository_error(e: Box<dyn Error>) -> PyErr {
+    RepositoryError::new_err(e.to_string())
+}
+
+fn parse_id(id: &str) -> PyResult<Uuid> {
+    Uuid::parse_str(id).map_err(|e| PyValueError::new_err(format!("Invalid id `{id}`: {e}")))
+}
+
+/// A postal address, the fields being those of ISO 20022 `PstlAdr` with a name
+///
+/// Optional fields are `None` when not set, `coordinates` is a `(lat, lon)` tuple.
+#[pyclass(name = "Address", module = "address", eq, get_all, set_all)]
+#[derive(Debug, Clone, Default, PartialEq)]
+struct PyAddress {
+    name: Option<String>,
+    department: Option<String>,
+    sub_department: Option<String>,
+    street_name: Option<String>,
+    building_number: Option<String>,
+    building_name: Option<String>,
+    floor: Option<String>,
+    post_box: Option<String>,
+    room: Option<String>,
+    post_code: String,
+    town_name: String,
+    town_location_name: Option<String>,
+    district_name: Option<String>,
+    country_sub_division: Option<String>,
+    country: String,
+    coordinates: Option<(f64, f64)>,
+}
+
+impl From<Address> for PyAddress {
+    fn from(value: Address) -> Self {
+        PyAddress {
+            name: value.name,
+            department: value.department,
+            sub_department: value.sub_department,
+            street_name: value.street_name,
+            building_number: value.building_number,
+            building_name: value.building_name,
+            floor: value.floor,
+            post_box: value.post_box,
+            room: value.room,
+            post_code: value.post_code,
+            town_name: value.town_name,
+            town_location_name: value.town_location_name,
+            district_name: value.district_name,
+            country_sub_division: value.country_sub_division,
+            country: value.country,
+            coordinates: value.coordinates.map(|x| (x.lat, x.lon)),
+        }
+    }
+}
+
+impl From<PyAddress> for Address {
+    fn from(value: PyAddress) -> Self {
+        Address {
+            name: value.name,
+            department: value.department,
+            sub_department: value.sub_department,
+            street_name: value.street_name,
+            building_number: value.building_number,
+            building_name: value.building_name,
+            floor: value.floor,
+            post_box: value.post_box,
+            room: value.room,
+            post_code: value.post_code,
+            town_name: value.town_name,
+            town_location_name: value.town_location_name,
+            district_name: value.district_name,
+            country_sub_division: value.country_sub_division,
+            country: value.country,
+            coordinates: value.coordinates.map(|(lat, lon)| Coordinates { lat, lon }),
+        }
+    }
+}
+
+impl PyAddress {
+    fn address(&self) -> Address {
+        self.clone().into()
+    }
+}
+
+#[pymethods]
+impl PyAddress {
+    #[new]
+    #[pyo3(signature = (
+        *,
+        name = None,
+        department = None,
+        sub_department = None,
+        street_name = None,
+        building_number = None,
+        building_name = None,
+        floor = None,
+        post_box = None,
+        room = None,
+        post_code = String::new(),
+        town_name = String::new(),
+        town_location_name = None,
+        district_name = None,
+        country_sub_division = None,
+        country = String::new(),
+        coordinates = None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        name: Option<String>,
+        department: Option<String>,
+        sub_department: Option<String>,
+        street_name: Option<String>,
+        building_number: Option<String>,
+        building_name: Option<String>,
+        floor: Option<String>,
+        post_box: Option<String>,
+        room: Option<String>,
+        post_code: String,
+        town_name: String,
+        town_location_name: Option<String>,
+        district_name: Option<String>,
+        country_sub_division: Option<String>,
+        country: String,
+        coordinates: Option<(f64, f64)>,
+    ) -> Self {
+        PyAddress {
+            name,
+            department,
+            sub_department,
+            street_name,
+            building_number,
+            building_name,
+            floor,
+            post_box,
+            room,
+            post_code,
+            town_name,
+            town_location_name,
+            district_name,
+            country_sub_division,
+            country,
+            coordinates,
+        }
+    }
+
+    /// Names of the fields, in declaration order
+    #[classattr]
+    fn fields() -> Vec<&'static str> {
+        Address::FIELDS.to_vec()
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let address = self.address();
+        let mut fields = Vec::new();
+        for name in Address::FIELDS {
+            if let Some(value) = address.field(name).map_err(parse_error)? {
+                fields.push(format!("{name}={}", PyString::new(py, value).repr()?));
+            }
+        }
+        if let Some((lat, lon)) = self.coordinates {
+            fields.push(format!("coordinates=({lat:?}, {lon:?})"));
+        }
+        Ok(format!("Address({})", fields.join(", ")))
+    }
+
+    /// Returns the fields as a dict, `None` for the fields that are not set
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        let address = self.address();
+        for name in Address::FIELDS {
+            dict.set_item(name, address.field(name).map_err(parse_error)?)?;
+        }
+        dict.set_item("coordinates", self.coordinates)?;
+        Ok(dict)
+    }
+
+    /// Builds an address from a dict of fields, unknown keys are errors
+    #[staticmethod]
+    fn from_dict(dict: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let mut address = Address::default();
+        let mut coordinates = None;
+        for (key, value) in dict.iter() {
+            let key: String = key.extract()?;
+            match key.as_str() {
+                "coordinates" => coordinates = value.extract()?,
+                name => address
+                    .set_field(name, value.extract()?)
+                    .map_err(parse_error)?,
+            }
+        }
+        Ok(PyAddress {
+            coordinates,
+            ..address.into()
+        })
+    }
+
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string_pretty(&self.address()).map_err(|e| render_error(e.into()))
+    }
+
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        let address: Address = serde_json::from_str(json).map_err(|e| parse_error(e.into()))?;
+        Ok(address.into())
+    }
+
+    /// Writes the ISO 20022 `PstlAdr` XML element
+    fn to_iso20022(&self) -> PyResult<String> {
+        ISO_20022::try_from(self.address())
+            .and_then(|x| x.to_xml())
+            .map_err(render_error)
+    }
+
+    /// Reads an ISO 20022 `PstlAdr` XML element, checking the field lengths when
+    /// `validate` is set
+    #[staticmethod]
+    #[pyo3(signature = (xml, *, validate = false))]
+    fn from_iso20022(xml: &str, validate: bool) -> PyResult<Self> {
+        let iso: ISO_20022 = xml.parse().map_err(parse_error)?;
+        if validate {
+            iso.validate().map_err(validation_error)?;
+        }
+        Ok(Address::try_from(iso).map_err(parse_error)?.into())
+    }
+
+    /// Writes the NF Z10-011 lines of an individual, separated by line breaks
+    fn to_nf(&self) -> PyResult<String> {
+        let nf = NF_Z10_011_Individual::try_from(self.address()).map_err(render_error)?;
+        Ok(nf.lines.join("\n"))
+    }
+
+    /// Reads the NF Z10-011 lines of an individual, or of a company when `enterprise`
+    /// is set, checking the line lengths when `validate` is set
+    #[staticmethod]
+    #[pyo3(signature = (text, *, enterprise = false, validate = false))]
+    fn from_nf(text: &str, enterprise: bool, validate: bool) -> PyResult<Self> {
+        let address = if enterprise {
+            let nf: NF_Z10_011_Enterprise = text.parse().map_err(parse_error)?;
+            if validate {
+                nf.validate().map_err(validation_error)?;
+            }
+            Address::try_from(nf)
+        } else {
+            let nf: NF_Z10_011_Individual = text.parse().map_err(parse_error)?;
+            if validate {
+                nf.validate().map_err(validation_error)?;
+            }
+            Address::try_from(nf)
+        };
+        Ok(address.map_err(parse_error)?.into())
+    }
+
+    /// Checks the address against the ISO 20022 field lengths, raises
+    /// `ValidationError`
+    fn validate(&self) -> PyResult<()> {
+        ISO_20022::try_from(self.address())
+            .and_then(|x| x.validate())
+            .map_err(validation_error)
+    }
+}
+
+/// Reads a patch: a JSON string, a dict (JSON Merge Patch) or a list (JSON Patch)
+fn patch_from(changes: &Bound<'_, PyAny>) -> PyResult<AddressPatch> {
+    let json = match changes.downcast::<PyString>() {
+        Ok(json) => json.to_string(),
+        Err(_) => {
+            let json = changes.py().import("json")?;
+            json.call_method1("dumps", (changes,))?.extract()?
+        }
+    };
+    json.parse().map_err(parse_error)
+}
+
+/// Implements the methods of `AddressRepository` for a wrapper of a repository
+macro_rules! repository_methods {
+    ($name:ident) => {
+        #[pymethods]
+        impl $name {
+            #[new]
+            fn new() -> Self {
+                $name(Default::default())
+            }
+
+            /// Saves a new address and returns its id
+            fn save(&mut self, address: PyAddress) -> PyResult<String> {
+                let id = self.0.save(&address.into()).map_err(repository_error)?;
+                Ok(id.to_string())
+            }
+
+            fn update(&mut self, id: &str, address: PyAddress) -> PyResult<()> {
+                let id = self.existing(id)?;
+                self.0.update(id, &address.into()).map_err(repository_error)
+            }
+
+            /// Applies a JSON Merge Patch (dict) or JSON Patch (list), returns the
+            /// updated address
+            fn patch(&mut self, id: &str, changes: &Bound<'_, PyAny>) -> PyResult<PyAddress> {
+                let id = self.existing(id)?;
+                let patch = patch_from(changes)?;
+                Ok(self.0.patch(id, &patch).map_err(repository_error)?.into())
+            }
+
+            fn delete(&mut self, id: &str) -> PyResult<()> {
+                let id = self.existing(id)?;
+                self.0.delete(id).map_err(repository_error)
+            }
+
+            /// Returns the address, `None` when no address has this id
+            fn get(&self, id: &str) -> PyResult<Option<PyAddress>> {
+                Ok(self.0.get(parse_id(id)?).map(|x| x.into()))
+            }
+
+            fn list(&self) -> Vec<PyAddress> {
+                self.0.list().into_iter().map(|x| x.into()).collect()
+            }
+
+            /// Returns `(id, address)` tuples
+            fn entries(&self) -> Vec<(String, PyAddress)> {
+                self.0
+                    .entries()
+                    .into_iter()
+                    .map(|(id, x)| (id.to_string(), x.into()))
+                    .collect()
+            }
+
+            fn __len__(&self) -> usize {
+                self.0.entries().len()
+            }
+
+            fn __getitem__(&self, id: &str) -> PyResult<PyAddress> {
+                let id = self.existing(id)?;
+                self.get(&id.to_string())?
+                    .ok_or_else(|| NotFoundError::new_err(id.to_string()))
+            }
+        }
+
+        impl $name {
+            /// Parses an id, raises `NotFoundError` when no address has it
+            fn existing(&self, id: &str) -> PyResult<Uuid> {
+                let id = parse_id(id)?;
+                match self.0.get(id) {
+                    Some(_) => Ok(id),
+                    None => Err(NotFoundError::new_err(format!("No address with id `{id}`"))),
+                }
+            }
+        }
+    };
+}
+
+/// Addresses kept in memory, lost when the object is dropped
+#[pyclass(name = "InMemoryRepository", module = "address")]
+struct PyInMemoryRepository(InMemoryRepository);
+
+repository_methods!(PyInMemoryRepository);
+
+/// Addresses kept in `addresses.json`, in the working directory like the CLI
+#[pyclass(name = "JsonFileRepository", module = "address")]
+struct PyJsonFileRepository(JsonFileRepository);
+
+repository_methods!(PyJsonFileRepository);
+
+/// Conversion of postal addresses between ISO 20022 and NF Z10-011
+#[pymodule]
+#[pyo3(name = "address")]
+fn address_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add_class::<PyAddress>()?;
+    m.add_class::<PyInMemoryRepository>()?;
+    m.add_class::<PyJsonFileRepository>()?;
+    m.add("AddressError", py.get_type::<AddressError>())?;
+    m.add("ParseError", py.get_type::<ParseError>())?;
+    m.add("ValidationError", py.get_type::<ValidationError>())?;
+    m.add("RenderError", py.get_type::<RenderError>())?;
+    m.add("NotFoundError", py.get_type::<NotFoundError>())?;
+    m.add("RepositoryError", py.get_type::<RepositoryError>())?;
+    Ok(())
+}
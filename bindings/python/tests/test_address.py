@@ -0,0 +1,114 @@
+import json
+import os
+import tempfile
+import unittest
+
+import address
+from address import Address
+
+NF = """Monsieur Jean DURAND
+Chez Mireille COPEAU Appartement 2
+Entrée A Bâtiment Jonquille
+25 RUE DE L'EGLISE
+CAUDOS
+33380 MIOS"""
+
+
+def durand():
+    return Address(
+        name="Monsieur Jean DURAND",
+        street_name="25D RUE DES FLEURS",
+        post_code="33500",
+        town_name="LIBOURNE",
+        country="FR",
+    )
+
+
+class TestAddress(unittest.TestCase):
+    def test_fields(self):
+        a = durand()
+        self.assertEqual(a.town_name, "LIBOURNE")
+        self.assertIsNone(a.floor)
+        a.floor = "3E ETAGE"
+        self.assertEqual(a.floor, "3E ETAGE")
+        self.assertEqual(len(Address.fields), 15)
+        self.assertEqual(durand(), durand())
+        self.assertNotEqual(a, durand())
+        self.assertIn("town_name='LIBOURNE'", repr(a))
+
+    def test_dict_and_json(self):
+        a = durand()
+        d = a.to_dict()
+        self.assertEqual(d["post_code"], "33500")
+        self.assertIsNone(d["department"])
+        self.assertEqual(Address.from_dict(d), a)
+        self.assertEqual(Address.from_json(a.to_json()), a)
+        with self.assertRaises(address.ParseError):
+            Address.from_dict({"city": "LIBOURNE"})
+
+    def test_iso20022(self):
+        xml = durand().to_iso20022()
+        self.assertIn("<TwnNm>LIBOURNE</TwnNm>", xml)
+        self.assertEqual(Address.from_iso20022(xml).town_name, "LIBOURNE")
+        with self.assertRaises(address.ParseError):
+            Address.from_iso20022("<PstlAdr>")
+
+    def test_nf(self):
+        a = Address.from_nf(NF + "\nFRANCE")
+        self.assertEqual(a.post_code, "33380")
+        self.assertEqual(a.town_name, "MIOS")
+        self.assertIn("33500 LIBOURNE", durand().to_nf())
+
+    def test_nf_other_countries(self):
+        a = Address(post_code="1012 LG", town_name="AMSTERDAM", country="NL")
+        self.assertTrue(a.to_nf().endswith("NETHERLANDS"))
+        self.assertEqual(Address.from_nf(a.to_nf()).country, "NL")
+        with self.assertRaises(address.RenderError):
+            Address(post_code="1", town_name="X", country="ZZ").to_nf()
+        self.assertTrue(issubclass(address.RenderError, address.AddressError))
+
+    def test_validate(self):
+        durand().validate()
+        a = durand()
+        a.building_number = "1" * 17
+        with self.assertRaises(address.ValidationError):
+            a.validate()
+        self.assertTrue(issubclass(address.ValidationError, address.AddressError))
+
+
+class TestRepository(unittest.TestCase):
+    def test_in_memory(self):
+        repository = address.InMemoryRepository()
+        id = repository.save(durand())
+        self.assertEqual(len(repository), 1)
+        self.assertEqual(repository.get(id), durand())
+        self.assertEqual(repository.entries(), [(id, durand())])
+
+        patched = repository.patch(id, {"floor": "3E ETAGE", "name": None})
+        self.assertEqual(patched.floor, "3E ETAGE")
+        self.assertIsNone(repository[id].name)
+        repository.patch(id, [{"op": "remove", "path": "/floor"}])
+        self.assertIsNone(repository[id].floor)
+
+        repository.delete(id)
+        self.assertIsNone(repository.get(id))
+        with self.assertRaises(address.NotFoundError):
+            repository.delete(id)
+        with self.assertRaises(ValueError):
+            repository.get("not an id")
+
+    def test_json_file(self):
+        cwd = os.getcwd()
+        with tempfile.TemporaryDirectory() as directory:
+            os.chdir(directory)
+            try:
+                id = address.JsonFileRepository().save(durand())
+                with open("addresses.json") as f:
+                    self.assertEqual(json.load(f)[id]["town_name"], "LIBOURNE")
+                self.assertEqual(address.JsonFileRepository()[id], durand())
+            finally:
+                os.chdir(cwd)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,312 @@
+use std::error::Error;
+
+use address::common::Result;
+use address::models::address::Address;
+use address::models::address_iso_20022::ISO_20022;
+use address::models::address_nf_z10_011_enterprise::NF_Z10_011_Enterprise;
+use address::models::address_nf_z10_011_individual::NF_Z10_011_Individual;
+use address::models::address_patch::AddressPatch;
+use address::models::validate::Validate;
+use address::repositories::address_repository::AddressRepository;
+use address::repositories::key_value_repository::{self, KeyValueStore};
+use serde::Serialize;
+use uuid::Uuid;
+use wasm_bindgen::prelude::*;
+
+/// Formats of the parsed and rendered strings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// JSON object of the `Address` fields
+    Json,
+    /// ISO 20022 `PstlAdr` XML element
+    Iso20022,
+    /// NF Z10-011 lines of an individual
+    Nf,
+    /// NF Z10-011 lines of a company
+    NfEnterprise,
+}
+
+impl std::str::FromStr for Format {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "json" => Ok(Format::Json),
+            "iso20022" => Ok(Format::Iso20022),
+            "nf" => Ok(Format::Nf),
+            "nf-enterprise" => Ok(Format::NfEnterprise),
+            _ => Err(
+                format!("Unknown format `{s}`, expected json, iso20022, nf or nf-enterprise")
+                    .into(),
+            ),
+        }
+    }
+}
+
+/// Reads an address, checking the rules of the format when `validate` is set
+fn parse_str(input: &str, format: Format, validate: bool) -> Result<Address> {
+    Ok(match format {
+        Format::Json => serde_json::from_str(input)?,
+        Format::Iso20022 => {
+            let iso: ISO_20022 = input.parse()?;
+            if validate {
+                iso.validate()?;
+            }
+            iso.try_into()?
+        }
+        Format::Nf => {
+            let nf: NF_Z10_011_Individual = input.parse()?;
+            if validate {
+                nf.validate()?;
+            }
+            nf.try_into()?
+        }
+        Format::NfEnterprise => {
+            let nf: NF_Z10_011_Enterprise = input.parse()?;
+            if validate {
+                nf.validate()?;
+            }
+            nf.try_into()?
+        }
+    })
+}
+
+fn render_str(address: &Address, format: Format) -> Result<String> {
+    let address = address.clone();
+    Ok(match format {
+        Format::Json => serde_json::to_string_pretty(&address)?,
+        Format::Iso20022 => ISO_20022::try_from(address)?.to_xml()?,
+        Format::Nf => NF_Z10_011_Individual::try_from(address)?.lines.join("\n"),
+        Format::NfEnterprise => NF_Z10_011_Enterprise::try_from(address)?.lines.join("\n"),
+    })
+}
+
+/// Checks an address against the rules of `ISO_20022::validate` for JSON and
+/// ISO 20022, of the NF `Validate` implementations for NF Z10-011
+fn validate_address(address: &Address, format: Format) -> Result<()> {
+    let address = address.clone();
+    match format {
+        Format::Json | Format::Iso20022 => ISO_20022::try_from(address)?.validate(),
+        Format::Nf => NF_Z10_011_Individual::try_from(address)?.validate(),
+        Format::NfEnterprise => NF_Z10_011_Enterprise::try_from(address)?.validate(),
+    }
+}
+
+fn js_error(e: impl std::fmt::Display) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+/// Converts to a plain JS object, `None` becoming `null`
+fn to_js<T: Serialize>(value: &T) -> std::result::Result<JsValue, JsError> {
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    value.serialize(&serializer).map_err(js_error)
+}
+
+fn from_js(address: JsValue) -> std::result::Result<Address, JsError> {
+    serde_wasm_bindgen::from_value(address).map_err(js_error)
+}
+
+/// Parses `input` in `format` (`json`, `iso20022`, `nf` or `nf-enterprise`) and returns
+/// the address object, checking the rules of the format when `validate` is set
+#[wasm_bindgen]
+pub fn parse(
+    input: &str,
+    format: &str,
+    validate: Option<bool>,
+) -> std::result::Result<JsValue, JsError> {
+    let format: Format = format.parse().map_err(js_error)?;
+    let address = parse_str(input, format, validate.unwrap_or(false)).map_err(js_error)?;
+    to_js(&address)
+}
+
+/// Renders an address object in `format`
+#[wasm_bindgen]
+pub fn render(address: JsValue, format: &str) -> std::result::Result<String, JsError> {
+    let format: Format = format.parse().map_err(js_error)?;
+    render_str(&from_js(address)?, format).map_err(js_error)
+}
+
+/// Converts `input` from a format to another
+#[wasm_bindgen]
+pub fn convert(input: &str, from: &str, to: &str) -> std::result::Result<String, JsError> {
+    let from: Format = from.parse().map_err(js_error)?;
+    let to: Format = to.parse().map_err(js_error)?;
+    let address = parse_str(input, from, false).map_err(js_error)?;
+    render_str(&address, to).map_err(js_error)
+}
+
+/// Checks an address object against the rules of `format`, returns the broken rule or
+/// `undefined` when the address is valid
+#[wasm_bindgen]
+pub fn validate(address: JsValue, format: &str) -> std::result::Result<Option<String>, JsError> {
+    let format: Format = format.parse().map_err(js_error)?;
+    Ok(validate_address(&from_js(address)?, format)
+        .err()
+        .map(|e| e.to_string()))
+}
+
+/// Names of the fields of an address object
+#[wasm_bindgen]
+pub fn fields() -> Vec<String> {
+    Address::FIELDS.iter().map(|x| x.to_string()).collect()
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const KEY_VALUE_STORE: &'static str = r#"
+export interface KeyValueStore {
+    get(key: string): string | null | undefined;
+    set(key: string, value: string): void;
+    remove(key: string): void;
+    keys(): string[];
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// Callbacks provided by the page, e.g. over `localStorage`
+    #[wasm_bindgen(typescript_type = "KeyValueStore")]
+    pub type JsKeyValueStore;
+
+    #[wasm_bindgen(method, catch, js_name = get)]
+    fn get_item(this: &JsKeyValueStore, key: &str) -> std::result::Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = set)]
+    fn set_item(this: &JsKeyValueStore, key: &str, value: &str)
+        -> std::result::Result<(), JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = remove)]
+    fn remove_item(this: &JsKeyValueStore, key: &str) -> std::result::Result<(), JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = keys)]
+    fn item_keys(this: &JsKeyValueStore) -> std::result::Result<JsValue, JsValue>;
+}
+
+/// Message of an exception thrown by a callback
+fn callback_error(e: JsValue) -> Box<dyn Error> {
+    let message = match e.dyn_ref::<js_sys::Error>() {
+        Some(error) => error.message().into(),
+        None => e.as_string().unwrap_or_default(),
+    };
+    format!("The key-value store failed: {message}").into()
+}
+
+impl KeyValueStore for JsKeyValueStore {
+    fn get(&self, key: &str) -> Option<String> {
+        self.get_item(key).ok()?.as_string()
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        self.set_item(key, value).map_err(callback_error)
+    }
+
+    fn remove(&mut self, key: &str) -> Result<()> {
+        self.remove_item(key).map_err(callback_error)
+    }
+
+    fn keys(&self) -> Vec<String> {
+        match self.item_keys() {
+            Ok(keys) => js_sys::Array::from(&keys)
+                .iter()
+                .filter_map(|x| x.as_string())
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+fn parse_id(id: &str) -> std::result::Result<Uuid, JsError> {
+    Uuid::parse_str(id).map_err(|e| JsError::new(&format!("Invalid id `{id}`: {e}")))
+}
+
+/// Addresses stored through the callbacks of a `KeyValueStore`, under `address:<id>`
+#[wasm_bindgen]
+pub struct KeyValueRepository(key_value_repository::KeyValueRepository<JsKeyValueStore>);
+
+#[wasm_bindgen]
+impl KeyValueRepository {
+    #[wasm_bindgen(constructor)]
+    pub fn new(store: JsKeyValueStore) -> Self {
+        KeyValueRepository(key_value_repository::KeyValueRepository::new(store))
+    }
+
+    /// Saves a new address and returns its id
+    pub fn save(&mut self, address: JsValue) -> std::result::Result<String, JsError> {
+        let id = self.0.save(&from_js(address)?).map_err(js_error)?;
+        Ok(id.to_string())
+    }
+
+    pub fn update(&mut self, id: &str, address: JsValue) -> std::result::Result<(), JsError> {
+        self.0
+            .update(parse_id(id)?, &from_js(address)?)
+            .map_err(js_error)
+    }
+
+    /// Applies a JSON Merge Patch (object) or JSON Patch (array), returns the updated
+    /// address
+    pub fn patch(&mut self, id: &str, changes: JsValue) -> std::result::Result<JsValue, JsError> {
+        let json =
+            js_sys::JSON::stringify(&changes).map_err(|_| JsError::new("The patch is not JSON"))?;
+        let patch: AddressPatch = String::from(json).parse().map_err(js_error)?;
+        let address = self.0.patch(parse_id(id)?, &patch).map_err(js_error)?;
+        to_js(&address)
+    }
+
+    pub fn delete(&mut self, id: &str) -> std::result::Result<(), JsError> {
+        self.0.delete(parse_id(id)?).map_err(js_error)
+    }
+
+    /// Returns the address, `undefined` when no address has this id
+    pub fn get(&self, id: &str) -> std::result::Result<JsValue, JsError> {
+        match self.0.get(parse_id(id)?) {
+            Some(address) => to_js(&address),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+
+    pub fn list(&self) -> std::result::Result<JsValue, JsError> {
+        to_js(&self.0.list())
+    }
+
+    /// Returns `[id, address]` pairs
+    pub fn entries(&self) -> std::result::Result<JsValue, JsError> {
+        let entries: Vec<(String, Address)> = self
+            .0
+            .entries()
+            .into_iter()
+            .map(|(id, address)| (id.to_string(), address))
+            .collect();
+        to_js(&entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NF: &str = "Monsieur Jean DURAND\n\n\n25 RUE DES FLEURS\n\n33500 LIBOURNE\nFRANCE";
+
+    #[test]
+    fn test_convert() {
+        let address = parse_str(NF, Format::Nf, true).expect("error");
+        assert_eq!(address.town_name, "LIBOURNE");
+
+        let xml = render_str(&address, Format::Iso20022).expect("error");
+        assert!(xml.contains("<PstCd>33500</PstCd>"));
+        let back = parse_str(&xml, Format::Iso20022, true).expect("error");
+        assert_eq!(back.street_name, address.street_name);
+
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut address = parse_str(NF, Format::Nf, false).expect("error");
+        assert!(validate_address(&address, Format::Iso20022).is_ok());
+
+        address.building_number = Some("1".repeat(17));
+        assert!(validate_address(&address, Format::Iso20022).is_err());
+
+        address.street_name = Some("R".repeat(40));
+        assert!(validate_address(&address, Format::Nf).is_err());
+    }
+}
@@ -3,30 +3,33 @@ use std::error::Error;
 /// A type alias for `Result` to avoid having to write the error type all the time
 pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
-/// Converts a country name to its corresponding ISO 3166-1 alpha-2 code.
+/// Converts a country name or ISO 3166-1 alpha-3 code to its alpha-2 code.
 ///
 /// # Arguments
 ///
-/// * `country` - Country name (case insensitive).
+/// * `country` - Country name in any known language, or three-letter code (case
+///   insensitive).
 ///
 /// # Returns
 ///
-/// * Two-letter country code.
+/// * Two-letter country code, or an error when the country is unknown.
 ///
 /// # Example
 ///
 /// ```
 /// use address::common::country_to_alpha2;
-/// let code = country_to_alpha2("France");
-/// assert_eq!(code, "FR");
-/// ```
-pub fn country_to_alpha2(country: &str) -> &str {
-    match country.to_uppercase().as_ref() {
-        "FRANCE" => "FR",
-        "PORTUGAL" => "PT",
-        "GERMANY" => "DE",
-        _ => todo!("Implement more countries"),
-    }
+/// assert_eq!(country_to_alpha2("France").unwrap(), "FR");
+/// assert_eq!(country_to_alpha2("NLD").unwrap(), "NL");
+/// assert!(country_to_alpha2("Atlantis").is_err());
+/// ```
+pub fn country_to_alpha2(country: &str) -> Result<&'static str> {
+    let upper = country.trim().to_uppercase();
+    COUNTRIES
+        .iter()
+        .find(|(_, alpha3, _)| *alpha3 == upper)
+        .map(|(alpha2, _, _)| *alpha2)
+        .or_else(|| find_country(&upper))
+        .ok_or_else(|| format!("Unknown country `{}`", country.trim()).into())
 }
 
 /// Converts an ISO 3166-1 alpha-2 country code to its corresponding country name.
@@ -37,20 +40,729 @@ pub fn country_to_alpha2(country: &str) -> &str {
 ///
 /// # Returns
 ///
-/// * Full country name.
+/// * English short name in capitals, or an error when the code is unknown.
 ///
 /// # Example
 ///
 /// ```
 /// use address::common::alpha2_to_country;
-/// let country = alpha2_to_country("FR");
-/// assert_eq!(country, "FRANCE");
-/// ```
-pub fn alpha2_to_country(alpha2: &str) -> &str {
-    match alpha2.to_uppercase().as_ref() {
-        "FR" => "FRANCE",
-        "PT" => "PORTUGAL",
-        "DE" => "GERMANY",
-        _ => todo!("Implement more countries"),
+/// assert_eq!(alpha2_to_country("FR").unwrap(), "FRANCE");
+/// assert_eq!(alpha2_to_country("nl").unwrap(), "NETHERLANDS");
+/// assert!(alpha2_to_country("N").is_err());
+/// ```
+pub fn alpha2_to_country(alpha2: &str) -> Result<&'static str> {
+    let upper = alpha2.trim().to_uppercase();
+    COUNTRIES
+        .iter()
+        .find(|(code, _, _)| *code == upper)
+        .map(|(_, _, name)| *name)
+        .ok_or_else(|| format!("Unknown country code `{}`", alpha2.trim()).into())
+}
+
+/// Normalizes a country given as a name or as an ISO 3166-1 alpha-2 or alpha-3 code.
+///
+/// # Arguments
+///
+/// * `country` - Country name or code (case insensitive).
+///
+/// # Returns
+///
+/// * Two-letter country code, an empty string when `country` is empty, or an error
+///   when the country is unknown.
+///
+/// # Example
+///
+/// ```
+/// use address::common::normalize_country;
+/// assert_eq!(normalize_country("fr").unwrap(), "FR");
+/// assert_eq!(normalize_country("Portugal").unwrap(), "PT");
+/// assert_eq!(normalize_country("USA").unwrap(), "US");
+/// assert!(normalize_country("XX").is_err());
+/// ```
+pub fn normalize_country(country: &str) -> Result<String> {
+    let country = country.trim();
+    if country.is_empty() {
+        return Ok(String::new());
+    }
+    if country.chars().count() == 2 && alpha2_to_country(country).is_ok() {
+        return Ok(country.to_uppercase());
     }
+    Ok(country_to_alpha2(country)?.to_string())
+}
+
+/// ISO 3166-1 countries: alpha-2 code, alpha-3 code and English short name in capitals
+const COUNTRIES: &[(&str, &str, &str)] = &[
+    ("AD", "AND", "ANDORRA"),
+    ("AE", "ARE", "UNITED ARAB EMIRATES"),
+    ("AF", "AFG", "AFGHANISTAN"),
+    ("AG", "ATG", "ANTIGUA AND BARBUDA"),
+    ("AI", "AIA", "ANGUILLA"),
+    ("AL", "ALB", "ALBANIA"),
+    ("AM", "ARM", "ARMENIA"),
+    ("AO", "AGO", "ANGOLA"),
+    ("AQ", "ATA", "ANTARCTICA"),
+    ("AR", "ARG", "ARGENTINA"),
+    ("AS", "ASM", "AMERICAN SAMOA"),
+    ("AT", "AUT", "AUSTRIA"),
+    ("AU", "AUS", "AUSTRALIA"),
+    ("AW", "ABW", "ARUBA"),
+    ("AX", "ALA", "ÅLAND ISLANDS"),
+    ("AZ", "AZE", "AZERBAIJAN"),
+    ("BA", "BIH", "BOSNIA AND HERZEGOVINA"),
+    ("BB", "BRB", "BARBADOS"),
+    ("BD", "BGD", "BANGLADESH"),
+    ("BE", "BEL", "BELGIUM"),
+    ("BF", "BFA", "BURKINA FASO"),
+    ("BG", "BGR", "BULGARIA"),
+    ("BH", "BHR", "BAHRAIN"),
+    ("BI", "BDI", "BURUNDI"),
+    ("BJ", "BEN", "BENIN"),
+    ("BL", "BLM", "SAINT BARTHÉLEMY"),
+    ("BM", "BMU", "BERMUDA"),
+    ("BN", "BRN", "BRUNEI"),
+    ("BO", "BOL", "BOLIVIA"),
+    ("BQ", "BES", "BONAIRE, SINT EUSTATIUS AND SABA"),
+    ("BR", "BRA", "BRAZIL"),
+    ("BS", "BHS", "BAHAMAS"),
+    ("BT", "BTN", "BHUTAN"),
+    ("BV", "BVT", "BOUVET ISLAND"),
+    ("BW", "BWA", "BOTSWANA"),
+    ("BY", "BLR", "BELARUS"),
+    ("BZ", "BLZ", "BELIZE"),
+    ("CA", "CAN", "CANADA"),
+    ("CC", "CCK", "COCOS ISLANDS"),
+    ("CD", "COD", "DEMOCRATIC REPUBLIC OF THE CONGO"),
+    ("CF", "CAF", "CENTRAL AFRICAN REPUBLIC"),
+    ("CG", "COG", "CONGO"),
+    ("CH", "CHE", "SWITZERLAND"),
+    ("CI", "CIV", "CÔTE D'IVOIRE"),
+    ("CK", "COK", "COOK ISLANDS"),
+    ("CL", "CHL", "CHILE"),
+    ("CM", "CMR", "CAMEROON"),
+    ("CN", "CHN", "CHINA"),
+    ("CO", "COL", "COLOMBIA"),
+    ("CR", "CRI", "COSTA RICA"),
+    ("CU", "CUB", "CUBA"),
+    ("CV", "CPV", "CABO VERDE"),
+    ("CW", "CUW", "CURAÇAO"),
+    ("CX", "CXR", "CHRISTMAS ISLAND"),
+    ("CY", "CYP", "CYPRUS"),
+    ("CZ", "CZE", "CZECHIA"),
+    ("DE", "DEU", "GERMANY"),
+    ("DJ", "DJI", "DJIBOUTI"),
+    ("DK", "DNK", "DENMARK"),
+    ("DM", "DMA", "DOMINICA"),
+    ("DO", "DOM", "DOMINICAN REPUBLIC"),
+    ("DZ", "DZA", "ALGERIA"),
+    ("EC", "ECU", "ECUADOR"),
+    ("EE", "EST", "ESTONIA"),
+    ("EG", "EGY", "EGYPT"),
+    ("EH", "ESH", "WESTERN SAHARA"),
+    ("ER", "ERI", "ERITREA"),
+    ("ES", "ESP", "SPAIN"),
+    ("ET", "ETH", "ETHIOPIA"),
+    ("FI", "FIN", "FINLAND"),
+    ("FJ", "FJI", "FIJI"),
+    ("FK", "FLK", "FALKLAND ISLANDS"),
+    ("FM", "FSM", "MICRONESIA"),
+    ("FO", "FRO", "FAROE ISLANDS"),
+    ("FR", "FRA", "FRANCE"),
+    ("GA", "GAB", "GABON"),
+    ("GB", "GBR", "UNITED KINGDOM"),
+    ("GD", "GRD", "GRENADA"),
+    ("GE", "GEO", "GEORGIA"),
+    ("GF", "GUF", "FRENCH GUIANA"),
+    ("GG", "GGY", "GUERNSEY"),
+    ("GH", "GHA", "GHANA"),
+    ("GI", "GIB", "GIBRALTAR"),
+    ("GL", "GRL", "GREENLAND"),
+    ("GM", "GMB", "GAMBIA"),
+    ("GN", "GIN", "GUINEA"),
+    ("GP", "GLP", "GUADELOUPE"),
+    ("GQ", "GNQ", "EQUATORIAL GUINEA"),
+    ("GR", "GRC", "GREECE"),
+    ("GS", "SGS", "SOUTH GEORGIA AND THE SOUTH SANDWICH ISLANDS"),
+    ("GT", "GTM", "GUATEMALA"),
+    ("GU", "GUM", "GUAM"),
+    ("GW", "GNB", "GUINEA-BISSAU"),
+    ("GY", "GUY", "GUYANA"),
+    ("HK", "HKG", "HONG KONG"),
+    ("HM", "HMD", "HEARD ISLAND AND MCDONALD ISLANDS"),
+    ("HN", "HND", "HONDURAS"),
+    ("HR", "HRV", "CROATIA"),
+    ("HT", "HTI", "HAITI"),
+    ("HU", "HUN", "HUNGARY"),
+    ("ID", "IDN", "INDONESIA"),
+    ("IE", "IRL", "IRELAND"),
+    ("IL", "ISR", "ISRAEL"),
+    ("IM", "IMN", "ISLE OF MAN"),
+    ("IN", "IND", "INDIA"),
+    ("IO", "IOT", "BRITISH INDIAN OCEAN TERRITORY"),
+    ("IQ", "IRQ", "IRAQ"),
+    ("IR", "IRN", "IRAN"),
+    ("IS", "ISL", "ICELAND"),
+    ("IT", "ITA", "ITALY"),
+    ("JE", "JEY", "JERSEY"),
+    ("JM", "JAM", "JAMAICA"),
+    ("JO", "JOR", "JORDAN"),
+    ("JP", "JPN", "JAPAN"),
+    ("KE", "KEN", "KENYA"),
+    ("KG", "KGZ", "KYRGYZSTAN"),
+    ("KH", "KHM", "CAMBODIA"),
+    ("KI", "KIR", "KIRIBATI"),
+    ("KM", "COM", "COMOROS"),
+    ("KN", "KNA", "SAINT KITTS AND NEVIS"),
+    ("KP", "PRK", "NORTH KOREA"),
+    ("KR", "KOR", "SOUTH KOREA"),
+    ("KW", "KWT", "KUWAIT"),
+    ("KY", "CYM", "CAYMAN ISLANDS"),
+    ("KZ", "KAZ", "KAZAKHSTAN"),
+    ("LA", "LAO", "LAOS"),
+    ("LB", "LBN", "LEBANON"),
+    ("LC", "LCA", "SAINT LUCIA"),
+    ("LI", "LIE", "LIECHTENSTEIN"),
+    ("LK", "LKA", "SRI LANKA"),
+    ("LR", "LBR", "LIBERIA"),
+    ("LS", "LSO", "LESOTHO"),
+    ("LT", "LTU", "LITHUANIA"),
+    ("LU", "LUX", "LUXEMBOURG"),
+    ("LV", "LVA", "LATVIA"),
+    ("LY", "LBY", "LIBYA"),
+    ("MA", "MAR", "MOROCCO"),
+    ("MC", "MCO", "MONACO"),
+    ("MD", "MDA", "MOLDOVA"),
+    ("ME", "MNE", "MONTENEGRO"),
+    ("MF", "MAF", "SAINT MARTIN"),
+    ("MG", "MDG", "MADAGASCAR"),
+    ("MH", "MHL", "MARSHALL ISLANDS"),
+    ("MK", "MKD", "NORTH MACEDONIA"),
+    ("ML", "MLI", "MALI"),
+    ("MM", "MMR", "MYANMAR"),
+    ("MN", "MNG", "MONGOLIA"),
+    ("MO", "MAC", "MACAO"),
+    ("MP", "MNP", "NORTHERN MARIANA ISLANDS"),
+    ("MQ", "MTQ", "MARTINIQUE"),
+    ("MR", "MRT", "MAURITANIA"),
+    ("MS", "MSR", "MONTSERRAT"),
+    ("MT", "MLT", "MALTA"),
+    ("MU", "MUS", "MAURITIUS"),
+    ("MV", "MDV", "MALDIVES"),
+    ("MW", "MWI", "MALAWI"),
+    ("MX", "MEX", "MEXICO"),
+    ("MY", "MYS", "MALAYSIA"),
+    ("MZ", "MOZ", "MOZAMBIQUE"),
+    ("NA", "NAM", "NAMIBIA"),
+    ("NC", "NCL", "NEW CALEDONIA"),
+    ("NE", "NER", "NIGER"),
+    ("NF", "NFK", "NORFOLK ISLAND"),
+    ("NG", "NGA", "NIGERIA"),
+    ("NI", "NIC", "NICARAGUA"),
+    ("NL", "NLD", "NETHERLANDS"),
+    ("NO", "NOR", "NORWAY"),
+    ("NP", "NPL", "NEPAL"),
+    ("NR", "NRU", "NAURU"),
+    ("NU", "NIU", "NIUE"),
+    ("NZ", "NZL", "NEW ZEALAND"),
+    ("OM", "OMN", "OMAN"),
+    ("PA", "PAN", "PANAMA"),
+    ("PE", "PER", "PERU"),
+    ("PF", "PYF", "FRENCH POLYNESIA"),
+    ("PG", "PNG", "PAPUA NEW GUINEA"),
+    ("PH", "PHL", "PHILIPPINES"),
+    ("PK", "PAK", "PAKISTAN"),
+    ("PL", "POL", "POLAND"),
+    ("PM", "SPM", "SAINT PIERRE AND MIQUELON"),
+    ("PN", "PCN", "PITCAIRN"),
+    ("PR", "PRI", "PUERTO RICO"),
+    ("PS", "PSE", "PALESTINE"),
+    ("PT", "PRT", "PORTUGAL"),
+    ("PW", "PLW", "PALAU"),
+    ("PY", "PRY", "PARAGUAY"),
+    ("QA", "QAT", "QATAR"),
+    ("RE", "REU", "RÉUNION"),
+    ("RO", "ROU", "ROMANIA"),
+    ("RS", "SRB", "SERBIA"),
+    ("RU", "RUS", "RUSSIA"),
+    ("RW", "RWA", "RWANDA"),
+    ("SA", "SAU", "SAUDI ARABIA"),
+    ("SB", "SLB", "SOLOMON ISLANDS"),
+    ("SC", "SYC", "SEYCHELLES"),
+    ("SD", "SDN", "SUDAN"),
+    ("SE", "SWE", "SWEDEN"),
+    ("SG", "SGP", "SINGAPORE"),
+    ("SH", "SHN", "SAINT HELENA"),
+    ("SI", "SVN", "SLOVENIA"),
+    ("SJ", "SJM", "SVALBARD AND JAN MAYEN"),
+    ("SK", "SVK", "SLOVAKIA"),
+    ("SL", "SLE", "SIERRA LEONE"),
+    ("SM", "SMR", "SAN MARINO"),
+    ("SN", "SEN", "SENEGAL"),
+    ("SO", "SOM", "SOMALIA"),
+    ("SR", "SUR", "SURINAME"),
+    ("SS", "SSD", "SOUTH SUDAN"),
+    ("ST", "STP", "SAO TOME AND PRINCIPE"),
+    ("SV", "SLV", "EL SALVADOR"),
+    ("SX", "SXM", "SINT MAARTEN"),
+    ("SY", "SYR", "SYRIA"),
+    ("SZ", "SWZ", "ESWATINI"),
+    ("TC", "TCA", "TURKS AND CAICOS ISLANDS"),
+    ("TD", "TCD", "CHAD"),
+    ("TF", "ATF", "FRENCH SOUTHERN TERRITORIES"),
+    ("TG", "TGO", "TOGO"),
+    ("TH", "THA", "THAILAND"),
+    ("TJ", "TJK", "TAJIKISTAN"),
+    ("TK", "TKL", "TOKELAU"),
+    ("TL", "TLS", "TIMOR-LESTE"),
+    ("TM", "TKM", "TURKMENISTAN"),
+    ("TN", "TUN", "TUNISIA"),
+    ("TO", "TON", "TONGA"),
+    ("TR", "TUR", "TÜRKIYE"),
+    ("TT", "TTO", "TRINIDAD AND TOBAGO"),
+    ("TV", "TUV", "TUVALU"),
+    ("TW", "TWN", "TAIWAN"),
+    ("TZ", "TZA", "TANZANIA"),
+    ("UA", "UKR", "UKRAINE"),
+    ("UG", "UGA", "UGANDA"),
+    ("UM", "UMI", "UNITED STATES MINOR OUTLYING ISLANDS"),
+    ("US", "USA", "UNITED STATES"),
+    ("UY", "URY", "URUGUAY"),
+    ("UZ", "UZB", "UZBEKISTAN"),
+    ("VA", "VAT", "VATICAN"),
+    ("VC", "VCT", "SAINT VINCENT AND THE GRENADINES"),
+    ("VE", "VEN", "VENEZUELA"),
+    ("VG", "VGB", "BRITISH VIRGIN ISLANDS"),
+    ("VI", "VIR", "UNITED STATES VIRGIN ISLANDS"),
+    ("VN", "VNM", "VIETNAM"),
+    ("VU", "VUT", "VANUATU"),
+    ("WF", "WLF", "WALLIS AND FUTUNA"),
+    ("WS", "WSM", "SAMOA"),
+    ("YE", "YEM", "YEMEN"),
+    ("YT", "MYT", "MAYOTTE"),
+    ("ZA", "ZAF", "SOUTH AFRICA"),
+    ("ZM", "ZMB", "ZAMBIA"),
+    ("ZW", "ZWE", "ZIMBABWE"),
+];
+
+/// Other English names of countries, e.g. ISO 3166-1 full and official names
+const COUNTRY_ALIASES: &[(&str, &str)] = &[
+    ("PRINCIPALITY OF ANDORRA", "AD"),
+    ("ISLAMIC REPUBLIC OF AFGHANISTAN", "AF"),
+    ("REPUBLIC OF ALBANIA", "AL"),
+    ("REPUBLIC OF ARMENIA", "AM"),
+    ("REPUBLIC OF ANGOLA", "AO"),
+    ("ARGENTINE REPUBLIC", "AR"),
+    ("REPUBLIC OF AUSTRIA", "AT"),
+    ("REPUBLIC OF AZERBAIJAN", "AZ"),
+    ("REPUBLIC OF BOSNIA AND HERZEGOVINA", "BA"),
+    ("PEOPLE'S REPUBLIC OF BANGLADESH", "BD"),
+    ("KINGDOM OF BELGIUM", "BE"),
+    ("REPUBLIC OF BULGARIA", "BG"),
+    ("KINGDOM OF BAHRAIN", "BH"),
+    ("REPUBLIC OF BURUNDI", "BI"),
+    ("REPUBLIC OF BENIN", "BJ"),
+    ("BRUNEI DARUSSALAM", "BN"),
+    ("BOLIVIA, PLURINATIONAL STATE OF", "BO"),
+    ("PLURINATIONAL STATE OF BOLIVIA", "BO"),
+    ("FEDERATIVE REPUBLIC OF BRAZIL", "BR"),
+    ("COMMONWEALTH OF THE BAHAMAS", "BS"),
+    ("KINGDOM OF BHUTAN", "BT"),
+    ("REPUBLIC OF BOTSWANA", "BW"),
+    ("REPUBLIC OF BELARUS", "BY"),
+    ("COCOS (KEELING) ISLANDS", "CC"),
+    ("CONGO, THE DEMOCRATIC REPUBLIC OF THE", "CD"),
+    ("REPUBLIC OF THE CONGO", "CG"),
+    ("SWISS CONFEDERATION", "CH"),
+    ("REPUBLIC OF CÔTE D'IVOIRE", "CI"),
+    ("REPUBLIC OF CHILE", "CL"),
+    ("REPUBLIC OF CAMEROON", "CM"),
+    ("PEOPLE'S REPUBLIC OF CHINA", "CN"),
+    ("REPUBLIC OF COLOMBIA", "CO"),
+    ("REPUBLIC OF COSTA RICA", "CR"),
+    ("REPUBLIC OF CUBA", "CU"),
+    ("REPUBLIC OF CABO VERDE", "CV"),
+    ("REPUBLIC OF CYPRUS", "CY"),
+    ("CZECH REPUBLIC", "CZ"),
+    ("FEDERAL REPUBLIC OF GERMANY", "DE"),
+    ("REPUBLIC OF DJIBOUTI", "DJ"),
+    ("KINGDOM OF DENMARK", "DK"),
+    ("COMMONWEALTH OF DOMINICA", "DM"),
+    ("PEOPLE'S DEMOCRATIC REPUBLIC OF ALGERIA", "DZ"),
+    ("REPUBLIC OF ECUADOR", "EC"),
+    ("REPUBLIC OF ESTONIA", "EE"),
+    ("ARAB REPUBLIC OF EGYPT", "EG"),
+    ("THE STATE OF ERITREA", "ER"),
+    ("KINGDOM OF SPAIN", "ES"),
+    ("FEDERAL DEMOCRATIC REPUBLIC OF ETHIOPIA", "ET"),
+    ("REPUBLIC OF FINLAND", "FI"),
+    ("REPUBLIC OF FIJI", "FJ"),
+    ("FALKLAND ISLANDS (MALVINAS)", "FK"),
+    ("MICRONESIA, FEDERATED STATES OF", "FM"),
+    ("FEDERATED STATES OF MICRONESIA", "FM"),
+    ("FRENCH REPUBLIC", "FR"),
+    ("GABONESE REPUBLIC", "GA"),
+    ("UNITED KINGDOM OF GREAT BRITAIN AND NORTHERN IRELAND", "GB"),
+    ("REPUBLIC OF GHANA", "GH"),
+    ("REPUBLIC OF THE GAMBIA", "GM"),
+    ("REPUBLIC OF GUINEA", "GN"),
+    ("REPUBLIC OF EQUATORIAL GUINEA", "GQ"),
+    ("HELLENIC REPUBLIC", "GR"),
+    ("REPUBLIC OF GUATEMALA", "GT"),
+    ("REPUBLIC OF GUINEA-BISSAU", "GW"),
+    ("REPUBLIC OF GUYANA", "GY"),
+    ("HONG KONG SPECIAL ADMINISTRATIVE REGION OF CHINA", "HK"),
+    ("REPUBLIC OF HONDURAS", "HN"),
+    ("REPUBLIC OF CROATIA", "HR"),
+    ("REPUBLIC OF HAITI", "HT"),
+    ("REPUBLIC OF INDONESIA", "ID"),
+    ("STATE OF ISRAEL", "IL"),
+    ("REPUBLIC OF INDIA", "IN"),
+    ("REPUBLIC OF IRAQ", "IQ"),
+    ("IRAN, ISLAMIC REPUBLIC OF", "IR"),
+    ("ISLAMIC REPUBLIC OF IRAN", "IR"),
+    ("REPUBLIC OF ICELAND", "IS"),
+    ("ITALIAN REPUBLIC", "IT"),
+    ("HASHEMITE KINGDOM OF JORDAN", "JO"),
+    ("REPUBLIC OF KENYA", "KE"),
+    ("KYRGYZ REPUBLIC", "KG"),
+    ("KINGDOM OF CAMBODIA", "KH"),
+    ("REPUBLIC OF KIRIBATI", "KI"),
+    ("UNION OF THE COMOROS", "KM"),
+    ("KOREA, DEMOCRATIC PEOPLE'S REPUBLIC OF", "KP"),
+    ("DEMOCRATIC PEOPLE'S REPUBLIC OF KOREA", "KP"),
+    ("KOREA, REPUBLIC OF", "KR"),
+    ("STATE OF KUWAIT", "KW"),
+    ("REPUBLIC OF KAZAKHSTAN", "KZ"),
+    ("LAO PEOPLE'S DEMOCRATIC REPUBLIC", "LA"),
+    ("LEBANESE REPUBLIC", "LB"),
+    ("PRINCIPALITY OF LIECHTENSTEIN", "LI"),
+    ("DEMOCRATIC SOCIALIST REPUBLIC OF SRI LANKA", "LK"),
+    ("REPUBLIC OF LIBERIA", "LR"),
+    ("KINGDOM OF LESOTHO", "LS"),
+    ("REPUBLIC OF LITHUANIA", "LT"),
+    ("GRAND DUCHY OF LUXEMBOURG", "LU"),
+    ("REPUBLIC OF LATVIA", "LV"),
+    ("KINGDOM OF MOROCCO", "MA"),
+    ("PRINCIPALITY OF MONACO", "MC"),
+    ("MOLDOVA, REPUBLIC OF", "MD"),
+    ("REPUBLIC OF MOLDOVA", "MD"),
+    ("SAINT MARTIN (FRENCH PART)", "MF"),
+    ("REPUBLIC OF MADAGASCAR", "MG"),
+    ("REPUBLIC OF THE MARSHALL ISLANDS", "MH"),
+    ("REPUBLIC OF NORTH MACEDONIA", "MK"),
+    ("REPUBLIC OF MALI", "ML"),
+    ("REPUBLIC OF MYANMAR", "MM"),
+    ("MACAO SPECIAL ADMINISTRATIVE REGION OF CHINA", "MO"),
+    ("COMMONWEALTH OF THE NORTHERN MARIANA ISLANDS", "MP"),
+    ("ISLAMIC REPUBLIC OF MAURITANIA", "MR"),
+    ("REPUBLIC OF MALTA", "MT"),
+    ("REPUBLIC OF MAURITIUS", "MU"),
+    ("REPUBLIC OF MALDIVES", "MV"),
+    ("REPUBLIC OF MALAWI", "MW"),
+    ("UNITED MEXICAN STATES", "MX"),
+    ("REPUBLIC OF MOZAMBIQUE", "MZ"),
+    ("REPUBLIC OF NAMIBIA", "NA"),
+    ("REPUBLIC OF THE NIGER", "NE"),
+    ("FEDERAL REPUBLIC OF NIGERIA", "NG"),
+    ("REPUBLIC OF NICARAGUA", "NI"),
+    ("KINGDOM OF THE NETHERLANDS", "NL"),
+    ("KINGDOM OF NORWAY", "NO"),
+    ("FEDERAL DEMOCRATIC REPUBLIC OF NEPAL", "NP"),
+    ("REPUBLIC OF NAURU", "NR"),
+    ("SULTANATE OF OMAN", "OM"),
+    ("REPUBLIC OF PANAMA", "PA"),
+    ("REPUBLIC OF PERU", "PE"),
+    ("INDEPENDENT STATE OF PAPUA NEW GUINEA", "PG"),
+    ("REPUBLIC OF THE PHILIPPINES", "PH"),
+    ("ISLAMIC REPUBLIC OF PAKISTAN", "PK"),
+    ("REPUBLIC OF POLAND", "PL"),
+    ("PALESTINE, STATE OF", "PS"),
+    ("THE STATE OF PALESTINE", "PS"),
+    ("PORTUGUESE REPUBLIC", "PT"),
+    ("REPUBLIC OF PALAU", "PW"),
+    ("REPUBLIC OF PARAGUAY", "PY"),
+    ("STATE OF QATAR", "QA"),
+    ("REPUBLIC OF SERBIA", "RS"),
+    ("RUSSIAN FEDERATION", "RU"),
+    ("RWANDESE REPUBLIC", "RW"),
+    ("KINGDOM OF SAUDI ARABIA", "SA"),
+    ("REPUBLIC OF SEYCHELLES", "SC"),
+    ("REPUBLIC OF THE SUDAN", "SD"),
+    ("KINGDOM OF SWEDEN", "SE"),
+    ("REPUBLIC OF SINGAPORE", "SG"),
+    ("SAINT HELENA, ASCENSION AND TRISTAN DA CUNHA", "SH"),
+    ("REPUBLIC OF SLOVENIA", "SI"),
+    ("SLOVAK REPUBLIC", "SK"),
+    ("REPUBLIC OF SIERRA LEONE", "SL"),
+    ("REPUBLIC OF SAN MARINO", "SM"),
+    ("REPUBLIC OF SENEGAL", "SN"),
+    ("FEDERAL REPUBLIC OF SOMALIA", "SO"),
+    ("REPUBLIC OF SURINAME", "SR"),
+    ("REPUBLIC OF SOUTH SUDAN", "SS"),
+    ("DEMOCRATIC REPUBLIC OF SAO TOME AND PRINCIPE", "ST"),
+    ("REPUBLIC OF EL SALVADOR", "SV"),
+    ("SINT MAARTEN (DUTCH PART)", "SX"),
+    ("SYRIAN ARAB REPUBLIC", "SY"),
+    ("KINGDOM OF ESWATINI", "SZ"),
+    ("REPUBLIC OF CHAD", "TD"),
+    ("TOGOLESE REPUBLIC", "TG"),
+    ("KINGDOM OF THAILAND", "TH"),
+    ("REPUBLIC OF TAJIKISTAN", "TJ"),
+    ("DEMOCRATIC REPUBLIC OF TIMOR-LESTE", "TL"),
+    ("REPUBLIC OF TUNISIA", "TN"),
+    ("KINGDOM OF TONGA", "TO"),
+    ("REPUBLIC OF TÜRKIYE", "TR"),
+    ("REPUBLIC OF TRINIDAD AND TOBAGO", "TT"),
+    ("TAIWAN, PROVINCE OF CHINA", "TW"),
+    ("TANZANIA, UNITED REPUBLIC OF", "TZ"),
+    ("UNITED REPUBLIC OF TANZANIA", "TZ"),
+    ("REPUBLIC OF UGANDA", "UG"),
+    ("UNITED STATES OF AMERICA", "US"),
+    ("EASTERN REPUBLIC OF URUGUAY", "UY"),
+    ("REPUBLIC OF UZBEKISTAN", "UZ"),
+    ("HOLY SEE (VATICAN CITY STATE)", "VA"),
+    ("VENEZUELA, BOLIVARIAN REPUBLIC OF", "VE"),
+    ("BOLIVARIAN REPUBLIC OF VENEZUELA", "VE"),
+    ("VIRGIN ISLANDS, BRITISH", "VG"),
+    ("VIRGIN ISLANDS, U.S.", "VI"),
+    ("VIRGIN ISLANDS OF THE UNITED STATES", "VI"),
+    ("VIET NAM", "VN"),
+    ("SOCIALIST REPUBLIC OF VIET NAM", "VN"),
+    ("REPUBLIC OF VANUATU", "VU"),
+    ("INDEPENDENT STATE OF SAMOA", "WS"),
+    ("REPUBLIC OF YEMEN", "YE"),
+    ("REPUBLIC OF SOUTH AFRICA", "ZA"),
+    ("REPUBLIC OF ZAMBIA", "ZM"),
+    ("REPUBLIC OF ZIMBABWE", "ZW"),
+    ("UK", "GB"),
+    ("U.K.", "GB"),
+    ("GREAT BRITAIN", "GB"),
+    ("ENGLAND", "GB"),
+    ("SCOTLAND", "GB"),
+    ("WALES", "GB"),
+    ("NORTHERN IRELAND", "GB"),
+    ("USA", "US"),
+    ("U.S.A.", "US"),
+    ("THE NETHERLANDS", "NL"),
+    ("HOLLAND", "NL"),
+    ("TURKEY", "TR"),
+    ("IVORY COAST", "CI"),
+    ("CAPE VERDE", "CV"),
+    ("SWAZILAND", "SZ"),
+    ("MACEDONIA", "MK"),
+    ("BURMA", "MM"),
+    ("EAST TIMOR", "TL"),
+    ("VATICAN CITY", "VA"),
+    ("UAE", "AE"),
+    ("DR CONGO", "CD"),
+];
+
+/// Country names, in capitals, by ISO 3166-1 alpha-2 code and ISO 639-1 language
+const COUNTRY_NAMES: &[(&str, &[(&str, &str)])] = &[
+    (
+        "FR",
+        &[
+            ("en", "FRANCE"),
+            ("fr", "FRANCE"),
+            ("de", "FRANKREICH"),
+            ("pt", "FRANÇA"),
+            ("es", "FRANCIA"),
+            ("it", "FRANCIA"),
+        ],
+    ),
+    (
+        "DE",
+        &[
+            ("en", "GERMANY"),
+            ("fr", "ALLEMAGNE"),
+            ("de", "DEUTSCHLAND"),
+            ("pt", "ALEMANHA"),
+            ("es", "ALEMANIA"),
+            ("it", "GERMANIA"),
+        ],
+    ),
+    (
+        "PT",
+        &[
+            ("en", "PORTUGAL"),
+            ("fr", "PORTUGAL"),
+            ("de", "PORTUGAL"),
+            ("pt", "PORTUGAL"),
+            ("es", "PORTUGAL"),
+            ("it", "PORTOGALLO"),
+        ],
+    ),
+    (
+        "ES",
+        &[
+            ("en", "SPAIN"),
+            ("fr", "ESPAGNE"),
+            ("de", "SPANIEN"),
+            ("pt", "ESPANHA"),
+            ("es", "ESPAÑA"),
+            ("it", "SPAGNA"),
+        ],
+    ),
+    (
+        "IT",
+        &[
+            ("en", "ITALY"),
+            ("fr", "ITALIE"),
+            ("de", "ITALIEN"),
+            ("pt", "ITÁLIA"),
+            ("es", "ITALIA"),
+            ("it", "ITALIA"),
+        ],
+    ),
+    (
+        "BE",
+        &[
+            ("en", "BELGIUM"),
+            ("fr", "BELGIQUE"),
+            ("de", "BELGIEN"),
+            ("pt", "BÉLGICA"),
+            ("es", "BÉLGICA"),
+            ("it", "BELGIO"),
+        ],
+    ),
+    (
+        "CH",
+        &[
+            ("en", "SWITZERLAND"),
+            ("fr", "SUISSE"),
+            ("de", "SCHWEIZ"),
+            ("pt", "SUÍÇA"),
+            ("es", "SUIZA"),
+            ("it", "SVIZZERA"),
+        ],
+    ),
+    (
+        "GB",
+        &[
+            ("en", "UNITED KINGDOM"),
+            ("fr", "ROYAUME-UNI"),
+            ("de", "VEREINIGTES KÖNIGREICH"),
+            ("pt", "REINO UNIDO"),
+            ("es", "REINO UNIDO"),
+            ("it", "REGNO UNITO"),
+        ],
+    ),
+    (
+        "US",
+        &[
+            ("en", "UNITED STATES"),
+            ("fr", "ÉTATS-UNIS"),
+            ("de", "VEREINIGTE STAATEN"),
+            ("pt", "ESTADOS UNIDOS"),
+            ("es", "ESTADOS UNIDOS"),
+            ("it", "STATI UNITI"),
+        ],
+    ),
+    (
+        "CA",
+        &[
+            ("en", "CANADA"),
+            ("fr", "CANADA"),
+            ("de", "KANADA"),
+            ("pt", "CANADÁ"),
+            ("es", "CANADÁ"),
+            ("it", "CANADA"),
+        ],
+    ),
+    (
+        "JP",
+        &[
+            ("en", "JAPAN"),
+            ("fr", "JAPON"),
+            ("de", "JAPAN"),
+            ("pt", "JAPÃO"),
+            ("es", "JAPÓN"),
+            ("it", "GIAPPONE"),
+        ],
+    ),
+];
+
+/// Returns the name of a country in a given language, as written on a country line.
+///
+/// # Arguments
+///
+/// * `alpha2` - Two-letter country code (case insensitive).
+/// * `language` - ISO 639-1 language code, e.g. `de`.
+///
+/// # Returns
+///
+/// * Country name in capitals, or `None` when the country or language is unknown.
+///
+/// # Example
+///
+/// ```
+/// use address::common::country_name;
+/// assert_eq!(country_name("fr", "de"), Some("FRANKREICH"));
+/// ```
+pub fn country_name(alpha2: &str, language: &str) -> Option<&'static str> {
+    let alpha2 = alpha2.to_uppercase();
+    let localized = COUNTRY_NAMES
+        .iter()
+        .find(|(code, _)| *code == alpha2)
+        .and_then(|(_, names)| {
+            names
+                .iter()
+                .find(|(lang, _)| lang.eq_ignore_ascii_case(language))
+        })
+        .map(|(_, name)| *name);
+
+    match localized {
+        None if language.eq_ignore_ascii_case("en") => alpha2_to_country(&alpha2).ok(),
+        localized => localized,
+    }
+}
+
+/// Finds the country code of a country name written in any known language.
+///
+/// # Example
+///
+/// ```
+/// use address::common::find_country;
+/// assert_eq!(find_country("Espagne"), Some("ES"));
+/// assert_eq!(find_country("The Netherlands"), Some("NL"));
+/// ```
+pub fn find_country(name: &str) -> Option<&'static str> {
+    let name = name.trim().to_uppercase();
+    COUNTRY_NAMES
+        .iter()
+        .find(|(_, names)| names.iter().any(|(_, x)| *x == name))
+        .map(|(code, _)| *code)
+        .or_else(|| {
+            COUNTRIES
+                .iter()
+                .find(|(_, _, x)| *x == name)
+                .map(|(code, _, _)| *code)
+        })
+        .or_else(|| {
+            COUNTRY_ALIASES
+                .iter()
+                .find(|(x, _)| *x == name)
+                .map(|(_, code)| *code)
+        })
+}
+
+/// Checks a value against a simple code pattern, such as a post code format.
+///
+/// In the pattern, `9` stands for a digit, `A` for a letter and any other character
+/// stands for itself.
+///
+/// # Example
+///
+/// ```
+/// use address::common::matches_pattern;
+/// assert!(matches_pattern("1000-205", "9999-999"));
+/// assert!(matches_pattern("SW1A 1AA", "AA9A 9AA"));
+/// assert!(!matches_pattern("33500", "9999"));
+/// ```
+pub fn matches_pattern(value: &str, pattern: &str) -> bool {
+    value.chars().count() == pattern.chars().count()
+        && value.chars().zip(pattern.chars()).all(|(c, p)| match p {
+            '9' => c.is_ascii_digit(),
+            'A' => c.is_alphabetic(),
+            p => c == p,
+        })
 }
@@ -1,3 +1,6 @@
 pub mod common;
 pub mod models;
+#[cfg(feature = "render")]
+pub mod render;
+#[cfg(feature = "repository")]
 pub mod repositories;
@@ -1,10 +1,61 @@
 use serde::Deserialize;
 use serde::Serialize;
 
+#[cfg(feature = "ctt")]
+use super::address_ctt::CTT_Address;
+#[cfg(feature = "din-5008")]
+use super::address_din_5008::DIN_5008;
+#[cfg(feature = "edifact")]
+use super::address_edifact::EDIFACT_NAD;
+#[cfg(feature = "fhir")]
+use super::address_fhir::FHIR_Address;
+#[cfg(feature = "geojson")]
+use super::address_geojson::GeoJSON_Feature;
+#[cfg(feature = "iso20022")]
 use super::address_iso_20022::ISO_20022;
+#[cfg(feature = "nf-z10-011")]
 use super::address_nf_z10_011_enterprise::NF_Z10_011_Enterprise;
+#[cfg(feature = "nf-z10-011")]
 use super::address_nf_z10_011_individual::NF_Z10_011_Individual;
+#[cfg(feature = "osm")]
+use super::address_osm::OSM_Address;
+#[cfg(feature = "paf")]
+use super::address_paf::PAF_Address;
+#[cfg(feature = "schema-org")]
+use super::address_schema_org::SchemaOrg_PostalAddress;
+#[cfg(feature = "swift-mt")]
+use super::address_swift_mt::SWIFT_MT_Party;
+#[cfg(feature = "ubl")]
+use super::address_ubl::UBL_PostalAddress;
+#[cfg(feature = "usps")]
+use super::address_usps::USPS_Address;
+#[cfg(feature = "vcard")]
+use super::address_vcard::VCard_ADR;
+#[cfg(feature = "x12")]
+use super::address_x12::X12_N1;
+#[cfg(any(
+    feature = "edifact",
+    feature = "fhir",
+    feature = "swift-mt",
+    feature = "x12"
+))]
+use super::nf_lines::assign_lines;
+#[cfg(feature = "ubl")]
+use super::nf_lines::{classify_line, LineKind};
+#[cfg(feature = "nf-z10-011")]
 use crate::common::country_to_alpha2;
+#[cfg(any(
+    feature = "ctt",
+    feature = "edifact",
+    feature = "fhir",
+    feature = "osm",
+    feature = "schema-org",
+    feature = "swift-mt",
+    feature = "ubl",
+    feature = "vcard",
+    feature = "x12"
+))]
+use crate::common::normalize_country;
 use std::error::Error;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
@@ -24,8 +75,94 @@ pub struct Address {
     pub district_name: Option<String>,
     pub country_sub_division: Option<String>,
     pub country: String,
+    /// Position of the address, e.g. found by geocoding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<Coordinates>,
 }
 
+/// WGS 84 position, in decimal degrees
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct Coordinates {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl Address {
+    /// Names of the fields of an `Address`, in declaration order
+    pub const FIELDS: [&'static str; 15] = [
+        "name",
+        "department",
+        "sub_department",
+        "street_name",
+        "building_number",
+        "building_name",
+        "floor",
+        "post_box",
+        "room",
+        "post_code",
+        "town_name",
+        "town_location_name",
+        "district_name",
+        "country_sub_division",
+        "country",
+    ];
+
+    /// Fields that are not optional, cleared as an empty string
+    pub const REQUIRED_FIELDS: [&'static str; 3] = ["post_code", "town_name", "country"];
+
+    /// Returns the value of a field from its name, `None` when the field is not set
+    pub fn field(&self, name: &str) -> Result<Option<&str>, Box<dyn Error>> {
+        fn required(x: &str) -> Option<&str> {
+            Some(x).filter(|x| !x.is_empty())
+        }
+
+        Ok(match name {
+            "name" => self.name.as_deref(),
+            "department" => self.department.as_deref(),
+            "sub_department" => self.sub_department.as_deref(),
+            "street_name" => self.street_name.as_deref(),
+            "building_number" => self.building_number.as_deref(),
+            "building_name" => self.building_name.as_deref(),
+            "floor" => self.floor.as_deref(),
+            "post_box" => self.post_box.as_deref(),
+            "room" => self.room.as_deref(),
+            "post_code" => required(&self.post_code),
+            "town_name" => required(&self.town_name),
+            "town_location_name" => self.town_location_name.as_deref(),
+            "district_name" => self.district_name.as_deref(),
+            "country_sub_division" => self.country_sub_division.as_deref(),
+            "country" => required(&self.country),
+            _ => return Err(format!("Unknown field `{name}`").into()),
+        })
+    }
+
+    /// Sets a field from its name, `None` clears it
+    ///
+    /// Clearing `post_code`, `town_name` or `country` sets them to an empty string.
+    pub fn set_field(&mut self, name: &str, value: Option<String>) -> Result<(), Box<dyn Error>> {
+        match name {
+            "name" => self.name = value,
+            "department" => self.department = value,
+            "sub_department" => self.sub_department = value,
+            "street_name" => self.street_name = value,
+            "building_number" => self.building_number = value,
+            "building_name" => self.building_name = value,
+            "floor" => self.floor = value,
+            "post_box" => self.post_box = value,
+            "room" => self.room = value,
+            "post_code" => self.post_code = value.unwrap_or_default(),
+            "town_name" => self.town_name = value.unwrap_or_default(),
+            "town_location_name" => self.town_location_name = value,
+            "district_name" => self.district_name = value,
+            "country_sub_division" => self.country_sub_division = value,
+            "country" => self.country = value.unwrap_or_default(),
+            _ => return Err(format!("Unknown field `{name}`").into()),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "nf-z10-011")]
 impl TryFrom<NF_Z10_011_Individual> for Address {
     type Error = Box<dyn Error>;
 
@@ -41,7 +178,7 @@ impl TryFrom<NF_Z10_011_Individual> for Address {
             .split_once(' ')
             .map(|(code, city)| (code.to_string(), city.to_string()))
             .unwrap_or_default();
-        let country = country_to_alpha2(&value[7]).to_string();
+        let country = country_to_alpha2(&value[7])?.to_string();
 
         Ok(Address {
             name,
@@ -57,6 +194,7 @@ impl TryFrom<NF_Z10_011_Individual> for Address {
     }
 }
 
+#[cfg(feature = "nf-z10-011")]
 impl TryFrom<NF_Z10_011_Enterprise> for Address {
     type Error = Box<dyn Error>;
 
@@ -84,7 +222,7 @@ impl TryFrom<NF_Z10_011_Enterprise> for Address {
             .split_once(' ')
             .map(|(code, city)| (code.to_string(), city.to_string()))
             .unwrap_or_default();
-        let country = country_to_alpha2(&value[7]).to_string();
+        let country = country_to_alpha2(&value[7])?.to_string();
 
         Ok(Address {
             name,
@@ -102,6 +240,7 @@ impl TryFrom<NF_Z10_011_Enterprise> for Address {
     }
 }
 
+#[cfg(feature = "iso20022")]
 impl TryFrom<ISO_20022> for Address {
     type Error = Box<dyn Error>;
 
@@ -122,17 +261,379 @@ impl TryFrom<ISO_20022> for Address {
             district_name: value.DstrctNm.into(),
             country_sub_division: value.CtrySubDvsn.into(),
             country: value.Ctry,
+            coordinates: None,
+        })
+    }
+}
+
+#[cfg(feature = "vcard")]
+impl TryFrom<VCard_ADR> for Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: VCard_ADR) -> Result<Self, Self::Error> {
+        let optional = |x: String| Some(x).filter(|x| !x.is_empty());
+
+        Ok(Address {
+            post_box: optional(value.pobox),
+            room: optional(value.ext),
+            street_name: optional(value.street),
+            town_name: value.locality,
+            country_sub_division: optional(value.region),
+            post_code: value.code,
+            country: normalize_country(&value.country)?,
+            ..Address::default()
+        })
+    }
+}
+
+#[cfg(feature = "schema-org")]
+impl TryFrom<SchemaOrg_PostalAddress> for Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: SchemaOrg_PostalAddress) -> Result<Self, Self::Error> {
+        Ok(Address {
+            name: value.name,
+            street_name: value.street_address,
+            post_box: value.post_office_box_number,
+            post_code: value.postal_code.unwrap_or_default(),
+            town_name: value.address_locality.unwrap_or_default(),
+            country_sub_division: value.address_region,
+            country: normalize_country(&value.address_country.unwrap_or_default())?,
+            ..Address::default()
+        })
+    }
+}
+
+#[cfg(feature = "fhir")]
+impl TryFrom<FHIR_Address> for Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: FHIR_Address) -> Result<Self, Self::Error> {
+        let mut address = Address {
+            post_code: value.postal_code.unwrap_or_default(),
+            town_name: value.city.unwrap_or_default(),
+            district_name: value.district,
+            country_sub_division: value.state,
+            country: normalize_country(&value.country.unwrap_or_default())?,
+            ..Address::default()
+        };
+        assign_lines(&value.line, &mut address);
+
+        Ok(address)
+    }
+}
+
+#[cfg(feature = "ubl")]
+impl TryFrom<UBL_PostalAddress> for Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: UBL_PostalAddress) -> Result<Self, Self::Error> {
+        let mut address = Address {
+            street_name: value.StreetName,
+            floor: value.Floor.or(value.AdditionalStreetName),
+            post_box: value.Postbox,
+            building_number: value.BuildingNumber,
+            post_code: value.PostalZone.unwrap_or_default(),
+            town_name: value.CityName.unwrap_or_default(),
+            town_location_name: value.District,
+            country_sub_division: value.CountrySubentity,
+            country: value
+                .Country
+                .map(|x| normalize_country(&x.IdentificationCode))
+                .transpose()?
+                .unwrap_or_default(),
+            ..Address::default()
+        };
+
+        for line in value.AddressLine.into_iter().map(|x| x.Line) {
+            match classify_line(&line) {
+                LineKind::PostBox if address.post_box.is_none() => address.post_box = Some(line),
+                _ if address.room.is_none() => address.room = Some(line),
+                _ if address.town_location_name.is_none() => {
+                    address.town_location_name = Some(line)
+                }
+                _ => {}
+            }
+        }
+
+        Ok(address)
+    }
+}
+
+#[cfg(feature = "edifact")]
+impl TryFrom<EDIFACT_NAD> for Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: EDIFACT_NAD) -> Result<Self, Self::Error> {
+        let optional = |x: String| Some(x).filter(|x| !x.is_empty());
+
+        let mut names = match value.party_name.is_empty() {
+            true => value.name_and_address.into_iter(),
+            false => value.party_name.into_iter(),
+        };
+
+        let mut address = Address {
+            name: names.next(),
+            department: names.next(),
+            post_code: value.post_code,
+            town_name: value.city,
+            country_sub_division: optional(value.country_sub_entity),
+            country: normalize_country(&value.country)?,
+            ..Address::default()
+        };
+        assign_lines(&value.street, &mut address);
+
+        Ok(address)
+    }
+}
+
+#[cfg(feature = "x12")]
+impl TryFrom<X12_N1> for Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: X12_N1) -> Result<Self, Self::Error> {
+        let optional = |x: String| Some(x).filter(|x| !x.is_empty());
+
+        let mut address = Address {
+            name: optional(value.name),
+            department: value.additional_names.into_iter().next(),
+            post_code: value.postal_code,
+            town_name: value.city,
+            country_sub_division: optional(value.state),
+            country: normalize_country(&value.country)?,
+            ..Address::default()
+        };
+        assign_lines(&value.address, &mut address);
+
+        Ok(address)
+    }
+}
+
+#[cfg(feature = "swift-mt")]
+impl TryFrom<SWIFT_MT_Party> for Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: SWIFT_MT_Party) -> Result<Self, Self::Error> {
+        let name = Some(value.name.join(" ")).filter(|x| !x.is_empty());
+
+        // The post code, when present, comes first on the town line
+        let (post_code, town_name) = match value.town.split_once(' ') {
+            Some((code, town)) if code.chars().any(|c| c.is_ascii_digit()) => {
+                (code.to_string(), town.to_string())
+            }
+            _ => (String::new(), value.town),
+        };
+
+        let mut address = Address {
+            name,
+            post_code,
+            town_name,
+            country: normalize_country(&value.country)?,
+            ..Address::default()
+        };
+        assign_lines(&value.address, &mut address);
+
+        Ok(address)
+    }
+}
+
+#[cfg(feature = "din-5008")]
+impl TryFrom<DIN_5008> for Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: DIN_5008) -> Result<Self, Self::Error> {
+        let country = value.country().to_string();
+        let (town, lines) = value
+            .address_lines()
+            .split_last()
+            .ok_or("The `PLZ Ort` line is missing")?;
+        let (post_code, town_name) = town
+            .split_once(' ')
+            .map(|(code, city)| (code.to_string(), city.to_string()))
+            .unwrap_or_default();
+
+        let mut address = Address {
+            post_code,
+            town_name,
+            country,
+            ..Address::default()
+        };
+
+        let mut others = Vec::new();
+        for line in lines.iter().skip(1) {
+            // The house number follows the street name
+            let street = line
+                .rsplit_once(' ')
+                .filter(|(_, number)| number.starts_with(|c: char| c.is_ascii_digit()));
+
+            match street {
+                _ if line.to_uppercase().starts_with("POSTFACH") => {
+                    address.post_box = Some(line.to_string())
+                }
+                Some((street, number)) if address.street_name.is_none() => {
+                    address.street_name = Some(street.to_string());
+                    address.building_number = Some(number.to_string());
+                }
+                _ => others.push(line.to_string()),
+            }
+        }
+
+        address.name = lines.first().cloned();
+        let mut others = others.into_iter();
+        address.department = others.next();
+        address.room = others.next();
+
+        Ok(address)
+    }
+}
+
+#[cfg(feature = "ctt")]
+impl TryFrom<CTT_Address> for Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: CTT_Address) -> Result<Self, Self::Error> {
+        let optional = |x: String| Some(x).filter(|x| !x.is_empty());
+
+        Ok(Address {
+            name: optional(value.name),
+            street_name: optional(value.street),
+            building_number: optional(value.number),
+            floor: optional(value.floor),
+            town_location_name: optional(value.locality),
+            post_code: value.post_code,
+            town_name: value.postal_designation,
+            country: normalize_country(&value.country)?,
+            ..Address::default()
         })
     }
 }
 
-#[cfg(test)]
+#[cfg(feature = "paf")]
+impl TryFrom<PAF_Address> for Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: PAF_Address) -> Result<Self, Self::Error> {
+        let optional = |x: String| Some(x).filter(|x| !x.is_empty());
+
+        Ok(Address {
+            name: optional(value.organisation),
+            room: optional(value.sub_building),
+            building_name: optional(value.building_name),
+            building_number: optional(value.building_number),
+            street_name: optional(value.thoroughfare),
+            district_name: optional(value.dependent_locality),
+            post_code: value.postcode,
+            town_name: value.post_town,
+            country: "GB".to_string(),
+            ..Address::default()
+        })
+    }
+}
+
+#[cfg(feature = "usps")]
+impl TryFrom<USPS_Address> for Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: USPS_Address) -> Result<Self, Self::Error> {
+        let optional = |x: String| Some(x).filter(|x| !x.is_empty());
+
+        let street_name = [
+            &value.pre_directional,
+            &value.street_name,
+            &value.suffix,
+            &value.post_directional,
+        ]
+        .into_iter()
+        .filter(|x| !x.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+        Ok(Address {
+            room: optional(value.secondary()),
+            name: optional(value.name),
+            building_number: optional(value.primary_number),
+            street_name: optional(street_name),
+            post_code: value.zip,
+            town_name: value.city,
+            country_sub_division: optional(value.state),
+            country: "US".to_string(),
+            ..Address::default()
+        })
+    }
+}
+
+#[cfg(feature = "geojson")]
+impl TryFrom<GeoJSON_Feature> for Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: GeoJSON_Feature) -> Result<Self, Self::Error> {
+        let mut address = value.properties;
+        address.coordinates = value.geometry.map(|x| x.as_point()).transpose()?;
+        Ok(address)
+    }
+}
+
+#[cfg(feature = "osm")]
+impl TryFrom<OSM_Address> for Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: OSM_Address) -> Result<Self, Self::Error> {
+        let optional = |x: String| Some(x).filter(|x| !x.is_empty());
+
+        Ok(Address {
+            name: optional(value.name),
+            building_number: optional(value.housenumber),
+            street_name: optional(value.street),
+            room: optional(value.unit),
+            floor: optional(value.floor),
+            post_code: value.postcode,
+            town_name: value.city,
+            town_location_name: optional(value.place),
+            district_name: optional(value.suburb),
+            country_sub_division: optional(value.province),
+            country: normalize_country(&value.country)?,
+            coordinates: value.coordinates,
+            ..Address::default()
+        })
+    }
+}
+
+#[cfg(all(test, feature = "nf-z10-011"))]
 mod tests {
 
+    #[cfg(feature = "ctt")]
+    use crate::models::address_ctt::CTT_Address;
+    #[cfg(feature = "din-5008")]
+    use crate::models::address_din_5008::DIN_5008;
+    #[cfg(feature = "edifact")]
+    use crate::models::address_edifact::EDIFACT_NAD;
+    #[cfg(feature = "fhir")]
+    use crate::models::address_fhir::FHIR_Address;
+    #[cfg(feature = "geojson")]
+    use crate::models::address_geojson::GeoJSON_Feature;
+    #[cfg(feature = "nf-z10-011")]
     use crate::models::address_nf_z10_011_enterprise::NF_Z10_011_Enterprise;
+    #[cfg(feature = "osm")]
+    use crate::models::address_osm::OSM_Address;
+    #[cfg(feature = "paf")]
+    use crate::models::address_paf::PAF_Address;
+    #[cfg(feature = "schema-org")]
+    use crate::models::address_schema_org::SchemaOrg_PostalAddress;
+    #[cfg(feature = "swift-mt")]
+    use crate::models::address_swift_mt::SWIFT_MT_Party;
+    #[cfg(feature = "ubl")]
+    use crate::models::address_ubl::{UBL_AddressLine, UBL_Country, UBL_PostalAddress};
+    #[cfg(feature = "usps")]
+    use crate::models::address_usps::USPS_Address;
+    #[cfg(feature = "vcard")]
+    use crate::models::address_vcard::VCard_ADR;
+    #[cfg(feature = "x12")]
+    use crate::models::address_x12::X12_N1;
 
     use super::*;
 
+    #[cfg(feature = "nf-z10-011")]
     #[test]
     fn test_from_french_individual_example1() {
         let nf_address = NF_Z10_011_Individual {
@@ -159,6 +660,7 @@ mod tests {
 
         assert_eq!(addr, result);
     }
+    #[cfg(feature = "nf-z10-011")]
     #[test]
     fn test_from_french_individual_example2() {
         let nf_address = NF_Z10_011_Individual {
@@ -189,6 +691,7 @@ mod tests {
         assert_eq!(addr, result);
     }
 
+    #[cfg(feature = "nf-z10-011")]
     #[test]
     fn test_from_french_individual_example3_v1() {
         let nf_address = NF_Z10_011_Individual {
@@ -217,6 +720,7 @@ mod tests {
         assert_eq!(addr, result);
     }
 
+    #[cfg(feature = "nf-z10-011")]
     #[test]
     fn test_from_french_individual_example3_v2() {
         let nf_address = NF_Z10_011_Individual {
@@ -245,6 +749,7 @@ mod tests {
         assert_eq!(addr, result);
     }
 
+    #[cfg(feature = "nf-z10-011")]
     #[test]
     fn test_from_french_enterprise_example1() {
         let nf_address = NF_Z10_011_Enterprise {
@@ -267,9 +772,9 @@ mod tests {
                 .to_string()
                 .into(),
             post_box: "BP 40122".to_string().into(),
-            post_code: "33506".to_string().into(),
-            town_name: "LIBOURNE CEDEX".to_string().into(),
-            country: "FR".to_string().into(),
+            post_code: "33506".to_string(),
+            town_name: "LIBOURNE CEDEX".to_string(),
+            country: "FR".to_string(),
             ..Default::default()
         };
 
@@ -277,6 +782,7 @@ mod tests {
         assert_eq!(addr, result);
     }
 
+    #[cfg(feature = "nf-z10-011")]
     #[test]
     fn test_from_french_enterprise_example2() {
         let nf_address = NF_Z10_011_Enterprise {
@@ -297,7 +803,7 @@ mod tests {
             street_name: "56 RUE EMILE ZOLA".to_string().into(),
             floor: "Résidence des Capucins Bâtiment Quater".to_string().into(),
             post_box: "BP 90432".to_string().into(),
-            post_code: "34092".to_string().into(),
+            post_code: "34092".to_string(),
             town_name: "MONTPELLIER CEDEX 5".to_string(),
             town_location_name: "MONTFERRIER SUR LEZ".to_string().into(),
             country: "FR".to_string(),
@@ -308,4 +814,375 @@ mod tests {
         let addr: Address = nf_address.try_into().expect("error");
         assert_eq!(addr, result);
     }
+
+    #[cfg(feature = "vcard")]
+    #[test]
+    fn test_from_vcard() {
+        let vcard = VCard_ADR {
+            pobox: "BP 40122".to_string(),
+            street: "22BIS RUE DES FLEURS".to_string(),
+            locality: "LIBOURNE CEDEX".to_string(),
+            code: "33506".to_string(),
+            country: "FRANCE".to_string(),
+            ..Default::default()
+        };
+
+        let result = Address {
+            street_name: "22BIS RUE DES FLEURS".to_string().into(),
+            post_box: "BP 40122".to_string().into(),
+            post_code: "33506".to_string(),
+            town_name: "LIBOURNE CEDEX".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let addr: Address = vcard.try_into().expect("error");
+        assert_eq!(addr, result);
+    }
+
+    #[cfg(feature = "schema-org")]
+    #[test]
+    fn test_from_schema_org() {
+        let json_ld = SchemaOrg_PostalAddress {
+            name: "DURAND SA".to_string().into(),
+            street_address: "22BIS RUE DES FLEURS".to_string().into(),
+            post_office_box_number: "BP 40122".to_string().into(),
+            address_locality: "LIBOURNE CEDEX".to_string().into(),
+            postal_code: "33506".to_string().into(),
+            address_country: "France".to_string().into(),
+            ..Default::default()
+        };
+
+        let result = Address {
+            name: "DURAND SA".to_string().into(),
+            street_name: "22BIS RUE DES FLEURS".to_string().into(),
+            post_box: "BP 40122".to_string().into(),
+            post_code: "33506".to_string(),
+            town_name: "LIBOURNE CEDEX".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let addr: Address = json_ld.try_into().expect("error");
+        assert_eq!(addr, result);
+    }
+
+    #[cfg(feature = "fhir")]
+    #[test]
+    fn test_from_fhir() {
+        let fhir = FHIR_Address {
+            line: vec![
+                "Chez Mireille COPEAU Appartement 2".to_string(),
+                "Entrée A Bâtiment Jonquille".to_string(),
+                "25 RUE DE L’EGLISE".to_string(),
+                "CAUDOS".to_string(),
+            ],
+            city: "MIOS".to_string().into(),
+            postal_code: "33380".to_string().into(),
+            country: "FR".to_string().into(),
+            ..Default::default()
+        };
+
+        let result = Address {
+            street_name: "25 RUE DE L’EGLISE".to_string().into(),
+            floor: "Entrée A Bâtiment Jonquille".to_string().into(),
+            room: "Chez Mireille COPEAU Appartement 2".to_string().into(),
+            post_code: "33380".to_string(),
+            town_name: "MIOS".to_string(),
+            town_location_name: "CAUDOS".to_string().into(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let addr: Address = fhir.try_into().expect("error");
+        assert_eq!(addr, result);
+    }
+
+    #[cfg(feature = "ubl")]
+    #[test]
+    fn test_from_ubl() {
+        let ubl = UBL_PostalAddress {
+            StreetName: "56 RUE EMILE ZOLA".to_string().into(),
+            AdditionalStreetName: "Résidence des Capucins Bâtiment Quater".to_string().into(),
+            CityName: "MONTPELLIER CEDEX 5".to_string().into(),
+            PostalZone: "34092".to_string().into(),
+            AddressLine: vec![UBL_AddressLine {
+                Line: "BP 90432".to_string(),
+            }],
+            Country: UBL_Country {
+                IdentificationCode: "FR".to_string(),
+            }
+            .into(),
+            ..Default::default()
+        };
+
+        let result = Address {
+            street_name: "56 RUE EMILE ZOLA".to_string().into(),
+            floor: "Résidence des Capucins Bâtiment Quater".to_string().into(),
+            post_box: "BP 90432".to_string().into(),
+            post_code: "34092".to_string(),
+            town_name: "MONTPELLIER CEDEX 5".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let addr: Address = ubl.try_into().expect("error");
+        assert_eq!(addr, result);
+    }
+
+    #[cfg(feature = "edifact")]
+    #[test]
+    fn test_from_edifact() {
+        let nad = EDIFACT_NAD {
+            party_qualifier: "BY".to_string(),
+            party_name: vec!["DURAND SA".to_string(), "Service achat".to_string()],
+            street: vec!["22BIS RUE DES FLEURS".to_string(), "BP 40122".to_string()],
+            city: "LIBOURNE CEDEX".to_string(),
+            post_code: "33506".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let result = Address {
+            name: "DURAND SA".to_string().into(),
+            department: "Service achat".to_string().into(),
+            street_name: "22BIS RUE DES FLEURS".to_string().into(),
+            post_box: "BP 40122".to_string().into(),
+            post_code: "33506".to_string(),
+            town_name: "LIBOURNE CEDEX".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let addr: Address = nad.try_into().expect("error");
+        assert_eq!(addr, result);
+    }
+
+    #[cfg(feature = "x12")]
+    #[test]
+    fn test_from_x12() {
+        let party = X12_N1 {
+            entity: "ST".to_string(),
+            name: "Monsieur Jean DURAND".to_string(),
+            address: vec!["25D RUE DES FLEURS".to_string()],
+            city: "LIBOURNE".to_string(),
+            postal_code: "33500".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let result = Address {
+            name: "Monsieur Jean DURAND".to_string().into(),
+            street_name: "25D RUE DES FLEURS".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let addr: Address = party.try_into().expect("error");
+        assert_eq!(addr, result);
+    }
+
+    #[cfg(feature = "swift-mt")]
+    #[test]
+    fn test_from_swift_mt() {
+        let party = SWIFT_MT_Party {
+            tag: "59F".to_string(),
+            name: vec!["Monsieur Jean".to_string(), "DURAND".to_string()],
+            address: vec!["25D RUE DES FLEURS".to_string()],
+            country: "FR".to_string(),
+            town: "33500 LIBOURNE".to_string(),
+            ..Default::default()
+        };
+
+        let result = Address {
+            name: "Monsieur Jean DURAND".to_string().into(),
+            street_name: "25D RUE DES FLEURS".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let addr: Address = party.try_into().expect("error");
+        assert_eq!(addr, result);
+    }
+
+    #[cfg(feature = "din-5008")]
+    #[test]
+    fn test_from_din_5008() {
+        let din = DIN_5008 {
+            lines: vec![
+                "Musterfirma GmbH".to_string(),
+                "Abteilung Einkauf".to_string(),
+                "Hauptstraße 12a".to_string(),
+                "10115 Berlin".to_string(),
+            ],
+        };
+
+        let result = Address {
+            name: "Musterfirma GmbH".to_string().into(),
+            department: "Abteilung Einkauf".to_string().into(),
+            street_name: "Hauptstraße".to_string().into(),
+            building_number: "12a".to_string().into(),
+            post_code: "10115".to_string(),
+            town_name: "Berlin".to_string(),
+            country: "DE".to_string(),
+            ..Default::default()
+        };
+
+        let addr: Address = din.try_into().expect("error");
+        assert_eq!(addr, result);
+    }
+
+    #[cfg(feature = "ctt")]
+    #[test]
+    fn test_from_ctt() {
+        let ctt = CTT_Address {
+            name: "Maria Silva".to_string(),
+            street: "Rua Augusta".to_string(),
+            number: "100".to_string(),
+            floor: "2º Esq.".to_string(),
+            post_code: "1100-053".to_string(),
+            postal_designation: "LISBOA".to_string(),
+            country: "PT".to_string(),
+            ..Default::default()
+        };
+
+        let result = Address {
+            name: "Maria Silva".to_string().into(),
+            street_name: "Rua Augusta".to_string().into(),
+            building_number: "100".to_string().into(),
+            floor: "2º Esq.".to_string().into(),
+            post_code: "1100-053".to_string(),
+            town_name: "LISBOA".to_string(),
+            country: "PT".to_string(),
+            ..Default::default()
+        };
+
+        let addr: Address = ctt.try_into().expect("error");
+        assert_eq!(addr, result);
+    }
+
+    #[cfg(feature = "paf")]
+    #[test]
+    fn test_from_paf() {
+        let paf = PAF_Address {
+            organisation: "Jane Smith".to_string(),
+            sub_building: "Flat 2".to_string(),
+            building_number: "10".to_string(),
+            thoroughfare: "High Street".to_string(),
+            dependent_locality: "Headington".to_string(),
+            post_town: "OXFORD".to_string(),
+            postcode: "OX3 9DU".to_string(),
+            ..Default::default()
+        };
+
+        let result = Address {
+            name: "Jane Smith".to_string().into(),
+            room: "Flat 2".to_string().into(),
+            building_number: "10".to_string().into(),
+            street_name: "High Street".to_string().into(),
+            district_name: "Headington".to_string().into(),
+            post_code: "OX3 9DU".to_string(),
+            town_name: "OXFORD".to_string(),
+            country: "GB".to_string(),
+            ..Default::default()
+        };
+
+        let addr: Address = paf.try_into().expect("error");
+        assert_eq!(addr, result);
+    }
+
+    #[cfg(feature = "usps")]
+    #[test]
+    fn test_from_usps() {
+        let usps = USPS_Address {
+            name: "JOHN DOE".to_string(),
+            primary_number: "102".to_string(),
+            pre_directional: "N".to_string(),
+            street_name: "MAIN".to_string(),
+            suffix: "ST".to_string(),
+            unit: "APT".to_string(),
+            unit_number: "12".to_string(),
+            city: "SPRINGFIELD".to_string(),
+            state: "IL".to_string(),
+            zip: "62704-1234".to_string(),
+            ..Default::default()
+        };
+
+        let result = Address {
+            name: "JOHN DOE".to_string().into(),
+            building_number: "102".to_string().into(),
+            street_name: "N MAIN ST".to_string().into(),
+            room: "APT 12".to_string().into(),
+            post_code: "62704-1234".to_string(),
+            town_name: "SPRINGFIELD".to_string(),
+            country_sub_division: "IL".to_string().into(),
+            country: "US".to_string(),
+            ..Default::default()
+        };
+
+        let addr: Address = usps.try_into().expect("error");
+        assert_eq!(addr, result);
+    }
+
+    #[cfg(feature = "geojson")]
+    #[test]
+    fn test_from_geojson() {
+        let feature: GeoJSON_Feature = r#"{
+            "type": "Feature",
+            "id": "8f6b6c52-2b1e-4c3e-9a41-0d4b1c1a9e11",
+            "geometry": { "type": "Point", "coordinates": [-0.2421, 44.9153] },
+            "properties": {
+                "street_name": "RUE DES FLEURS",
+                "building_number": "25D",
+                "post_code": "33500",
+                "town_name": "LIBOURNE",
+                "country": "FR"
+            }
+        }"#
+        .parse()
+        .expect("Cannot parse GeoJSON!");
+
+        let result = Address {
+            street_name: "RUE DES FLEURS".to_string().into(),
+            building_number: "25D".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            coordinates: Some(Coordinates {
+                lat: 44.9153,
+                lon: -0.2421,
+            }),
+            ..Default::default()
+        };
+
+        let addr: Address = feature.try_into().expect("error");
+        assert_eq!(addr, result);
+    }
+
+    #[cfg(feature = "osm")]
+    #[test]
+    fn test_from_osm() {
+        let osm: OSM_Address = "addr:housenumber=10\naddr:street=Hauptstraße\naddr:unit=3\naddr:suburb=Mitte\naddr:postcode=10115\naddr:city=Berlin\naddr:state=Berlin\naddr:country=de"
+            .parse()
+            .expect("Cannot parse OSM tags!");
+
+        let result = Address {
+            building_number: "10".to_string().into(),
+            street_name: "Hauptstraße".to_string().into(),
+            room: "3".to_string().into(),
+            district_name: "Mitte".to_string().into(),
+            post_code: "10115".to_string(),
+            town_name: "Berlin".to_string(),
+            country_sub_division: "Berlin".to_string().into(),
+            country: "DE".to_string(),
+            ..Default::default()
+        };
+
+        let addr: Address = osm.try_into().expect("error");
+        assert_eq!(addr, result);
+    }
 }
@@ -0,0 +1,236 @@
+use std::fmt;
+use std::str::FromStr;
+
+use std::error::Error;
+
+use crate::common::{country_name, find_country, matches_pattern, normalize_country, Result};
+
+use super::address::Address;
+use super::validate::Validate;
+
+/// CP4-CP3 post code format
+const POST_CODE_PATTERN: &str = "9999-999";
+/// Country of the sender, no country line is written for domestic mail
+const DOMESTIC_COUNTRY: &str = "PT";
+
+/// A Portuguese address, as laid out by CTT (Correios de Portugal)
+///
+/// ```text
+/// Maria Silva
+/// Rua Augusta, 100, 2º Esq.
+/// Baixa
+/// 1100-053 LISBOA
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CTT_Address {
+    /// Addressee
+    pub name: String,
+    /// Street, avenue, ... (Arruamento)
+    pub street: String,
+    /// Door number (Número de polícia)
+    pub number: String,
+    /// Floor and unit, e.g. `2º Esq.` (Andar/Fração)
+    pub floor: String,
+    /// Locality, when different from the postal designation (Localidade)
+    pub locality: String,
+    /// CP4-CP3 post code, e.g. `1100-053`
+    pub post_code: String,
+    /// Postal designation, in capitals (Designação postal)
+    pub postal_designation: String,
+    /// ISO country code
+    pub country: String,
+}
+
+impl CTT_Address {
+    /// Returns the street line: street, number and floor separated by commas
+    pub fn street_line(&self) -> String {
+        [&self.street, &self.number, &self.floor]
+            .into_iter()
+            .filter(|x| !x.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Parses a street line, the first part starting with a digit is the door number
+    fn parse_street_line(&mut self, line: &str) {
+        let mut parts = line.split(',').map(|x| x.trim()).filter(|x| !x.is_empty());
+        self.street = parts.next().unwrap_or_default().to_string();
+
+        // `Rua Augusta 100` without comma
+        if let Some((street, number)) = self.street.clone().rsplit_once(' ') {
+            if number.starts_with(|c: char| c.is_ascii_digit()) {
+                self.street = street.to_string();
+                self.number = number.to_string();
+            }
+        }
+
+        let rest: Vec<&str> = parts.collect();
+        match rest.split_first() {
+            Some((number, floor)) if self.number.is_empty() => {
+                self.number = number.to_string();
+                self.floor = floor.join(", ");
+            }
+            _ => self.floor = rest.join(", "),
+        }
+    }
+}
+
+impl Validate for CTT_Address {
+    fn validate(&self) -> Result<()> {
+        if !matches_pattern(&self.post_code, POST_CODE_PATTERN) {
+            return Err(format!(
+                "The post code `{}` must have the {POST_CODE_PATTERN} format",
+                self.post_code
+            )
+            .into());
+        }
+        if self.postal_designation.is_empty() {
+            return Err("The postal designation is mandatory".into());
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for CTT_Address {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut lines: Vec<&str> = s
+            .lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+            .collect();
+
+        let mut res = CTT_Address {
+            country: DOMESTIC_COUNTRY.to_string(),
+            ..Default::default()
+        };
+        if let Some(country) = lines.last().and_then(|x| find_country(x)) {
+            res.country = country.to_string();
+            lines.pop();
+        }
+
+        let (town, lines) = lines
+            .split_last()
+            .ok_or("The `XXXX-XXX DESIGNAÇÃO POSTAL` line is missing")?;
+        let (post_code, postal_designation) = town.split_once(' ').unwrap_or((town, ""));
+        res.post_code = post_code.to_string();
+        res.postal_designation = postal_designation.trim().to_string();
+
+        match lines {
+            [] => {}
+            [name] => res.name = name.to_string(),
+            [name, street] => {
+                res.name = name.to_string();
+                res.parse_street_line(street);
+            }
+            [name, street, locality] => {
+                res.name = name.to_string();
+                res.parse_street_line(street);
+                res.locality = locality.to_string();
+            }
+            _ => return Err("Input must contain no more than 5 lines".into()),
+        }
+
+        Ok(res)
+    }
+}
+
+impl fmt::Display for CTT_Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let town = format!("{} {}", self.post_code, self.postal_designation);
+        let mut lines = vec![
+            self.name.clone(),
+            self.street_line(),
+            self.locality.clone(),
+            town.trim().to_string(),
+        ];
+
+        let country = normalize_country(&self.country).unwrap_or_else(|_| self.country.clone());
+        if !country.is_empty() && country != DOMESTIC_COUNTRY {
+            lines.push(country_name(&country, "pt").unwrap_or(&country).to_string());
+        }
+
+        lines.retain(|x| !x.is_empty());
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl TryFrom<Address> for CTT_Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: Address) -> Result<Self> {
+        let floor = [value.floor, value.room]
+            .into_iter()
+            .flatten()
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Ok(CTT_Address {
+            name: value.name.unwrap_or_default(),
+            street: value.street_name.unwrap_or_default(),
+            number: value.building_number.unwrap_or_default(),
+            floor,
+            locality: value.town_location_name.unwrap_or_default(),
+            post_code: value.post_code,
+            postal_designation: value.town_name.to_uppercase(),
+            country: normalize_country(&value.country)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let content = "Maria Silva\nRua Augusta, 100, 2º Esq.\nBaixa\n1100-053 LISBOA";
+
+        let result = CTT_Address {
+            name: "Maria Silva".to_string(),
+            street: "Rua Augusta".to_string(),
+            number: "100".to_string(),
+            floor: "2º Esq.".to_string(),
+            locality: "Baixa".to_string(),
+            post_code: "1100-053".to_string(),
+            postal_designation: "LISBOA".to_string(),
+            country: "PT".to_string(),
+        };
+
+        let parse: CTT_Address = content.parse().expect("Cannot parse CTT!");
+        assert_eq!(parse, result);
+        assert_eq!(parse.to_string(), content);
+    }
+
+    #[test]
+    fn test_from_address() {
+        let address = Address {
+            name: "João Santos".to_string().into(),
+            street_name: "Avenida da Boavista".to_string().into(),
+            building_number: "1203".to_string().into(),
+            post_code: "4100-130".to_string(),
+            town_name: "Porto".to_string(),
+            country: "PT".to_string(),
+            ..Default::default()
+        };
+
+        let ctt = CTT_Address::try_from(address).expect("error");
+        assert_eq!(
+            ctt.to_string(),
+            "João Santos\nAvenida da Boavista, 1203\n4100-130 PORTO"
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let ctt: CTT_Address = "Maria Silva\nRua Augusta 100\n1100 LISBOA"
+            .parse()
+            .expect("error");
+        assert_eq!(ctt.number, "100");
+        assert!(ctt.validate().is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use std::fmt;
+use std::str::FromStr;
+
+use std::error::Error;
+
+use crate::common::{country_name, find_country, matches_pattern, normalize_country, Result};
+
+use super::address::Address;
+use super::validate::Validate;
+
+const MAX_LENGTH: usize = 35;
+const MAX_LINES: usize = 6;
+/// Country of the sender, no country line is written for domestic mail
+const DOMESTIC_COUNTRY: &str = "DE";
+
+/// The address zone of a German letter (DIN 5008)
+///
+/// ```text
+/// Frau Erika Mustermann
+/// Hauptstraße 12a
+/// 10115 Berlin
+/// ```
+///
+/// Empty lines are not part of the address zone, the country line is only present
+/// for foreign mail.
+#[derive(Debug, Default, PartialEq)]
+pub struct DIN_5008 {
+    pub lines: Vec<String>,
+}
+
+impl DIN_5008 {
+    /// Returns the destination country, read from the country line if any
+    pub fn country(&self) -> &str {
+        self.lines
+            .last()
+            .and_then(|x| find_country(x))
+            .unwrap_or(DOMESTIC_COUNTRY)
+    }
+
+    /// Returns the lines above the country line, if any
+    pub fn address_lines(&self) -> &[String] {
+        match self.lines.split_last() {
+            Some((last, rest)) if find_country(last).is_some() => rest,
+            _ => &self.lines,
+        }
+    }
+}
+
+impl Validate for DIN_5008 {
+    fn validate(&self) -> Result<()> {
+        if self.lines.len() > MAX_LINES {
+            return Err(format!("Address must have at most {MAX_LINES} lines").into());
+        }
+
+        for (i, line) in self.lines.iter().enumerate() {
+            if line.chars().count() > MAX_LENGTH {
+                return Err(
+                    format!("Line {} must have less than {MAX_LENGTH} chars", i + 1).into(),
+                );
+            }
+        }
+
+        if self.country() == DOMESTIC_COUNTRY {
+            let plz = self
+                .address_lines()
+                .last()
+                .and_then(|x| x.split_whitespace().next())
+                .unwrap_or_default();
+            if !matches_pattern(plz, "99999") {
+                return Err(format!("The PLZ `{plz}` must have 5 digits").into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for DIN_5008 {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let lines: Vec<String> = s
+            .lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+            .map(|x| x.to_string())
+            .collect();
+
+        if lines.len() > MAX_LINES {
+            return Err(format!("Input must contain no more than {MAX_LINES} lines").into());
+        }
+
+        Ok(DIN_5008 { lines })
+    }
+}
+
+impl fmt::Display for DIN_5008 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lines.join("\n"))
+    }
+}
+
+impl TryFrom<Address> for DIN_5008 {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: Address) -> Result<Self> {
+        let country = normalize_country(&value.country)?;
+
+        // German order: street first, then the house number
+        let street = [value.street_name, value.building_number]
+            .into_iter()
+            .flatten()
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let town = format!("{} {}", value.post_code, value.town_name)
+            .trim()
+            .to_string();
+
+        let mut lines: Vec<String> = [
+            value.name,
+            value.department,
+            value.room,
+            Some(street),
+            value.post_box,
+            Some(town),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|x| !x.is_empty())
+        .collect();
+
+        if !country.is_empty() && country != DOMESTIC_COUNTRY {
+            let name = country_name(&country, "de")
+                .or_else(|| country_name(&country, "en"))
+                .ok_or(format!("Unknown country `{country}`"))?;
+            lines.push(name.to_string());
+        }
+
+        Ok(DIN_5008 { lines })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_address() {
+        let address = Address {
+            name: "Erika Mustermann".to_string().into(),
+            street_name: "Hauptstraße".to_string().into(),
+            building_number: "12a".to_string().into(),
+            post_code: "10115".to_string(),
+            town_name: "Berlin".to_string(),
+            country: "DE".to_string(),
+            ..Default::default()
+        };
+
+        let din: DIN_5008 = address.try_into().expect("error");
+        assert_eq!(
+            din.to_string(),
+            "Erika Mustermann\nHauptstraße 12a\n10115 Berlin"
+        );
+    }
+
+    #[test]
+    fn test_from_address_foreign() {
+        let address = Address {
+            name: "Monsieur Jean DURAND".to_string().into(),
+            street_name: "25D RUE DES FLEURS".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let din: DIN_5008 = address.try_into().expect("error");
+        assert_eq!(din.lines.last().map(|x| x.as_str()), Some("FRANKREICH"));
+        assert_eq!(din.country(), "FR");
+        assert!(din.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate() {
+        let din: DIN_5008 = "Max Mustermann\nHauptstraße 1\n1011 Berlin"
+            .parse()
+            .expect("error");
+        assert!(din.validate().is_err());
+
+        let din: DIN_5008 = "Max Mustermann\nHauptstraße 1\n01011 Berlin"
+            .parse()
+            .expect("error");
+        assert!(din.validate().is_ok());
+    }
+}
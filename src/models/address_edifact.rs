@@ -0,0 +1,401 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::common::Result;
+
+use super::address::Address;
+use super::nf_lines::address_lines;
+use super::validate::Validate;
+
+/// Party qualifier used when converting from an `Address` (delivery party)
+const DEFAULT_QUALIFIER: &str = "DP";
+/// Components of the street composite `C059`
+const MAX_STREET_LINES: usize = 4;
+
+/// Delimiters of an EDIFACT interchange, as declared by the `UNA` service string
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EDIFACT_Delimiters {
+    /// Component data element separator
+    pub component: char,
+    /// Data element separator
+    pub element: char,
+    /// Release character
+    pub release: char,
+    /// Segment terminator
+    pub segment: char,
+}
+
+impl Default for EDIFACT_Delimiters {
+    fn default() -> Self {
+        EDIFACT_Delimiters {
+            component: ':',
+            element: '+',
+            release: '?',
+            segment: '\'',
+        }
+    }
+}
+
+impl EDIFACT_Delimiters {
+    /// Reads the delimiters from the `UNA` segment of an interchange, if any
+    pub fn from_interchange(s: &str) -> Self {
+        let s = s.trim_start();
+        let chars: Vec<char> = s.chars().take(9).collect();
+        match (s.starts_with("UNA"), chars.as_slice()) {
+            (true, [_, _, _, component, element, _, release, _, segment]) => EDIFACT_Delimiters {
+                component: *component,
+                element: *element,
+                release: *release,
+                segment: *segment,
+            },
+            _ => EDIFACT_Delimiters::default(),
+        }
+    }
+
+    /// Splits on `sep`, keeping escaped characters (with their release character)
+    fn split_raw(&self, s: &str, sep: char) -> Vec<String> {
+        let mut parts = Vec::new();
+        let mut current = String::new();
+        let mut chars = s.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                c if c == self.release => {
+                    current.push(c);
+                    current.extend(chars.next());
+                }
+                c if c == sep => parts.push(std::mem::take(&mut current)),
+                c => current.push(c),
+            }
+        }
+        parts.push(current);
+
+        parts
+    }
+
+    /// Splits on `sep` and unescapes the parts
+    fn split(&self, s: &str, sep: char) -> Vec<String> {
+        let mut parts = Vec::new();
+        let mut current = String::new();
+        let mut chars = s.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                c if c == self.release => current.extend(chars.next()),
+                c if c == sep => parts.push(std::mem::take(&mut current)),
+                c => current.push(c),
+            }
+        }
+        parts.push(current);
+
+        parts
+    }
+
+    fn escape(&self, s: &str) -> String {
+        let mut res = String::new();
+        for c in s.chars() {
+            if [self.component, self.element, self.release, self.segment].contains(&c) {
+                res.push(self.release);
+            }
+            res.push(c);
+        }
+        res
+    }
+}
+
+/// The UN/EDIFACT `NAD` (name and address) segment
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EDIFACT_NAD {
+    /// Party function code qualifier (3035)
+    pub party_qualifier: String,
+    /// Party identification (C082)
+    pub party_id: String,
+    /// Unstructured name and address lines (C058)
+    pub name_and_address: Vec<String>,
+    /// Party name lines (C080)
+    pub party_name: Vec<String>,
+    /// Street and number / P.O. box lines (C059)
+    pub street: Vec<String>,
+    /// City name (3164)
+    pub city: String,
+    /// Country sub-entity name (C819)
+    pub country_sub_entity: String,
+    /// Postal identification code (3251)
+    pub post_code: String,
+    /// Country name code (3207)
+    pub country: String,
+}
+
+impl EDIFACT_NAD {
+    /// Parses every `NAD` segment of an interchange
+    pub fn parse_all(s: &str) -> Result<Vec<Self>> {
+        let delimiters = EDIFACT_Delimiters::from_interchange(s);
+
+        delimiters
+            .split_raw(s, delimiters.segment)
+            .iter()
+            .map(|x| x.trim())
+            .filter(|x| x.starts_with("NAD"))
+            .map(|x| Self::parse_segment(x, &delimiters))
+            .collect()
+    }
+
+    /// Parses a single `NAD` segment, without its terminator
+    pub fn parse_segment(segment: &str, delimiters: &EDIFACT_Delimiters) -> Result<Self> {
+        let elements = delimiters.split_raw(segment, delimiters.element);
+
+        if elements.first().map(|x| x.as_str()) != Some("NAD") {
+            return Err("Segment is not a `NAD`".into());
+        }
+
+        let components = |i: usize| -> Vec<String> {
+            elements
+                .get(i)
+                .map(|x| delimiters.split(x, delimiters.component))
+                .unwrap_or_default()
+        };
+        let lines = |i: usize| -> Vec<String> {
+            let mut lines = components(i);
+            while lines.last().is_some_and(|x| x.is_empty()) {
+                lines.pop();
+            }
+            lines
+        };
+        let first = |i: usize| components(i).into_iter().next().unwrap_or_default();
+
+        Ok(EDIFACT_NAD {
+            party_qualifier: first(1),
+            party_id: first(2),
+            name_and_address: lines(3),
+            party_name: lines(4),
+            street: lines(5),
+            city: first(6),
+            // C819: code, code list, agency, name
+            country_sub_entity: components(7).get(3).cloned().unwrap_or_else(|| first(7)),
+            post_code: first(8),
+            country: first(9),
+        })
+    }
+
+    /// Writes the segment with the given delimiters
+    pub fn to_segment(&self, delimiters: &EDIFACT_Delimiters) -> String {
+        let join = |values: &[String]| {
+            values
+                .iter()
+                .map(|x| delimiters.escape(x))
+                .collect::<Vec<_>>()
+                .join(&delimiters.component.to_string())
+        };
+        let sub_entity = match self.country_sub_entity.as_str() {
+            "" => String::new(),
+            name => format!(
+                "{c}{c}{c}{}",
+                delimiters.escape(name),
+                c = delimiters.component
+            ),
+        };
+
+        let mut elements = vec![
+            "NAD".to_string(),
+            delimiters.escape(&self.party_qualifier),
+            delimiters.escape(&self.party_id),
+            join(&self.name_and_address),
+            join(&self.party_name),
+            join(&self.street),
+            delimiters.escape(&self.city),
+            sub_entity,
+            delimiters.escape(&self.post_code),
+            delimiters.escape(&self.country),
+        ];
+        while elements.last().is_some_and(|x| x.is_empty()) {
+            elements.pop();
+        }
+
+        format!(
+            "{}{}",
+            elements.join(&delimiters.element.to_string()),
+            delimiters.segment
+        )
+    }
+}
+
+impl Validate for EDIFACT_NAD {
+    fn validate(&self) -> Result<()> {
+        let check = |x: &str, max_len: usize, field_name: &str| {
+            if x.chars().count() > max_len {
+                Err(format!(
+                    "The field `{field_name}` must have less than {max_len} chars"
+                ))
+            } else {
+                Ok(())
+            }
+        };
+        let check_lines = |lines: &[String], max_lines: usize, max_len: usize, name: &str| {
+            if lines.len() > max_lines {
+                return Err(format!(
+                    "The field `{name}` must have at most {max_lines} lines"
+                ));
+            }
+            lines.iter().try_for_each(|x| check(x, max_len, name))
+        };
+
+        check(&self.party_qualifier, 3, "3035")?;
+        check(&self.party_id, 35, "3039")?;
+        check_lines(&self.name_and_address, 5, 35, "3124")?;
+        check_lines(&self.party_name, 5, 35, "3036")?;
+        check_lines(&self.street, MAX_STREET_LINES, 35, "3042")?;
+        check(&self.city, 35, "3164")?;
+        check(&self.country_sub_entity, 35, "3228")?;
+        check(&self.post_code, 17, "3251")?;
+        check(&self.country, 3, "3207")?;
+
+        Ok(())
+    }
+}
+
+impl FromStr for EDIFACT_NAD {
+    type Err = Box<dyn Error>;
+
+    /// Parses the first `NAD` segment of an interchange
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_all(s)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| "No `NAD` segment found".into())
+    }
+}
+
+impl fmt::Display for EDIFACT_NAD {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_segment(&EDIFACT_Delimiters::default()))
+    }
+}
+
+impl TryFrom<Address> for EDIFACT_NAD {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: Address) -> Result<Self> {
+        let party_name = [&value.name, &value.department]
+            .into_iter()
+            .flatten()
+            .filter(|x| !x.is_empty())
+            .cloned()
+            .collect();
+        let street = address_lines(&value);
+        if street.len() > MAX_STREET_LINES {
+            return Err(format!(
+                "At most {MAX_STREET_LINES} street lines fit in `C059`, not {}",
+                street.len()
+            )
+            .into());
+        }
+
+        Ok(EDIFACT_NAD {
+            party_qualifier: DEFAULT_QUALIFIER.to_string(),
+            street,
+            party_name,
+            city: value.town_name,
+            country_sub_entity: value.country_sub_division.unwrap_or_default(),
+            post_code: value.post_code,
+            country: value.country,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_all() {
+        let content = "UNA:+.? '\
+            UNB+UNOC:3+SENDER+RECEIVER+240101:1200+1'\
+            UNH+1+ORDERS:D:96A:UN'\
+            NAD+BY+5412345000013::9++DURAND SA:Service achat+22BIS RUE DES FLEURS:BP 40122+LIBOURNE CEDEX++33506+FR'\
+            NAD+DP+++Monsieur Jean DURAND+25D RUE DES FLEURS+LIBOURNE+:::Gironde+33500+FR'\
+            UNT+4+1'\
+            UNZ+1+1'";
+
+        let result = EDIFACT_NAD {
+            party_qualifier: "BY".to_string(),
+            party_id: "5412345000013".to_string(),
+            party_name: vec!["DURAND SA".to_string(), "Service achat".to_string()],
+            street: vec!["22BIS RUE DES FLEURS".to_string(), "BP 40122".to_string()],
+            city: "LIBOURNE CEDEX".to_string(),
+            post_code: "33506".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let parse = EDIFACT_NAD::parse_all(content).expect("Cannot parse EDIFACT!");
+        assert_eq!(parse.len(), 2);
+        assert_eq!(parse[0], result);
+        assert_eq!(parse[1].country_sub_entity, "Gironde");
+    }
+
+    #[test]
+    fn test_delimiters() {
+        let content = "UNA|*,# ~NAD*DP***DUPONT #* FILS*1 RUE A|ETAGE 2*PARIS**75001*FR~";
+
+        let parse: EDIFACT_NAD = content.parse().expect("Cannot parse EDIFACT!");
+        assert_eq!(parse.party_name, vec!["DUPONT * FILS".to_string()]);
+        assert_eq!(parse.street.len(), 2);
+    }
+
+    #[test]
+    fn test_to_string() {
+        let address = Address {
+            name: "L'ATELIER".to_string().into(),
+            street_name: "25D RUE DES FLEURS".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let nad = EDIFACT_NAD::try_from(address).expect("error");
+        assert_eq!(
+            nad.to_string(),
+            "NAD+DP+++L?'ATELIER+25D RUE DES FLEURS+LIBOURNE++33500+FR'"
+        );
+        assert_eq!(nad.to_string().parse::<EDIFACT_NAD>().expect("error"), nad);
+    }
+
+    #[test]
+    fn test_too_many_lines() {
+        let address = Address {
+            room: "APPARTEMENT 2".to_string().into(),
+            floor: "ENTREE A".to_string().into(),
+            street_name: "25 RUE DE L'EGLISE".to_string().into(),
+            post_box: "BP 12".to_string().into(),
+            town_location_name: "CAUDOS".to_string().into(),
+            post_code: "33380".to_string(),
+            town_name: "MIOS".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+        assert!(EDIFACT_NAD::try_from(address.clone()).is_err());
+
+        let address = Address {
+            post_box: None,
+            ..address
+        };
+        let nad = EDIFACT_NAD::try_from(address).expect("error");
+        assert_eq!(nad.street.len(), 4);
+        assert!(nad.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate() {
+        let result = EDIFACT_NAD {
+            party_qualifier: "DP".to_string(),
+            street: vec!["1".to_string(); 5],
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        assert!(result.validate().is_err())
+    }
+}
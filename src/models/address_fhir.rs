@@ -0,0 +1,363 @@
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use std::error::Error;
+use std::str::FromStr;
+
+use crate::common::Result;
+
+use super::address::Address;
+use super::nf_lines::address_lines;
+
+const NAMESPACE: &str = "http://hl7.org/fhir";
+
+/// Time period during which an address was in use
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FHIR_Period {
+    /// Starting time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<String>,
+    /// End time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+}
+
+/// The HL7 FHIR R4 `Address` datatype
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FHIR_Address {
+    /// home | work | temp | old | billing
+    #[serde(rename = "use", default, skip_serializing_if = "Option::is_none")]
+    pub use_: Option<String>,
+    /// postal | physical | both
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Text representation of the address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Street name, number, direction & P.O. Box etc.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub line: Vec<String>,
+    /// Name of city, town etc.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// District name (aka county)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub district: Option<String>,
+    /// Sub-unit of country (abbreviations ok)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Postal code for area
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+    /// Country (e.g. can be ISO 3166 2 or 3 letter code)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// Time period when address was/is in use
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period: Option<FHIR_Period>,
+}
+
+impl FHIR_Address {
+    /// Extracts every `Address` found in a FHIR JSON resource (Patient, Organization,
+    /// Bundle, ...) or a bare `Address`
+    pub fn extract_all_json(value: &Value) -> Result<Vec<Self>> {
+        let mut res = Vec::new();
+
+        match value.get("resourceType") {
+            Some(_) => collect_json(value, &mut res)?,
+            None => res.push(serde_json::from_value(value.clone())?),
+        }
+
+        Ok(res)
+    }
+
+    /// Extracts every `address` element found in a FHIR XML resource, or a bare
+    /// `Address` element
+    pub fn extract_all_xml(s: &str) -> Result<Vec<Self>> {
+        let mut reader = Reader::from_str(s);
+        reader.config_mut().trim_text(true);
+
+        let mut res = Vec::new();
+        let mut current: Option<FHIR_Address> = None;
+        let mut depth = 0;
+        let mut in_period = false;
+
+        loop {
+            let event = reader.read_event()?;
+            let (element, is_start) = match &event {
+                Event::Start(e) => (Some(e), true),
+                Event::Empty(e) => (Some(e), false),
+                _ => (None, false),
+            };
+
+            if let Some(e) = element {
+                let name = e.local_name();
+                let name = std::str::from_utf8(name.as_ref())?.to_string();
+
+                match current.as_mut() {
+                    None if name.eq_ignore_ascii_case("address") => {
+                        current = Some(FHIR_Address::default());
+                        depth = 0;
+                        if !is_start {
+                            res.extend(current.take());
+                        }
+                        continue;
+                    }
+                    None => continue,
+                    Some(address) => {
+                        let value = xml_value(e)?;
+                        match (in_period, name.as_str(), value) {
+                            (false, "period", _) => in_period = is_start,
+                            (true, "start", value) => address.period_mut().start = value,
+                            (true, "end", value) => address.period_mut().end = value,
+                            (false, "line", Some(value)) => address.line.push(value),
+                            (false, name, value) => address.set(name, value),
+                            _ => {}
+                        }
+                    }
+                }
+
+                if is_start {
+                    depth += 1;
+                }
+                continue;
+            }
+
+            match event {
+                Event::End(_) if current.is_some() => {
+                    if depth == 0 {
+                        res.extend(current.take());
+                    } else {
+                        depth -= 1;
+                        in_period = false;
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Extracts every `Address` from either a FHIR JSON or FHIR XML document
+    pub fn parse_all(s: &str) -> Result<Vec<Self>> {
+        if s.trim_start().starts_with('<') {
+            Self::extract_all_xml(s)
+        } else {
+            Self::extract_all_json(&serde_json::from_str(s)?)
+        }
+    }
+
+    /// Converts the address into a FHIR XML `address` element
+    pub fn to_xml(&self) -> String {
+        let element = |name: &str, value: &Option<String>| match value {
+            Some(value) => format!("<{name} value=\"{}\"/>", escape(value)),
+            None => String::new(),
+        };
+
+        let mut xml = format!("<address xmlns=\"{NAMESPACE}\">");
+        xml += &element("use", &self.use_);
+        xml += &element("type", &self.kind);
+        xml += &element("text", &self.text);
+        for line in &self.line {
+            xml += &element("line", &Some(line.clone()));
+        }
+        xml += &element("city", &self.city);
+        xml += &element("district", &self.district);
+        xml += &element("state", &self.state);
+        xml += &element("postalCode", &self.postal_code);
+        xml += &element("country", &self.country);
+        if let Some(period) = &self.period {
+            xml += "<period>";
+            xml += &element("start", &period.start);
+            xml += &element("end", &period.end);
+            xml += "</period>";
+        }
+        xml += "</address>";
+
+        xml
+    }
+
+    fn period_mut(&mut self) -> &mut FHIR_Period {
+        self.period.get_or_insert_with(FHIR_Period::default)
+    }
+
+    fn set(&mut self, name: &str, value: Option<String>) {
+        match name {
+            "use" => self.use_ = value,
+            "type" => self.kind = value,
+            "text" => self.text = value,
+            "city" => self.city = value,
+            "district" => self.district = value,
+            "state" => self.state = value,
+            "postalCode" => self.postal_code = value,
+            "country" => self.country = value,
+            _ => {}
+        }
+    }
+}
+
+impl FromStr for FHIR_Address {
+    type Err = Box<dyn Error>;
+
+    /// Parses the first `Address` of a FHIR JSON or XML document
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_all(s)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| "No FHIR `Address` found".into())
+    }
+}
+
+impl TryFrom<Address> for FHIR_Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: Address) -> Result<Self> {
+        let optional = |x: String| Some(x).filter(|x| !x.is_empty());
+
+        Ok(FHIR_Address {
+            kind: Some("postal".to_string()),
+            line: address_lines(&value),
+            city: optional(value.town_name),
+            district: value.district_name,
+            state: value.country_sub_division,
+            postal_code: optional(value.post_code),
+            country: optional(value.country),
+            ..Default::default()
+        })
+    }
+}
+
+/// Reads the `value` attribute of a FHIR XML primitive element
+fn xml_value(e: &BytesStart) -> Result<Option<String>> {
+    Ok(match e.try_get_attribute("value")? {
+        Some(attr) => Some(attr.unescape_value()?.into_owned()),
+        None => None,
+    })
+}
+
+fn collect_json(value: &Value, res: &mut Vec<FHIR_Address>) -> Result<()> {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                match (key.as_str(), child) {
+                    ("address", Value::Array(items)) => {
+                        for item in items {
+                            res.push(serde_json::from_value(item.clone())?);
+                        }
+                    }
+                    ("address", Value::Object(_)) => {
+                        res.push(serde_json::from_value(child.clone())?)
+                    }
+                    _ => collect_json(child, res)?,
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_json(item, res)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json() {
+        let content = r#"{
+            "resourceType": "Patient",
+            "name": [{ "family": "DURAND", "given": ["Jean"] }],
+            "address": [{
+                "use": "home",
+                "type": "postal",
+                "line": ["25D RUE DES FLEURS"],
+                "city": "LIBOURNE",
+                "postalCode": "33500",
+                "country": "FR",
+                "period": { "start": "2020-01-01" }
+            }]
+        }"#;
+
+        let result = FHIR_Address {
+            use_: "home".to_string().into(),
+            kind: "postal".to_string().into(),
+            line: vec!["25D RUE DES FLEURS".to_string()],
+            city: "LIBOURNE".to_string().into(),
+            postal_code: "33500".to_string().into(),
+            country: "FR".to_string().into(),
+            period: FHIR_Period {
+                start: "2020-01-01".to_string().into(),
+                end: None,
+            }
+            .into(),
+            ..Default::default()
+        };
+
+        let parse: FHIR_Address = content.parse().expect("Cannot parse FHIR!");
+        assert_eq!(parse, result);
+    }
+
+    #[test]
+    fn test_parse_xml() {
+        let content = r#"<Organization xmlns="http://hl7.org/fhir">
+            <name value="DURAND SA"/>
+            <address>
+                <use value="work"/>
+                <line value="Zone industrielle de la Ballastrierre Ouest"/>
+                <line value="22BIS RUE DES FLEURS"/>
+                <city value="LIBOURNE CEDEX"/>
+                <postalCode value="33506"/>
+                <country value="FR"/>
+                <period><start value="2020-01-01"/></period>
+            </address>
+            <contact>
+                <address>
+                    <city value="PARIS"/>
+                </address>
+            </contact>
+        </Organization>"#;
+
+        let parse = FHIR_Address::parse_all(content).expect("Cannot parse FHIR XML!");
+
+        assert_eq!(parse.len(), 2);
+        assert_eq!(parse[0].use_.as_deref(), Some("work"));
+        assert_eq!(parse[0].line.len(), 2);
+        assert_eq!(parse[0].postal_code.as_deref(), Some("33506"));
+        assert_eq!(
+            parse[0].period.as_ref().and_then(|x| x.start.as_deref()),
+            Some("2020-01-01")
+        );
+        assert_eq!(parse[1].city.as_deref(), Some("PARIS"));
+    }
+
+    #[test]
+    fn test_xml() {
+        let address = FHIR_Address {
+            line: vec!["25D RUE DES FLEURS".to_string()],
+            city: "LIBOURNE".to_string().into(),
+            postal_code: "33500".to_string().into(),
+            country: "FR".to_string().into(),
+            ..Default::default()
+        };
+
+        let content = r#"<address xmlns="http://hl7.org/fhir"><line value="25D RUE DES FLEURS"/><city value="LIBOURNE"/><postalCode value="33500"/><country value="FR"/></address>"#;
+
+        assert_eq!(address.to_xml(), content);
+        assert_eq!(
+            FHIR_Address::parse_all(content).expect("error"),
+            vec![address]
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use std::error::Error;
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::common::Result;
+
+use super::address::{Address, Coordinates};
+
+/// A GeoJSON geometry (RFC 7946), only `Point` geometries carry an address position
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GeoJSON_Geometry {
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// `[longitude, latitude]` for a `Point`
+    pub coordinates: Value,
+}
+
+/// A GeoJSON `Feature` whose properties are the fields of an `Address`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GeoJSON_Feature {
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// UUID of the address in the repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// `null` when the address has no coordinates
+    pub geometry: Option<GeoJSON_Geometry>,
+    pub properties: Address,
+}
+
+/// A GeoJSON `FeatureCollection` of addresses
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GeoJSON_FeatureCollection {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub features: Vec<GeoJSON_Feature>,
+}
+
+impl GeoJSON_Geometry {
+    /// Creates a `Point` geometry
+    pub fn point(coordinates: Coordinates) -> Self {
+        GeoJSON_Geometry {
+            kind: "Point".to_string(),
+            coordinates: serde_json::json!([coordinates.lon, coordinates.lat]),
+        }
+    }
+
+    /// Returns the position of a `Point` geometry
+    ///
+    /// Fails for other geometries and for coordinates that are not a
+    /// `[longitude, latitude]` position.
+    pub fn as_point(&self) -> Result<Coordinates> {
+        if self.kind != "Point" {
+            return Err(format!("The geometry `{}` is not a `Point`", self.kind).into());
+        }
+        let position = self.coordinates.as_array().map(|x| x.as_slice());
+        match position {
+            Some([lon, lat, ..]) => match (lon.as_f64(), lat.as_f64()) {
+                (Some(lon), Some(lat))
+                    if (-180.0..=180.0).contains(&lon) && (-90.0..=90.0).contains(&lat) =>
+                {
+                    Ok(Coordinates { lat, lon })
+                }
+                _ => Err(format!("Invalid `Point` coordinates `{}`", self.coordinates).into()),
+            },
+            _ => Err(format!("Invalid `Point` coordinates `{}`", self.coordinates).into()),
+        }
+    }
+}
+
+impl GeoJSON_Feature {
+    /// Creates the feature of a stored address
+    pub fn new(id: Option<Uuid>, address: &Address) -> Self {
+        let mut properties = address.clone();
+        let geometry = properties.coordinates.take().map(GeoJSON_Geometry::point);
+
+        GeoJSON_Feature {
+            kind: "Feature".to_string(),
+            id: id.map(|x| x.to_string()),
+            geometry,
+            properties,
+        }
+    }
+}
+
+impl GeoJSON_FeatureCollection {
+    /// Creates a collection from stored addresses and their UUIDs
+    pub fn new(addresses: &[(Uuid, Address)]) -> Self {
+        GeoJSON_FeatureCollection {
+            kind: "FeatureCollection".to_string(),
+            features: addresses
+                .iter()
+                .map(|(id, address)| GeoJSON_Feature::new(Some(*id), address))
+                .collect(),
+        }
+    }
+
+    /// Extracts the address features of a `FeatureCollection` or a single `Feature`
+    ///
+    /// Features with a geometry other than `Point`, or invalid, are skipped, see
+    /// [`Self::parse_features`].
+    pub fn parse_all(s: &str) -> Result<Vec<GeoJSON_Feature>> {
+        Ok(Self::parse_features(s)?.0)
+    }
+
+    /// Extracts the address features, with a message for each skipped feature
+    ///
+    /// Fails when no feature can be read.
+    pub fn parse_features(s: &str) -> Result<(Vec<GeoJSON_Feature>, Vec<String>)> {
+        let value: Value = serde_json::from_str(s)?;
+        let features = match value.get("type").and_then(|x| x.as_str()) {
+            Some("FeatureCollection") => value["features"]
+                .as_array()
+                .ok_or("The `features` member is missing")?
+                .clone(),
+            Some("Feature") => vec![value],
+            _ => return Err("Input must be a GeoJSON `FeatureCollection` or `Feature`".into()),
+        };
+
+        let feature = |value: Value| -> Result<GeoJSON_Feature> {
+            let geometry: Option<GeoJSON_Geometry> =
+                serde_json::from_value(value["geometry"].clone())?;
+            if let Some(geometry) = geometry {
+                geometry.as_point()?;
+            }
+            Ok(serde_json::from_value(value)?)
+        };
+
+        let mut res = Vec::new();
+        let mut skipped = Vec::new();
+        for (i, value) in features.into_iter().enumerate() {
+            match feature(value) {
+                Ok(feature) => res.push(feature),
+                Err(e) => skipped.push(format!("Feature {}: {e}", i + 1)),
+            }
+        }
+
+        match (res.is_empty(), skipped.is_empty()) {
+            (true, true) => Err("No GeoJSON feature found".into()),
+            (true, false) => {
+                Err(format!("No GeoJSON `Point` feature found\n{}", skipped.join("\n")).into())
+            }
+            _ => Ok((res, skipped)),
+        }
+    }
+}
+
+impl FromStr for GeoJSON_Feature {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        GeoJSON_FeatureCollection::parse_all(s)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| "No GeoJSON `Point` feature found".into())
+    }
+}
+
+impl TryFrom<Address> for GeoJSON_Feature {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: Address) -> Result<Self> {
+        Ok(GeoJSON_Feature::new(None, &value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collection() {
+        let id = Uuid::new_v4();
+        let address = Address {
+            street_name: "RUE DES FLEURS".to_string().into(),
+            building_number: "25D".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            coordinates: Some(Coordinates {
+                lat: 44.9153,
+                lon: -0.2421,
+            }),
+            ..Default::default()
+        };
+
+        let collection = GeoJSON_FeatureCollection::new(&[(id, address.clone())]);
+        let json = serde_json::to_value(&collection).expect("error");
+        assert_eq!(json["type"], "FeatureCollection");
+        assert_eq!(json["features"][0]["id"], id.to_string());
+        assert_eq!(
+            json["features"][0]["geometry"]["coordinates"],
+            serde_json::json!([-0.2421, 44.9153])
+        );
+        assert_eq!(json["features"][0]["properties"]["post_code"], "33500");
+        assert!(json["features"][0]["properties"]
+            .get("coordinates")
+            .is_none());
+
+        let features =
+            GeoJSON_FeatureCollection::parse_all(&json.to_string()).expect("Cannot parse!");
+        assert_eq!(features, collection.features);
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let content = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "geometry": { "type": "Point", "coordinates": [2.3522, 48.8566] },
+                    "properties": { "post_code": "75001", "town_name": "PARIS", "country": "FR" }
+                },
+                {
+                    "type": "Feature",
+                    "geometry": { "type": "LineString", "coordinates": [[0, 0], [1, 1]] },
+                    "properties": {}
+                }
+            ]
+        }"#;
+
+        let (features, skipped) =
+            GeoJSON_FeatureCollection::parse_features(content).expect("Cannot parse!");
+        assert_eq!(features.len(), 1);
+        assert_eq!(
+            features[0]
+                .geometry
+                .as_ref()
+                .map(|x| x.as_point().expect("error")),
+            Some(Coordinates {
+                lat: 48.8566,
+                lon: 2.3522
+            })
+        );
+        assert_eq!(
+            skipped,
+            vec!["Feature 2: The geometry `LineString` is not a `Point`"]
+        );
+    }
+
+    #[test]
+    fn test_parse_all_invalid() {
+        let content = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "geometry": { "type": "Point", "coordinates": [1] },
+                    "properties": { "post_code": "75001", "town_name": "PARIS", "country": "FR" }
+                },
+                {
+                    "type": "Feature",
+                    "geometry": { "type": "LineString", "coordinates": [[0, 0], [1, 1]] },
+                    "properties": {}
+                }
+            ]
+        }"#;
+
+        let error = GeoJSON_FeatureCollection::parse_all(content)
+            .expect_err("Nothing should be imported")
+            .to_string();
+        assert!(error.contains("Feature 1: Invalid `Point` coordinates `[1]`"));
+        assert!(error.contains("Feature 2: The geometry `LineString` is not a `Point`"));
+
+        let content = r#"{ "type": "FeatureCollection", "features": [] }"#;
+        assert!(GeoJSON_FeatureCollection::parse_all(content).is_err());
+    }
+}
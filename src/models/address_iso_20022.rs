@@ -47,9 +47,9 @@ impl Validate for ISO_20022 {
     fn validate(&self) -> Result<()> {
         let check = |x: &str, max_len: usize, field_name: &str| {
             if x.len() > max_len {
-                return Err(format!(
+                Err(format!(
                     "The field `{field_name}` must have less than {max_len} chars"
-                ));
+                ))
             } else {
                 Ok(())
             }
@@ -74,6 +74,17 @@ impl Validate for ISO_20022 {
     }
 }
 
+impl ISO_20022 {
+    /// Writes the `PstlAdr` element, indented with 4 spaces
+    pub fn to_xml(&self) -> Result<String> {
+        let mut buffer = String::new();
+        let mut serializer = quick_xml::se::Serializer::new(&mut buffer);
+        serializer.indent(' ', 4);
+        self.serialize(serializer)?;
+        Ok(buffer)
+    }
+}
+
 impl FromStr for ISO_20022 {
     type Err = Box<dyn Error>;
 
@@ -132,7 +143,7 @@ mod tests {
         "#;
 
         let result = ISO_20022 {
-            StrtNm: "25D RUE DES FLEURS".to_string().into(),
+            StrtNm: "25D RUE DES FLEURS".to_string(),
             PstCd: "33500".to_string(),
             TwnNm: "LISBOURNE".to_string(),
             Ctry: "FR".to_string(),
@@ -148,7 +159,7 @@ mod tests {
         let content = r#"<PstlAdr><Dept/><SubDept/><StrtNm>25D RUE DES FLEURS</StrtNm><BldgNb/><BldgNm/><Flr/><PstBx/><Room/><PstCd>33500</PstCd><TwnNm>LISBOURNE</TwnNm><TwnLctnNm/><DstrctNm/><CtrySubDvsn/><Ctry>FR</Ctry></PstlAdr>"#;
 
         let result = ISO_20022 {
-            StrtNm: "25D RUE DES FLEURS".to_string().into(),
+            StrtNm: "25D RUE DES FLEURS".to_string(),
             PstCd: "33500".to_string(),
             TwnNm: "LISBOURNE".to_string(),
             Ctry: "FR".to_string(),
@@ -163,7 +174,7 @@ mod tests {
     #[test]
     fn test_validate() {
         let result = ISO_20022 {
-            StrtNm: "25D RUE DES FLEURS".to_string().into(),
+            StrtNm: "25D RUE DES FLEURS".to_string(),
             PstCd: "XXXXXXXXXXXXXXXXXXXX".to_string(),
             TwnNm: "LISBOURNE".to_string(),
             Ctry: "FR".to_string(),
@@ -64,7 +64,7 @@ impl TryFrom<Address> for NF_Z10_011_Enterprise {
     type Error = Box<dyn Error>;
 
     fn try_from(value: Address) -> Result<Self> {
-        let country = alpha2_to_country(&value.country).to_string();
+        let country = alpha2_to_country(&value.country)?.to_string();
         Ok(NF_Z10_011_Enterprise {
             lines: [
                 value.name.unwrap_or_default(),
@@ -102,9 +102,9 @@ mod tests {
                 .to_string()
                 .into(),
             post_box: "BP 40122".to_string().into(),
-            post_code: "33506".to_string().into(),
-            town_name: "LIBOURNE CEDEX".to_string().into(),
-            country: "FR".to_string().into(),
+            post_code: "33506".to_string(),
+            town_name: "LIBOURNE CEDEX".to_string(),
+            country: "FR".to_string(),
             ..Default::default()
         };
 
@@ -131,7 +131,7 @@ mod tests {
             street_name: "56 RUE EMILE ZOLA".to_string().into(),
             floor: "Résidence des Capucins Bâtiment Quater".to_string().into(),
             post_box: "BP 90432".to_string().into(),
-            post_code: "34092".to_string().into(),
+            post_code: "34092".to_string(),
             town_name: "MONTPELLIER CEDEX 5".to_string(),
             town_location_name: "MONTFERRIER SUR LEZ".to_string().into(),
             country: "FR".to_string(),
@@ -64,7 +64,7 @@ impl TryFrom<Address> for NF_Z10_011_Individual {
     type Error = Box<dyn Error>;
 
     fn try_from(value: Address) -> Result<Self> {
-        let country = alpha2_to_country(&value.country).to_string();
+        let country = alpha2_to_country(&value.country)?.to_string();
         Ok(NF_Z10_011_Individual {
             lines: [
                 value.name.unwrap_or_default(),
@@ -0,0 +1,361 @@
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::common::{normalize_country, Result};
+
+use super::address::{Address, Coordinates};
+
+/// Words completing a house number, e.g. `25 BIS`
+const SUFFIXES: &[&str] = &["BIS", "TER", "QUATER"];
+
+/// The `addr:*` tags of an OpenStreetMap object
+///
+/// ```text
+/// addr:housenumber=25D
+/// addr:street=Rue des Fleurs
+/// addr:postcode=33500
+/// addr:city=Libourne
+/// addr:country=FR
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OSM_Address {
+    /// `name`, the addressee
+    pub name: String,
+    /// `addr:housenumber`
+    pub housenumber: String,
+    /// `addr:street`
+    pub street: String,
+    /// `addr:unit`, flat or suite
+    pub unit: String,
+    /// `addr:floor`
+    pub floor: String,
+    /// `addr:postcode`
+    pub postcode: String,
+    /// `addr:city`
+    pub city: String,
+    /// `addr:place`, named place used instead of a street
+    pub place: String,
+    /// `addr:suburb`
+    pub suburb: String,
+    /// `addr:province`, or `addr:state`
+    pub province: String,
+    /// `addr:country`, ISO 3166-1 alpha-2 code
+    pub country: String,
+    /// Position of the node
+    pub coordinates: Option<Coordinates>,
+}
+
+impl OSM_Address {
+    /// Returns the tags with a value, in the order of the fields
+    pub fn tags(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("name", &self.name),
+            ("addr:housenumber", &self.housenumber),
+            ("addr:street", &self.street),
+            ("addr:unit", &self.unit),
+            ("addr:floor", &self.floor),
+            ("addr:place", &self.place),
+            ("addr:suburb", &self.suburb),
+            ("addr:postcode", &self.postcode),
+            ("addr:city", &self.city),
+            ("addr:province", &self.province),
+            ("addr:country", &self.country),
+        ]
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(key, value)| (key, value.as_str()))
+        .collect()
+    }
+
+    /// Sets a tag, other keys than `name` and the supported `addr:*` are ignored
+    pub fn set_tag(&mut self, key: &str, value: &str) {
+        let value = value.trim().to_string();
+        match key.trim() {
+            "name" => self.name = value,
+            "addr:housenumber" => self.housenumber = value,
+            "addr:street" => self.street = value,
+            "addr:unit" => self.unit = value,
+            "addr:floor" => self.floor = value,
+            "addr:postcode" => self.postcode = value,
+            "addr:city" => self.city = value,
+            "addr:place" => self.place = value,
+            "addr:suburb" => self.suburb = value,
+            "addr:province" | "addr:state" => self.province = value,
+            "addr:country" => self.country = value,
+            _ => {}
+        }
+    }
+
+    /// Checks whether at least one `addr:*` tag is set
+    pub fn is_address(&self) -> bool {
+        self.tags().iter().any(|(key, _)| key.starts_with("addr:"))
+    }
+
+    /// Extracts the nodes with `addr:*` tags of an OSM XML document
+    pub fn parse_all_xml(s: &str) -> Result<Vec<Self>> {
+        let mut reader = Reader::from_str(s);
+        reader.config_mut().trim_text(true);
+
+        let mut res = Vec::new();
+        let mut current: Option<OSM_Address> = None;
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) if e.local_name().as_ref() == b"node" => {
+                    current = Some(OSM_Address {
+                        coordinates: node_coordinates(&e)?,
+                        ..Default::default()
+                    });
+                }
+                Event::Empty(e) if e.local_name().as_ref() == b"tag" => {
+                    if let Some(address) = current.as_mut() {
+                        let key = attribute(&e, "k")?.unwrap_or_default();
+                        let value = attribute(&e, "v")?.unwrap_or_default();
+                        address.set_tag(&key, &value);
+                    }
+                }
+                Event::End(e) if e.local_name().as_ref() == b"node" => {
+                    res.extend(current.take().filter(|x| x.is_address()));
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Extracts the addresses of an OSM XML document, or of `key=value` tags
+    pub fn parse_all(s: &str) -> Result<Vec<Self>> {
+        match s.trim_start().starts_with('<') {
+            true => Self::parse_all_xml(s),
+            false => Ok(vec![s.parse()?]),
+        }
+    }
+
+    /// Writes the address as the tags of a new node in an OSM XML document
+    ///
+    /// Fails without coordinates, as a node needs a position.
+    pub fn to_xml(&self) -> Result<String> {
+        let Coordinates { lat, lon } = self
+            .coordinates
+            .ok_or("An OSM node needs coordinates, geocode the address first")?;
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml += "<osm version=\"0.6\" generator=\"address\">\n";
+        xml += &format!("  <node id=\"-1\" lat=\"{lat}\" lon=\"{lon}\">\n");
+        for (key, value) in self.tags() {
+            xml += &format!("    <tag k=\"{key}\" v=\"{}\"/>\n", escape(value));
+        }
+        xml += "  </node>\n</osm>";
+
+        Ok(xml)
+    }
+}
+
+/// Splits a leading house number, e.g. `25D` or `25 BIS`, from a street line
+fn split_housenumber(street: &str) -> (String, String) {
+    let mut words = street.split_whitespace().peekable();
+    let Some(number) = words
+        .next_if(|x| x.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|x| x.to_string())
+    else {
+        return (String::new(), street.trim().to_string());
+    };
+    let number = match words.next_if(|x| SUFFIXES.contains(&x.to_uppercase().as_str())) {
+        Some(suffix) => format!("{number} {suffix}"),
+        None => number,
+    };
+    (number, words.collect::<Vec<_>>().join(" "))
+}
+
+/// Reads the `lat` and `lon` attributes of a node
+fn node_coordinates(e: &BytesStart) -> Result<Option<Coordinates>> {
+    let lat = attribute(e, "lat")?.and_then(|x| x.parse().ok());
+    let lon = attribute(e, "lon")?.and_then(|x| x.parse().ok());
+    Ok(lat.zip(lon).map(|(lat, lon)| Coordinates { lat, lon }))
+}
+
+fn attribute(e: &BytesStart, name: &str) -> Result<Option<String>> {
+    Ok(match e.try_get_attribute(name)? {
+        Some(attr) => Some(attr.unescape_value()?.into_owned()),
+        None => None,
+    })
+}
+
+impl FromStr for OSM_Address {
+    type Err = Box<dyn Error>;
+
+    /// Parses `key=value` tags, one per line
+    fn from_str(s: &str) -> Result<Self> {
+        if s.trim_start().starts_with('<') {
+            return Self::parse_all_xml(s)?
+                .into_iter()
+                .next()
+                .ok_or_else(|| "No node with `addr:*` tags found".into());
+        }
+
+        let mut res = OSM_Address::default();
+        for line in s.lines().map(|x| x.trim()).filter(|x| !x.is_empty()) {
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(format!("The tag `{line}` must be written key=value"))?;
+            res.set_tag(key, value);
+        }
+
+        match res.is_address() {
+            true => Ok(res),
+            false => Err("No `addr:*` tag found".into()),
+        }
+    }
+}
+
+impl fmt::Display for OSM_Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tags: Vec<String> = self
+            .tags()
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect();
+        write!(f, "{}", tags.join("\n"))
+    }
+}
+
+impl TryFrom<Address> for OSM_Address {
+    type Error = Box<dyn Error>;
+
+    /// A house number written at the start of the street name goes to
+    /// `addr:housenumber`
+    fn try_from(value: Address) -> Result<Self> {
+        let street = value.street_name.unwrap_or_default();
+        let (housenumber, street) = match value.building_number {
+            Some(number) if !number.is_empty() => (number, street),
+            _ => split_housenumber(&street),
+        };
+
+        Ok(OSM_Address {
+            name: value.name.unwrap_or_default(),
+            housenumber,
+            street,
+            unit: value.room.unwrap_or_default(),
+            floor: value.floor.unwrap_or_default(),
+            postcode: value.post_code,
+            city: value.town_name,
+            place: value.town_location_name.unwrap_or_default(),
+            suburb: value.district_name.unwrap_or_default(),
+            province: value.country_sub_division.unwrap_or_default(),
+            country: normalize_country(&value.country)?,
+            coordinates: value.coordinates,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let content = "addr:housenumber=25D\naddr:street=Rue des Fleurs\naddr:postcode=33500\naddr:city=Libourne\naddr:country=FR\nbuilding=yes";
+
+        let result = OSM_Address {
+            housenumber: "25D".to_string(),
+            street: "Rue des Fleurs".to_string(),
+            postcode: "33500".to_string(),
+            city: "Libourne".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let parse: OSM_Address = content.parse().expect("Cannot parse OSM tags!");
+        assert_eq!(parse, result);
+        assert_eq!(
+            parse.to_string(),
+            content.trim_end_matches("\nbuilding=yes")
+        );
+        assert!("building=yes".parse::<OSM_Address>().is_err());
+    }
+
+    #[test]
+    fn test_parse_all_xml() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<osm version="0.6" generator="osmium">
+  <node id="1" lat="44.9153" lon="-0.2421">
+    <tag k="addr:housenumber" v="25D"/>
+    <tag k="addr:street" v="Rue des Fleurs"/>
+    <tag k="addr:postcode" v="33500"/>
+    <tag k="addr:city" v="Libourne"/>
+  </node>
+  <node id="2" lat="44.9" lon="-0.2"/>
+  <node id="3" lat="44.6051" lon="-0.9375">
+    <tag k="amenity" v="townhall"/>
+  </node>
+  <node id="4" lat="48.8566" lon="2.3522">
+    <tag k="name" v="Caf&#233; de la Paix"/>
+    <tag k="addr:housenumber" v="5"/>
+    <tag k="addr:place" v="Place de l'Op&#233;ra"/>
+    <tag k="addr:postcode" v="75009"/>
+    <tag k="addr:city" v="Paris"/>
+  </node>
+</osm>"#;
+
+        let addresses = OSM_Address::parse_all_xml(content).expect("Cannot parse OSM XML!");
+        assert_eq!(addresses.len(), 2);
+        assert_eq!(
+            addresses[0].coordinates,
+            Some(Coordinates {
+                lat: 44.9153,
+                lon: -0.2421
+            })
+        );
+        assert_eq!(addresses[1].name, "Café de la Paix");
+        assert_eq!(addresses[1].place, "Place de l'Opéra");
+
+        let xml = addresses[1].to_xml().expect("error");
+        assert_eq!(
+            OSM_Address::parse_all_xml(&xml).expect("error"),
+            vec![addresses[1].clone()]
+        );
+
+        let address = OSM_Address {
+            coordinates: None,
+            ..addresses[1].clone()
+        };
+        assert!(address.to_xml().is_err());
+    }
+
+    #[test]
+    fn test_housenumber() {
+        let address = Address {
+            street_name: "25D RUE DES FLEURS".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+        let osm = OSM_Address::try_from(address.clone()).expect("error");
+        assert_eq!(osm.housenumber, "25D");
+        assert_eq!(osm.street, "RUE DES FLEURS");
+
+        let address = Address {
+            street_name: "22 BIS RUE DES FLEURS".to_string().into(),
+            ..address
+        };
+        let osm = OSM_Address::try_from(address.clone()).expect("error");
+        assert_eq!(osm.housenumber, "22 BIS");
+        assert_eq!(osm.street, "RUE DES FLEURS");
+
+        let address = Address {
+            street_name: "RUE DES FLEURS".to_string().into(),
+            ..address
+        };
+        let osm = OSM_Address::try_from(address).expect("error");
+        assert_eq!(osm.housenumber, "");
+        assert_eq!(osm.street, "RUE DES FLEURS");
+    }
+}
@@ -0,0 +1,352 @@
+use std::fmt;
+use std::str::FromStr;
+
+use std::error::Error;
+
+use crate::common::{find_country, normalize_country, Result};
+
+use super::address::Address;
+use super::validate::Validate;
+
+/// Words starting a sub-building name (`FLAT 2`, `UNIT 5`, ...)
+const SUB_BUILDING_WORDS: &[&str] = &["FLAT", "APARTMENT", "UNIT", "SUITE", "STUDIO", "ROOM"];
+
+/// A British address, following the Royal Mail PAF (BS7666) structure
+///
+/// ```text
+/// Acme Ltd
+/// Flat 2
+/// Rose Court
+/// 10 High Street
+/// Headington
+/// OXFORD
+/// OX3 9DU
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PAF_Address {
+    /// Organisation or addressee
+    pub organisation: String,
+    /// Flat, unit or suite inside a building
+    pub sub_building: String,
+    /// Building name, or a number with a letter (`1A`)
+    pub building_name: String,
+    /// Building number
+    pub building_number: String,
+    /// Street
+    pub thoroughfare: String,
+    /// Village or part of the post town
+    pub dependent_locality: String,
+    /// Post town, in capitals
+    pub post_town: String,
+    /// Postcode, e.g. `SW1A 2AA`
+    pub postcode: String,
+}
+
+/// Checks a UK postcode: outward code (area and district), a space and the inward code
+///
+/// # Example
+///
+/// ```
+/// use address::models::address_paf::is_valid_postcode;
+/// assert!(is_valid_postcode("SW1A 2AA"));
+/// assert!(is_valid_postcode("GIR 0AA"));
+/// assert!(!is_valid_postcode("QA1 1AA"));
+/// ```
+pub fn is_valid_postcode(postcode: &str) -> bool {
+    // Girobank
+    if postcode == "GIR 0AA" {
+        return true;
+    }
+
+    let Some((outward, inward)) = postcode.split_once(' ') else {
+        return false;
+    };
+
+    let letter = |c: char, excluded: &str| c.is_ascii_uppercase() && !excluded.contains(c);
+    let digit = |c: char| c.is_ascii_digit();
+
+    let outward_ok = match outward.chars().collect::<Vec<_>>().as_slice() {
+        // A9, A99, A9A
+        [a, b] => letter(*a, "QVX") && digit(*b),
+        [a, b, c] if digit(*b) => {
+            letter(*a, "QVX") && (digit(*c) || "ABCDEFGHJKPSTUW".contains(*c))
+        }
+        // AA9
+        [a, b, c] => letter(*a, "QVX") && letter(*b, "IJZ") && digit(*c),
+        // AA99, AA9A
+        [a, b, c, d] => {
+            letter(*a, "QVX")
+                && letter(*b, "IJZ")
+                && digit(*c)
+                && (digit(*d) || "ABEHMNPRVWXY".contains(*d))
+        }
+        _ => false,
+    };
+
+    let inward_ok = match inward.chars().collect::<Vec<_>>().as_slice() {
+        [a, b, c] => digit(*a) && letter(*b, "CIKMOV") && letter(*c, "CIKMOV"),
+        _ => false,
+    };
+
+    outward_ok && inward_ok
+}
+
+impl PAF_Address {
+    /// Returns the lines of the address, following the PAF layout rules
+    pub fn lines(&self) -> Vec<String> {
+        // A building name such as `1A` is written on the thoroughfare line
+        let name_is_number = self.building_name.starts_with(|c: char| c.is_ascii_digit())
+            && !self.building_name.contains(' ');
+
+        let street = match name_is_number {
+            true => [&self.building_name, &self.thoroughfare],
+            false => [&self.building_number, &self.thoroughfare],
+        }
+        .into_iter()
+        .filter(|x| !x.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+        let building_name = match name_is_number {
+            true => String::new(),
+            false => self.building_name.clone(),
+        };
+
+        [
+            self.organisation.clone(),
+            self.sub_building.clone(),
+            building_name,
+            street,
+            self.dependent_locality.clone(),
+            self.post_town.to_uppercase(),
+            self.postcode.to_uppercase(),
+        ]
+        .into_iter()
+        .filter(|x| !x.is_empty())
+        .collect()
+    }
+}
+
+impl Validate for PAF_Address {
+    fn validate(&self) -> Result<()> {
+        let check = |x: &str, max_len: usize, field_name: &str| {
+            if x.chars().count() > max_len {
+                Err(format!(
+                    "The field `{field_name}` must have less than {max_len} chars"
+                ))
+            } else {
+                Ok(())
+            }
+        };
+
+        check(&self.organisation, 60, "organisation")?;
+        check(&self.sub_building, 30, "sub_building")?;
+        check(&self.building_name, 50, "building_name")?;
+        check(&self.building_number, 4, "building_number")?;
+        check(&self.thoroughfare, 60, "thoroughfare")?;
+        check(&self.dependent_locality, 35, "dependent_locality")?;
+        check(&self.post_town, 30, "post_town")?;
+
+        if self.post_town.is_empty() {
+            return Err("The post town is mandatory".into());
+        }
+        if !is_valid_postcode(&self.postcode) {
+            return Err(format!(
+                "The postcode `{}` is not a valid UK postcode",
+                self.postcode
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for PAF_Address {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut lines: Vec<&str> = s
+            .lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+            .collect();
+
+        if lines.last().is_some_and(|x| find_country(x).is_some()) {
+            lines.pop();
+        }
+
+        let [first, rest @ .., post_town, postcode] = lines.as_slice() else {
+            return Err("Input must contain a post town and a postcode".into());
+        };
+
+        // The first line is the organisation, unless it starts with the building number
+        let starts_with_number = |x: &str| x.starts_with(|c: char| c.is_ascii_digit());
+        let (organisation, middle) = match starts_with_number(first) {
+            true => ("", &lines[..lines.len() - 2]),
+            false => (*first, rest),
+        };
+
+        let mut res = PAF_Address {
+            organisation: organisation.to_string(),
+            post_town: post_town.to_string(),
+            postcode: postcode
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_uppercase(),
+            ..Default::default()
+        };
+
+        // The thoroughfare line starts with the building number, else it is the last line
+        let street = middle
+            .iter()
+            .rposition(|x| starts_with_number(x))
+            .or(middle.len().checked_sub(1));
+
+        if let Some(i) = street {
+            match middle[i].split_once(' ') {
+                Some((number, thoroughfare)) if number.chars().all(|c| c.is_ascii_digit()) => {
+                    res.building_number = number.to_string();
+                    res.thoroughfare = thoroughfare.to_string();
+                }
+                Some((name, thoroughfare)) if name.starts_with(|c: char| c.is_ascii_digit()) => {
+                    res.building_name = name.to_string();
+                    res.thoroughfare = thoroughfare.to_string();
+                }
+                _ => res.thoroughfare = middle[i].to_string(),
+            }
+
+            res.dependent_locality = middle[i + 1..].join(", ");
+
+            for line in &middle[..i] {
+                let word = line.split_whitespace().next().unwrap_or_default();
+                match SUB_BUILDING_WORDS.contains(&word.to_uppercase().as_str()) {
+                    true => res.sub_building = line.to_string(),
+                    false => res.building_name = line.to_string(),
+                }
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+impl fmt::Display for PAF_Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lines().join("\n"))
+    }
+}
+
+impl TryFrom<Address> for PAF_Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: Address) -> Result<Self> {
+        let country = normalize_country(&value.country)?;
+        if !country.is_empty() && country != "GB" {
+            return Err(format!("PAF addresses must be in GB, not `{country}`").into());
+        }
+
+        Ok(PAF_Address {
+            organisation: value.name.unwrap_or_default(),
+            sub_building: value.room.unwrap_or_default(),
+            building_name: value.building_name.unwrap_or_default(),
+            building_number: value.building_number.unwrap_or_default(),
+            thoroughfare: value.street_name.unwrap_or_default(),
+            dependent_locality: value.district_name.unwrap_or_default(),
+            post_town: value.town_name.to_uppercase(),
+            postcode: value.post_code.to_uppercase(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let content = "Acme Ltd\nFlat 2\nRose Court\n10 High Street\nHeadington\nOXFORD\nox3 9du";
+
+        let result = PAF_Address {
+            organisation: "Acme Ltd".to_string(),
+            sub_building: "Flat 2".to_string(),
+            building_name: "Rose Court".to_string(),
+            building_number: "10".to_string(),
+            thoroughfare: "High Street".to_string(),
+            dependent_locality: "Headington".to_string(),
+            post_town: "OXFORD".to_string(),
+            postcode: "OX3 9DU".to_string(),
+        };
+
+        let parse: PAF_Address = content.parse().expect("Cannot parse PAF!");
+        assert_eq!(parse, result);
+        assert!(parse.validate().is_ok());
+
+        let result = PAF_Address {
+            building_number: "10".to_string(),
+            thoroughfare: "Downing Street".to_string(),
+            post_town: "LONDON".to_string(),
+            postcode: "SW1A 2AA".to_string(),
+            ..Default::default()
+        };
+
+        let parse: PAF_Address = "10 Downing Street\nLONDON\nSW1A 2AA"
+            .parse()
+            .expect("Cannot parse PAF!");
+        assert_eq!(parse, result);
+    }
+
+    #[test]
+    fn test_to_string() {
+        let address = PAF_Address {
+            organisation: "Jane Smith".to_string(),
+            building_name: "1A".to_string(),
+            thoroughfare: "Seaview Road".to_string(),
+            post_town: "Brighton".to_string(),
+            postcode: "BN1 1AA".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            address.to_string(),
+            "Jane Smith\n1A Seaview Road\nBRIGHTON\nBN1 1AA"
+        );
+        assert_eq!(
+            address
+                .to_string()
+                .parse::<PAF_Address>()
+                .expect("error")
+                .building_name,
+            "1A"
+        );
+    }
+
+    #[test]
+    fn test_postcode() {
+        for postcode in [
+            "M1 1AE", "B33 8TH", "CR2 6XH", "DN55 1PT", "W1A 0AX", "EC1A 1BB", "GIR 0AA",
+        ] {
+            assert!(is_valid_postcode(postcode), "{postcode}");
+        }
+        for postcode in [
+            "SW1A2AA", "QA1 1AA", "AI1 1AA", "W1I 0AX", "EC1C 1BB", "M1 1CE", "M1 AAE",
+        ] {
+            assert!(!is_valid_postcode(postcode), "{postcode}");
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let address = PAF_Address {
+            thoroughfare: "Downing Street".to_string(),
+            building_number: "10".to_string(),
+            post_town: "LONDON".to_string(),
+            postcode: "SW1A 2AAA".to_string(),
+            ..Default::default()
+        };
+
+        assert!(address.validate().is_err())
+    }
+}
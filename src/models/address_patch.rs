@@ -0,0 +1,323 @@
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+
+use crate::common::Result;
+use crate::models::address::{Address, Coordinates};
+
+/// A change of one field of an address
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldChange {
+    /// Sets a field, `None` clears it
+    Set {
+        field: String,
+        value: Option<String>,
+    },
+    /// Sets or clears the coordinates
+    Coordinates(Option<Coordinates>),
+    /// Fails unless the field has this value, the JSON Patch `test` operation
+    Test {
+        field: String,
+        value: Option<String>,
+    },
+    /// Copies the value of `from` to `field`, fails when `from` is not set
+    Copy { from: String, field: String },
+    /// Clears a field or the coordinates, fails when they are not set, the JSON Patch
+    /// `remove` operation
+    Remove(String),
+}
+
+/// Changes applied in order to an address, from CLI options, a JSON Merge Patch
+/// (RFC 7396) or a JSON Patch (RFC 6902)
+///
+/// Field names are those of `Address::FIELDS`. `post_code`, `town_name` and `country`
+/// are mandatory: a patch clearing them is rejected.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AddressPatch {
+    pub changes: Vec<FieldChange>,
+}
+
+/// Checks that `name` is a field of an address
+fn check_field(name: &str) -> Result<String> {
+    Address::default().field(name)?;
+    Ok(name.to_string())
+}
+
+/// Reads a field value, a string or `null`
+fn field_value(field: &str, value: &Value) -> Result<Option<String>> {
+    match value {
+        Value::Null => Ok(None),
+        Value::String(x) => Ok(Some(x.clone())),
+        _ => Err(format!("The value of `{field}` must be a string or null").into()),
+    }
+}
+
+/// Reads the field name of a JSON Pointer, e.g. `/floor`
+fn pointer_field(pointer: &str) -> Result<String> {
+    let name = pointer
+        .strip_prefix('/')
+        .filter(|x| !x.contains('/'))
+        .ok_or(format!("The path `{pointer}` is not a field of an address"))?;
+    Ok(name.replace("~1", "/").replace("~0", "~"))
+}
+
+impl AddressPatch {
+    pub fn new() -> Self {
+        AddressPatch::default()
+    }
+
+    /// Sets a field, `None` clears it
+    pub fn set(&mut self, field: &str, value: Option<String>) -> Result<()> {
+        let field = check_field(field)?;
+        self.changes.push(FieldChange::Set { field, value });
+        Ok(())
+    }
+
+    /// Clears a field, except a mandatory one
+    pub fn clear(&mut self, field: &str) -> Result<()> {
+        match field {
+            "coordinates" => {
+                self.changes.push(FieldChange::Coordinates(None));
+                Ok(())
+            }
+            field if Address::REQUIRED_FIELDS.contains(&field) => {
+                Err(format!("The field `{field}` is mandatory and cannot be cleared").into())
+            }
+            field => self.set(field, None),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Reads a JSON Merge Patch: an object of fields, `null` clearing a field
+    pub fn from_merge_patch(s: &str) -> Result<Self> {
+        let object: Map<String, Value> = serde_json::from_str(s)
+            .map_err(|e| format!("A JSON Merge Patch must be an object: {e}"))?;
+
+        let mut patch = AddressPatch::new();
+        for (field, value) in &object {
+            match field.as_str() {
+                "coordinates" => {
+                    let coordinates = serde_json::from_value(value.clone())?;
+                    patch.changes.push(FieldChange::Coordinates(coordinates));
+                }
+                field => patch.set(field, field_value(field, value)?)?,
+            }
+        }
+        Ok(patch)
+    }
+
+    /// Reads a JSON Patch: an array of `add`, `replace`, `remove`, `test`, `copy` and
+    /// `move` operations on paths like `/floor`
+    pub fn from_json_patch(s: &str) -> Result<Self> {
+        let operations: Vec<Map<String, Value>> = serde_json::from_str(s)
+            .map_err(|e| format!("A JSON Patch must be an array of operations: {e}"))?;
+
+        let mut patch = AddressPatch::new();
+        for operation in &operations {
+            let member = |name: &str| {
+                operation
+                    .get(name)
+                    .ok_or(format!("A JSON Patch operation has no `{name}`"))
+            };
+            let op = member("op")?.as_str().unwrap_or_default();
+            let path = member("path")?.as_str().unwrap_or_default();
+            let field = pointer_field(path)?;
+
+            let change = match (op, field.as_str()) {
+                ("add" | "replace", "coordinates") => {
+                    FieldChange::Coordinates(serde_json::from_value(member("value")?.clone())?)
+                }
+                ("remove", "coordinates") => FieldChange::Remove("coordinates".to_string()),
+                ("add" | "replace", field) => FieldChange::Set {
+                    value: field_value(field, member("value")?)?,
+                    field: check_field(field)?,
+                },
+                ("remove", field) => FieldChange::Remove(check_field(field)?),
+                ("test", field) => FieldChange::Test {
+                    value: field_value(field, member("value")?)?,
+                    field: check_field(field)?,
+                },
+                ("copy" | "move", field) => {
+                    let from = check_field(&pointer_field(
+                        member("from")?.as_str().unwrap_or_default(),
+                    )?)?;
+                    let field = check_field(field)?;
+                    // A move is a copy, then the removal of `from`
+                    if op == "move" && from != field {
+                        patch.changes.push(FieldChange::Copy {
+                            from: from.clone(),
+                            field,
+                        });
+                        FieldChange::Remove(from)
+                    } else {
+                        FieldChange::Copy { from, field }
+                    }
+                }
+                (op, _) => return Err(format!("Unknown JSON Patch operation `{op}`").into()),
+            };
+            patch.changes.push(change);
+        }
+        Ok(patch)
+    }
+
+    /// Applies the changes to a copy of `address`, nothing is changed if one fails
+    ///
+    /// Fails when a mandatory field set in `address` is cleared.
+    pub fn apply(&self, address: &Address) -> Result<Address> {
+        let mut res = address.clone();
+
+        for change in &self.changes {
+            match change {
+                FieldChange::Set { field, value } => res.set_field(field, value.clone())?,
+                FieldChange::Coordinates(coordinates) => res.coordinates = *coordinates,
+                FieldChange::Test { field, value } => {
+                    let current = res.field(field)?;
+                    if current != value.as_deref() {
+                        return Err(format!(
+                            "The field `{field}` is {current:?}, not {:?}",
+                            value.as_deref()
+                        )
+                        .into());
+                    }
+                }
+                FieldChange::Copy { from, field } => {
+                    let value = res
+                        .field(from)?
+                        .ok_or(format!("The field `{from}` is not set"))?
+                        .to_string();
+                    res.set_field(field, Some(value))?;
+                }
+                FieldChange::Remove(field) if field == "coordinates" => {
+                    res.coordinates
+                        .take()
+                        .ok_or("The coordinates are not set")?;
+                }
+                FieldChange::Remove(field) => {
+                    res.field(field)?
+                        .ok_or(format!("The field `{field}` is not set"))?;
+                    res.set_field(field, None)?;
+                }
+            }
+        }
+
+        for field in Address::REQUIRED_FIELDS {
+            if address.field(field)?.is_some() && res.field(field)?.is_none() {
+                return Err(
+                    format!("The field `{field}` is mandatory and cannot be cleared").into(),
+                );
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl FromStr for AddressPatch {
+    type Err = Box<dyn std::error::Error>;
+
+    /// Reads a JSON Patch when the document is an array, a JSON Merge Patch otherwise
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim_start().starts_with('[') {
+            true => AddressPatch::from_json_patch(s),
+            false => AddressPatch::from_merge_patch(s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address() -> Address {
+        Address {
+            name: Some("Monsieur Jean DURAND".to_string()),
+            floor: Some("3E ETAGE".to_string()),
+            street_name: Some("25D RUE DES FLEURS".to_string()),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_set_and_clear() {
+        let mut patch = AddressPatch::new();
+        patch
+            .set("department", Some("SERVICE ACHATS".to_string()))
+            .expect("error");
+        patch.clear("floor").expect("error");
+        assert!(patch.clear("post_code").is_err());
+        assert!(patch.clear("city").is_err());
+
+        let address = patch.apply(&address()).expect("error");
+        assert_eq!(address.department.as_deref(), Some("SERVICE ACHATS"));
+        assert_eq!(address.floor, None);
+        assert_eq!(address.post_code, "33500");
+
+        let mut patch = AddressPatch::new();
+        patch.set("country", Some(String::new())).expect("error");
+        assert!(patch.apply(&address).is_err());
+    }
+
+    #[test]
+    fn test_merge_patch() {
+        let patch: AddressPatch =
+            r#"{"floor": null, "room": "BUREAU 12", "coordinates": {"lat": 44.91, "lon": -0.24}}"#
+                .parse()
+                .expect("error");
+
+        let address = patch.apply(&address()).expect("error");
+        assert_eq!(address.floor, None);
+        assert_eq!(address.room.as_deref(), Some("BUREAU 12"));
+        assert_eq!(address.coordinates.map(|x| x.lat), Some(44.91));
+
+        assert!(AddressPatch::from_merge_patch(r#"{"post_code": 33500}"#).is_err());
+        assert!(AddressPatch::from_merge_patch(r#"{"city": "LIBOURNE"}"#).is_err());
+
+        let clearing: AddressPatch = r#"{"town_name": null}"#.parse().expect("error");
+        assert!(clearing.apply(&address).is_err());
+    }
+
+    #[test]
+    fn test_json_patch() {
+        let patch: AddressPatch = r#"[
+            {"op": "test", "path": "/town_name", "value": "LIBOURNE"},
+            {"op": "remove", "path": "/floor"},
+            {"op": "move", "from": "/name", "path": "/department"},
+            {"op": "replace", "path": "/post_code", "value": "33501"}
+        ]"#
+        .parse()
+        .expect("error");
+
+        let address = patch.apply(&address()).expect("error");
+        assert_eq!(address.floor, None);
+        assert_eq!(address.name, None);
+        assert_eq!(address.department.as_deref(), Some("Monsieur Jean DURAND"));
+        assert_eq!(address.post_code, "33501");
+
+        let failing: AddressPatch = r#"[
+            {"op": "replace", "path": "/floor", "value": "RDC"},
+            {"op": "test", "path": "/town_name", "value": "MIOS"}
+        ]"#
+        .parse()
+        .expect("error");
+        assert!(failing.apply(&address).is_err());
+
+        // RFC 6902: removing or moving a member that is not there is an error
+        for absent in [
+            r#"[{"op": "remove", "path": "/room"}]"#,
+            r#"[{"op": "remove", "path": "/coordinates"}]"#,
+            r#"[{"op": "move", "from": "/room", "path": "/floor"}]"#,
+            r#"[{"op": "remove", "path": "/country"}]"#,
+        ] {
+            let patch: AddressPatch = absent.parse().expect("error");
+            assert!(patch.apply(&address).is_err(), "{absent}");
+        }
+
+        assert!(AddressPatch::from_json_patch(r#"[{"op": "remove", "path": "/a/b"}]"#).is_err());
+        assert!(AddressPatch::from_json_patch(r#"[{"op": "swap", "path": "/floor"}]"#).is_err());
+    }
+}
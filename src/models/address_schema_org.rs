@@ -0,0 +1,304 @@
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde_json::Value;
+
+use std::error::Error;
+use std::str::FromStr;
+
+use crate::common::Result;
+
+use super::address::Address;
+
+const CONTEXT: &str = "https://schema.org";
+const TYPE: &str = "PostalAddress";
+
+/// A schema.org `PostalAddress` in JSON-LD
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaOrg_PostalAddress {
+    /// JSON-LD Context
+    #[serde(rename = "@context", default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<Value>,
+    /// JSON-LD Type, either a single type or an array of types
+    #[serde(rename = "@type", default = "default_type")]
+    pub kind: Value,
+    /// Name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Street Address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub street_address: Option<String>,
+    /// Post Office Box Number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_office_box_number: Option<String>,
+    /// Locality
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_locality: Option<String>,
+    /// Region
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_region: Option<String>,
+    /// Postal Code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+    /// Country, either a code or a `Country` node
+    #[serde(
+        default,
+        deserialize_with = "deserialize_country",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub address_country: Option<String>,
+}
+
+impl Default for SchemaOrg_PostalAddress {
+    fn default() -> Self {
+        SchemaOrg_PostalAddress {
+            context: Some(Value::from(CONTEXT)),
+            kind: default_type(),
+            name: None,
+            street_address: None,
+            post_office_box_number: None,
+            address_locality: None,
+            address_region: None,
+            postal_code: None,
+            address_country: None,
+        }
+    }
+}
+
+impl SchemaOrg_PostalAddress {
+    /// Extracts every `PostalAddress` node found in a JSON-LD document
+    pub fn extract_all(value: &Value) -> Result<Vec<Self>> {
+        let mut res = Vec::new();
+        collect(value, &mut res)?;
+        Ok(res)
+    }
+
+    /// Extracts every `PostalAddress` node found in the
+    /// `<script type="application/ld+json">` blocks of an HTML page
+    pub fn extract_all_html(html: &str) -> Result<Vec<Self>> {
+        let mut res = Vec::new();
+        for script in json_ld_scripts(html) {
+            let value: Value = serde_json::from_str(script)?;
+            collect(&value, &mut res)?;
+        }
+        Ok(res)
+    }
+
+    /// Extracts every `PostalAddress` node from either a JSON-LD document or an HTML page
+    pub fn parse_all(s: &str) -> Result<Vec<Self>> {
+        if s.trim_start().starts_with('<') {
+            Self::extract_all_html(s)
+        } else {
+            Self::extract_all(&serde_json::from_str(s)?)
+        }
+    }
+}
+
+impl FromStr for SchemaOrg_PostalAddress {
+    type Err = Box<dyn Error>;
+
+    /// Parses the first `PostalAddress` node of a JSON-LD document or HTML page
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_all(s)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| "No `PostalAddress` found".into())
+    }
+}
+
+impl TryFrom<Address> for SchemaOrg_PostalAddress {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: Address) -> Result<Self> {
+        let optional = |x: String| Some(x).filter(|x| !x.is_empty());
+
+        let street_address = [&value.building_number, &value.street_name]
+            .into_iter()
+            .flatten()
+            .filter(|x| !x.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Ok(SchemaOrg_PostalAddress {
+            name: value.name,
+            street_address: optional(street_address),
+            post_office_box_number: value.post_box,
+            address_locality: optional(value.town_name),
+            address_region: value.country_sub_division,
+            postal_code: optional(value.post_code),
+            address_country: optional(value.country),
+            ..Default::default()
+        })
+    }
+}
+
+fn default_type() -> Value {
+    Value::from(TYPE)
+}
+
+fn deserialize_country<'de, D>(deserializer: D) -> std::result::Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Value::deserialize(deserializer)? {
+        Value::String(x) => Some(x),
+        Value::Object(country) => country
+            .get("identifier")
+            .or_else(|| country.get("name"))
+            .and_then(Value::as_str)
+            .map(|x| x.to_string()),
+        _ => None,
+    })
+}
+
+fn is_postal_address(value: &Value) -> bool {
+    let is_type = |x: &Value| {
+        x.as_str()
+            .is_some_and(|x| x.rsplit(['/', ':']).next() == Some(TYPE))
+    };
+
+    match value.get("@type") {
+        Some(Value::Array(types)) => types.iter().any(is_type),
+        Some(x) => is_type(x),
+        None => false,
+    }
+}
+
+fn collect(value: &Value, res: &mut Vec<SchemaOrg_PostalAddress>) -> Result<()> {
+    match value {
+        Value::Object(map) => {
+            if is_postal_address(value) {
+                res.push(serde_json::from_value(value.clone())?);
+            } else {
+                for child in map.values() {
+                    collect(child, res)?;
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect(item, res)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Returns the content of every `<script type="application/ld+json">` element
+fn json_ld_scripts(html: &str) -> Vec<&str> {
+    let lower = html.to_ascii_lowercase();
+    let mut scripts = Vec::new();
+    let mut pos = 0;
+
+    while let Some(start) = lower[pos..].find("<script").map(|x| x + pos) {
+        let Some(tag_end) = lower[start..].find('>').map(|x| x + start) else {
+            break;
+        };
+        let Some(end) = lower[tag_end..].find("</script").map(|x| x + tag_end) else {
+            break;
+        };
+
+        if lower[start..tag_end].contains("application/ld+json") {
+            scripts.push(&html[tag_end + 1..end]);
+        }
+        pos = end;
+    }
+
+    scripts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let content = r#"{
+            "@context": "https://schema.org",
+            "@type": "PostalAddress",
+            "streetAddress": "25D RUE DES FLEURS",
+            "addressLocality": "LIBOURNE",
+            "postalCode": "33500",
+            "addressCountry": { "@type": "Country", "name": "FR" }
+        }"#;
+
+        let result = SchemaOrg_PostalAddress {
+            street_address: "25D RUE DES FLEURS".to_string().into(),
+            address_locality: "LIBOURNE".to_string().into(),
+            postal_code: "33500".to_string().into(),
+            address_country: "FR".to_string().into(),
+            ..Default::default()
+        };
+
+        let parse: SchemaOrg_PostalAddress = content.parse().expect("Cannot parse JSON-LD!");
+        assert_eq!(parse, result);
+    }
+
+    #[test]
+    fn test_parse_json_ld_variants() {
+        let content = r#"{
+            "@context": { "@vocab": "https://schema.org/" },
+            "@type": ["PostalAddress", "Place"],
+            "postalCode": "33500",
+            "addressLocality": "LIBOURNE"
+        }"#;
+
+        let parse: SchemaOrg_PostalAddress = content.parse().expect("Cannot parse JSON-LD!");
+
+        assert_eq!(
+            parse.context,
+            Some(serde_json::json!({ "@vocab": "https://schema.org/" }))
+        );
+        assert_eq!(parse.kind, serde_json::json!(["PostalAddress", "Place"]));
+        assert_eq!(parse.postal_code.as_deref(), Some("33500"));
+        assert_eq!(parse.address_locality.as_deref(), Some("LIBOURNE"));
+    }
+
+    #[test]
+    fn test_extract_html() {
+        let content = r#"<html><head>
+            <script type="application/ld+json">
+            {
+                "@context": "https://schema.org",
+                "@type": "Organization",
+                "name": "DURAND SA",
+                "address": [
+                    { "@type": "PostalAddress", "postalCode": "33506", "addressLocality": "LIBOURNE CEDEX" },
+                    { "@type": "PostalAddress", "postalCode": "34092", "addressLocality": "MONTPELLIER CEDEX 5" }
+                ]
+            }
+            </script>
+            <script>var x = "PostalAddress";</script>
+        </head></html>"#;
+
+        let parse = SchemaOrg_PostalAddress::parse_all(content).expect("Cannot parse HTML!");
+
+        assert_eq!(parse.len(), 2);
+        assert_eq!(parse[0].postal_code.as_deref(), Some("33506"));
+        assert_eq!(
+            parse[1].address_locality.as_deref(),
+            Some("MONTPELLIER CEDEX 5")
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let address = Address {
+            street_name: "RUE DES FLEURS".to_string().into(),
+            building_number: "25D".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let content = r#"{"@context":"https://schema.org","@type":"PostalAddress","streetAddress":"25D RUE DES FLEURS","addressLocality":"LIBOURNE","postalCode":"33500","addressCountry":"FR"}"#;
+
+        let json_ld = SchemaOrg_PostalAddress::try_from(address).expect("error");
+        assert_eq!(serde_json::to_string(&json_ld).expect("error"), content);
+    }
+}
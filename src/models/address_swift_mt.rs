@@ -0,0 +1,391 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::common::Result;
+
+use super::address::Address;
+use super::address_iso_20022::ISO_20022;
+use super::validate::Validate;
+
+/// Maximum length of a line, including the `n/` prefix
+const MAX_LENGTH: usize = 35;
+/// Maximum number of numbered lines after the party identifier
+const MAX_LINES: usize = 4;
+/// Field used when converting from an `Address` (beneficiary customer)
+const DEFAULT_TAG: &str = "59F";
+
+/// Splits a text into lines of at most `first_width` then `width` chars, at spaces
+/// when possible
+fn wrap(text: &str, first_width: usize, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for mut word in text.split_whitespace() {
+        loop {
+            let max = if lines.is_empty() { first_width } else { width };
+            let separator = usize::from(!current.is_empty());
+            if current.chars().count() + separator + word.chars().count() <= max {
+                if separator == 1 {
+                    current.push(' ');
+                }
+                current.push_str(word);
+                break;
+            }
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+                continue;
+            }
+            // A word longer than a line is cut
+            let end = word.char_indices().nth(max).map_or(word.len(), |(i, _)| i);
+            lines.push(word[..end].to_string());
+            word = &word[end..];
+            if word.is_empty() {
+                break;
+            }
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// A SWIFT MT structured party field (50F ordering customer / 59F beneficiary)
+///
+/// ```text
+/// :59F:/FR7630006000011234567890189
+/// 1/Monsieur Jean DURAND
+/// 2/25D RUE DES FLEURS
+/// 3/FR/33500 LIBOURNE
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SWIFT_MT_Party {
+    /// Field tag, `50F` or `59F`
+    pub tag: String,
+    /// Party identifier (account or code), without its leading `/` for accounts
+    pub party_identifier: String,
+    /// Name lines (`1/`)
+    pub name: Vec<String>,
+    /// Address lines (`2/`)
+    pub address: Vec<String>,
+    /// ISO country code (`3/CC/...`)
+    pub country: String,
+    /// Town, with its post code when any (`3/CC/TOWN`)
+    pub town: String,
+}
+
+impl SWIFT_MT_Party {
+    /// Parses every 50F/59F field of an MT message
+    pub fn parse_all(s: &str) -> Result<Vec<Self>> {
+        let mut res = Vec::new();
+        let mut current: Option<Vec<&str>> = None;
+
+        for line in s.lines().map(|x| x.trim_end()) {
+            let is_field = line.starts_with(':') && line[1..].contains(':');
+            if is_field || line.starts_with('-') {
+                if let Some(field) = current.take() {
+                    res.push(Self::parse_field(&field)?);
+                }
+            }
+            match current.as_mut() {
+                _ if line.starts_with(":50F:") || line.starts_with(":59F:") => {
+                    current = Some(vec![line])
+                }
+                Some(field) if !is_field => field.push(line),
+                _ => {}
+            }
+        }
+        if let Some(field) = current {
+            res.push(Self::parse_field(&field)?);
+        }
+
+        Ok(res)
+    }
+
+    /// Parses the lines of a single field, with or without its `:50F:`/`:59F:` tag
+    fn parse_field(lines: &[&str]) -> Result<Self> {
+        let mut res = SWIFT_MT_Party::default();
+
+        for (i, line) in lines.iter().enumerate() {
+            let line = match line.strip_prefix(':').and_then(|x| x.split_once(':')) {
+                Some((tag, rest)) => {
+                    res.tag = tag.to_string();
+                    rest
+                }
+                None => line,
+            };
+
+            let numbered = line
+                .split_once('/')
+                .and_then(|(n, text)| Some((n.parse::<u8>().ok()?, text)));
+
+            match numbered {
+                Some((1, text)) => res.name.push(text.to_string()),
+                Some((2, text)) => res.address.push(text.to_string()),
+                Some((3, text)) if res.country.is_empty() => {
+                    let (country, town) = text.split_once('/').unwrap_or((text, ""));
+                    res.country = country.to_string();
+                    res.town = town.to_string();
+                }
+                Some((3, text)) => {
+                    res.town.push(' ');
+                    res.town.push_str(text);
+                }
+                // Birth date, national identifiers, ... are not part of the address
+                Some((4..=8, _)) => {}
+                _ if i == 0 => {
+                    res.party_identifier = line.strip_prefix('/').unwrap_or(line).to_string()
+                }
+                _ => return Err(format!("Invalid line `{line}`").into()),
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Returns the numbered lines of the field, without the party identifier
+    ///
+    /// A long town continues on other `3/` lines.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        lines.extend(self.name.iter().map(|x| format!("1/{x}")));
+        lines.extend(self.address.iter().map(|x| format!("2/{x}")));
+        if !self.country.is_empty() {
+            let prefix = format!("3/{}/", self.country);
+            let first_width = MAX_LENGTH.saturating_sub(prefix.chars().count());
+            let mut town = wrap(&self.town, first_width, MAX_LENGTH - 2).into_iter();
+            lines.push(format!("{prefix}{}", town.next().unwrap_or_default()));
+            lines.extend(town.map(|x| format!("3/{x}")));
+        }
+        lines
+    }
+}
+
+impl Validate for SWIFT_MT_Party {
+    fn validate(&self) -> Result<()> {
+        let lines = self.lines();
+
+        if lines.len() > MAX_LINES {
+            return Err(format!("Field must have at most {MAX_LINES} numbered lines").into());
+        }
+        if self.party_identifier.chars().count() + 1 > MAX_LENGTH {
+            return Err(format!("Party identifier must have less than {MAX_LENGTH} chars").into());
+        }
+        for (i, line) in lines.iter().enumerate() {
+            if line.chars().count() > MAX_LENGTH {
+                return Err(
+                    format!("Line {} must have less than {MAX_LENGTH} chars", i + 1).into(),
+                );
+            }
+        }
+        if !self.address.is_empty() && self.country.is_empty() {
+            return Err("Line `3/` is mandatory when an address line `2/` is present".into());
+        }
+        if !self.country.is_empty() && self.country.len() != 2 {
+            return Err("Country must be an ISO 3166-1 alpha-2 code".into());
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for SWIFT_MT_Party {
+    type Err = Box<dyn Error>;
+
+    /// Parses the first 50F/59F field of an MT message, or a bare field value
+    fn from_str(s: &str) -> Result<Self> {
+        match Self::parse_all(s)?.into_iter().next() {
+            Some(party) => Ok(party),
+            None => Self::parse_field(&s.lines().collect::<Vec<_>>()),
+        }
+    }
+}
+
+impl fmt::Display for SWIFT_MT_Party {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = Vec::new();
+        if !self.party_identifier.is_empty() {
+            lines.push(format!("/{}", self.party_identifier));
+        }
+        lines.extend(self.lines());
+
+        let tag = if self.tag.is_empty() {
+            DEFAULT_TAG
+        } else {
+            &self.tag
+        };
+        write!(f, ":{tag}:{}", lines.join("\n"))
+    }
+}
+
+impl TryFrom<Address> for SWIFT_MT_Party {
+    type Error = Box<dyn Error>;
+
+    /// Long names and address lines are wrapped on several lines, fails when the
+    /// field needs more than 4 lines
+    fn try_from(value: Address) -> Result<Self> {
+        let street = [value.building_number, value.street_name]
+            .into_iter()
+            .flatten()
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let town = format!("{} {}", value.post_code, value.town_name)
+            .trim()
+            .to_string();
+
+        let width = MAX_LENGTH - "1/".len();
+        let party = SWIFT_MT_Party {
+            tag: DEFAULT_TAG.to_string(),
+            name: value
+                .name
+                .iter()
+                .flat_map(|x| wrap(x, width, width))
+                .collect(),
+            address: [Some(street), value.post_box]
+                .iter()
+                .flatten()
+                .flat_map(|x| wrap(x, width, width))
+                .collect(),
+            country: value.country,
+            town,
+            ..Default::default()
+        };
+        party.validate()?;
+        Ok(party)
+    }
+}
+
+/// MT to MX migration: builds the ISO 20022 `PstlAdr` of a 50F/59F party
+impl TryFrom<SWIFT_MT_Party> for ISO_20022 {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: SWIFT_MT_Party) -> Result<Self> {
+        ISO_20022::try_from(Address::try_from(value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_all() {
+        let content = "{1:F01BANKFRPPAXXX0000000000}{2:I103BANKDEFFXXXXN}{4:\n\
+            :20:REF123\n\
+            :32A:240101EUR1000,00\n\
+            :50F:/FR7630006000011234567890189\n\
+            1/DURAND SA\n\
+            2/22BIS RUE DES FLEURS\n\
+            3/FR/33506 LIBOURNE CEDEX\n\
+            :59F:/DE89370400440532013000\n\
+            1/Max MUSTERMANN\n\
+            2/HAUPTSTRASSE 1\n\
+            3/DE/10115 BERLIN\n\
+            :71A:SHA\n\
+            -}";
+
+        let result = SWIFT_MT_Party {
+            tag: "50F".to_string(),
+            party_identifier: "FR7630006000011234567890189".to_string(),
+            name: vec!["DURAND SA".to_string()],
+            address: vec!["22BIS RUE DES FLEURS".to_string()],
+            country: "FR".to_string(),
+            town: "33506 LIBOURNE CEDEX".to_string(),
+        };
+
+        let parse = SWIFT_MT_Party::parse_all(content).expect("Cannot parse MT!");
+        assert_eq!(parse.len(), 2);
+        assert_eq!(parse[0], result);
+        assert_eq!(parse[1].tag, "59F");
+        assert_eq!(parse[1].town, "10115 BERLIN");
+    }
+
+    #[test]
+    fn test_to_string() {
+        let address = Address {
+            name: "Monsieur Jean DURAND".to_string().into(),
+            street_name: "25D RUE DES FLEURS".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let party = SWIFT_MT_Party::try_from(address).expect("error");
+        assert_eq!(
+            party.to_string(),
+            ":59F:1/Monsieur Jean DURAND\n2/25D RUE DES FLEURS\n3/FR/33500 LIBOURNE"
+        );
+        assert_eq!(
+            party.to_string().parse::<SWIFT_MT_Party>().expect("error"),
+            party
+        );
+    }
+
+    #[test]
+    fn test_wrap() {
+        let address = Address {
+            name: "Jean-Christophe DE LA VILLARDIERE".to_string().into(),
+            street_name: "RUE DES FLEURS".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "SAINT-ANDRE-DE-CUBZAC LES ILES ET PORTETS".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let party = SWIFT_MT_Party::try_from(address.clone()).expect("error");
+        let text = party.to_string();
+        assert_eq!(text.lines().count(), 4);
+        assert!(text
+            .lines()
+            .all(|x| x.chars().count() <= MAX_LENGTH + ":59F:".len()));
+        assert!(party.validate().is_ok());
+
+        let back = Address::try_from(text.parse::<SWIFT_MT_Party>().expect("error"));
+        assert_eq!(back.expect("error").name, address.name);
+
+        let address = Address {
+            post_box: "BP 40122".to_string().into(),
+            ..address
+        };
+        assert!(SWIFT_MT_Party::try_from(address).is_err());
+    }
+
+    #[test]
+    fn test_iso_20022() {
+        let party: SWIFT_MT_Party = "/FR7630006000011234567890189\n\
+            1/DURAND SA\n\
+            2/22BIS RUE DES FLEURS\n\
+            3/FR/33506 LIBOURNE CEDEX"
+            .parse()
+            .expect("Cannot parse MT!");
+
+        let result = ISO_20022 {
+            StrtNm: "22BIS RUE DES FLEURS".to_string(),
+            PstCd: "33506".to_string(),
+            TwnNm: "LIBOURNE CEDEX".to_string(),
+            Ctry: "FR".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(ISO_20022::try_from(party).expect("error"), result);
+    }
+
+    #[test]
+    fn test_validate() {
+        let result = SWIFT_MT_Party {
+            name: vec!["DURAND SA".to_string()],
+            address: vec![
+                "22BIS RUE DES FLEURS".to_string(),
+                "Zone industrielle de la Ballastrierre Ouest".to_string(),
+            ],
+            country: "FR".to_string(),
+            town: "33506 LIBOURNE CEDEX".to_string(),
+            ..Default::default()
+        };
+
+        assert!(result.validate().is_err())
+    }
+}
@@ -0,0 +1,512 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use std::collections::HashMap;
+
+use crate::common::{country_name, find_country, matches_pattern, normalize_country, Result};
+
+use super::address::Address;
+use super::nf_lines::{classify_line, LineKind};
+
+/// Templates shipped with the library
+const BUILTIN_TEMPLATES: &str = include_str!("templates.json");
+
+/// Language of the country line when the sender has no template
+const DEFAULT_LANGUAGE: &str = "en";
+
+/// The address layout of a country, following UPU S42
+///
+/// Each line is made of `{field}` placeholders, named after the fields of `Address`,
+/// separated by literal separators, e.g. `{post_code} {town_name}`. Lines whose
+/// fields are all empty are left out, as are the separators of empty fields.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AddressTemplate {
+    /// ISO 3166-1 alpha-2 country code
+    pub country: String,
+    /// ISO 639-1 language used for the country line of mail sent from this country
+    #[serde(default = "default_language")]
+    pub language: String,
+    /// Lines of the address block, from top to bottom, without the country line
+    pub lines: Vec<String>,
+    /// Fields written in capitals, `*` for the whole address block
+    #[serde(default)]
+    pub uppercase: Vec<String>,
+    /// Post code formats, `9` stands for a digit and `A` for a letter
+    #[serde(default)]
+    pub post_code: Vec<String>,
+}
+
+/// A template line split into its fields and the separators between them
+struct Pattern<'a> {
+    fields: Vec<&'a str>,
+    /// `separators[i]` precedes `fields[i + 1]`
+    separators: Vec<&'a str>,
+}
+
+impl<'a> Pattern<'a> {
+    fn parse(line: &'a str) -> Result<Self> {
+        let mut fields = Vec::new();
+        let mut separators = Vec::new();
+        let mut rest = line;
+
+        while let Some(start) = rest.find('{') {
+            let end = start
+                + rest[start..]
+                    .find('}')
+                    .ok_or(format!("Unclosed `{{` in `{line}`"))?;
+            if rest[..start].contains('}') || rest[start + 1..end].contains('{') {
+                return Err(format!("Unmatched braces in `{line}`").into());
+            }
+            match fields.is_empty() {
+                true if start > 0 => {
+                    return Err(format!("Text before the first field in `{line}`").into())
+                }
+                true => {}
+                false => separators.push(&rest[..start]),
+            }
+            fields.push(&rest[start + 1..end]);
+            rest = &rest[end + 1..];
+        }
+
+        if !rest.is_empty() {
+            return Err(format!("Text after the last field in `{line}`").into());
+        }
+
+        Ok(Pattern { fields, separators })
+    }
+
+    /// The separator between two kept fields is the one right before the second field
+    fn separator(&self, field: usize) -> &'a str {
+        self.separators[field - 1]
+    }
+}
+
+impl AddressTemplate {
+    /// Checks a post code against the formats of the country
+    pub fn is_post_code(&self, value: &str) -> bool {
+        self.post_code
+            .iter()
+            .any(|x| matches_pattern(&value.to_uppercase(), x))
+    }
+
+    /// Renders the lines of an address, without the country line
+    pub fn render(&self, address: &Address) -> Result<Vec<String>> {
+        let all_upper = self.uppercase.iter().any(|x| x == "*");
+        let mut lines = Vec::new();
+
+        for line in &self.lines {
+            let pattern = Pattern::parse(line)?;
+            let mut rendered = String::new();
+
+            for (i, field) in pattern.fields.iter().enumerate() {
+                let Some(value) = address.field(field)?.map(|x| x.trim()) else {
+                    continue;
+                };
+                if value.is_empty() {
+                    continue;
+                }
+                if !rendered.is_empty() {
+                    rendered.push_str(pattern.separator(i));
+                }
+                match all_upper || self.uppercase.iter().any(|x| x == field) {
+                    true => rendered.push_str(&value.to_uppercase()),
+                    false => rendered.push_str(value),
+                }
+            }
+
+            if !rendered.is_empty() {
+                lines.push(rendered);
+            }
+        }
+
+        Ok(lines)
+    }
+
+    /// Parses the lines of an address block, without the country line
+    ///
+    /// Every line is matched to a template line, in order, picking the assignment
+    /// that best fits the post code formats, house numbers and post boxes.
+    pub fn parse(&self, lines: &[&str]) -> Result<Address> {
+        let patterns = self
+            .lines
+            .iter()
+            .map(|x| Pattern::parse(x))
+            .collect::<Result<Vec<_>>>()?;
+
+        let n = lines.len();
+        let m = patterns.len();
+
+        // best[i][j]: score of matching the first `i` lines with the first `j` template lines
+        let mut best = vec![vec![None; m + 1]; n + 1];
+        let mut matches = vec![vec![None; m]; n];
+        best[0].fill(Some(0.0));
+
+        for i in 1..=n {
+            for j in 1..=m {
+                let skip = best[i][j - 1];
+                let take = match (
+                    best[i - 1][j - 1],
+                    self.match_line(&patterns[j - 1], lines[i - 1]),
+                ) {
+                    (Some(previous), Some((score, values))) => {
+                        // Prefer keeping lines close to their template position
+                        let distance = (i as f64 - j as f64).abs() * 0.01;
+                        matches[i - 1][j - 1] = Some(values);
+                        Some(previous + score - distance)
+                    }
+                    _ => None,
+                };
+                best[i][j] = match (skip, take) {
+                    (Some(skip), Some(take)) if skip >= take => Some(skip),
+                    (_, Some(take)) => Some(take),
+                    (skip, None) => skip,
+                };
+            }
+        }
+
+        if best[n][m].is_none() {
+            return Err(format!(
+                "The address does not fit the template of `{}`",
+                self.country
+            )
+            .into());
+        }
+
+        let mut address = Address {
+            country: self.country.clone(),
+            ..Address::default()
+        };
+
+        let (mut i, mut j) = (n, m);
+        while i > 0 {
+            if best[i][j] == best[i][j - 1] {
+                j -= 1;
+                continue;
+            }
+            for (field, value) in matches[i - 1][j - 1].take().unwrap_or_default() {
+                address.set_field(field, Some(value))?;
+            }
+            i -= 1;
+            j -= 1;
+        }
+
+        Ok(address)
+    }
+
+    /// Finds the best way to split a line over the fields of a template line
+    fn match_line<'a>(
+        &self,
+        pattern: &Pattern<'a>,
+        line: &str,
+    ) -> Option<(f64, Vec<(&'a str, String)>)> {
+        let k = pattern.fields.len();
+        let mut best: Option<(f64, Vec<(&str, String)>)> = None;
+
+        // Any non empty subset of the fields may be present
+        for mask in 1..(1u32 << k) {
+            let kept: Vec<usize> = (0..k).filter(|i| mask & (1 << i) != 0).collect();
+            let separators: Vec<&str> = kept[1..].iter().map(|i| pattern.separator(*i)).collect();
+
+            for values in split(line, &separators) {
+                let alone = kept.len() == 1;
+                let score = kept
+                    .iter()
+                    .zip(&values)
+                    .map(|(i, value)| self.score_field(pattern.fields[*i], value, alone))
+                    .sum::<Option<f64>>();
+
+                if let Some(score) = score {
+                    if best.as_ref().is_none_or(|(x, _)| score > *x) {
+                        let values = kept
+                            .iter()
+                            .zip(values)
+                            .map(|(i, value)| (pattern.fields[*i], value.to_string()))
+                            .collect();
+                        best = Some((score, values));
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Scores how well a value fits a field, `None` when it cannot be this field
+    fn score_field(&self, field: &str, value: &str, alone: bool) -> Option<f64> {
+        let tokens: Vec<&str> = value.split_whitespace().collect();
+        let has_digit = value.chars().any(|c| c.is_ascii_digit());
+
+        match field {
+            "post_code" if self.post_code.is_empty() => has_digit.then_some(2.0),
+            "post_code" => self.is_post_code(value).then_some(4.0),
+            "building_number" => (tokens.len() == 1
+                && value.starts_with(|c: char| c.is_ascii_digit()))
+            .then_some(2.0),
+            "post_box" => {
+                let starts_with_word = tokens.first()?.chars().all(char::is_alphabetic);
+                let ends_with_number = tokens.last()?.chars().any(|c| c.is_ascii_digit());
+                (starts_with_word && ends_with_number && tokens.len() <= 3).then_some(2.0)
+            }
+            // Inside a line, only short codes are taken as a subdivision (`NY`, `RM`, ...)
+            "country_sub_division" if !alone => {
+                let is_code =
+                    (2..=3).contains(&value.len()) && value.chars().all(|c| c.is_ascii_uppercase());
+                is_code.then_some(0.5)
+            }
+            "street_name" if classify_line(value) == LineKind::Street => Some(1.0),
+            "room" if classify_line(value) == LineKind::Room => Some(1.0),
+            "floor" if classify_line(value) == LineKind::Floor => Some(1.0),
+            // The town is mandatory, unlike the locality that may precede it
+            "town_name" => Some(0.5),
+            // Seldom used lines are only filled when nothing else fits
+            "department" | "sub_department" | "building_name" => Some(-0.05),
+            _ => Some(0.0),
+        }
+    }
+}
+
+/// Splits a line on the given separators, in order, returning every possible split
+///
+/// A separator made of spaces matches any number of spaces, as in typed addresses.
+fn split<'a>(line: &'a str, separators: &[&str]) -> Vec<Vec<&'a str>> {
+    let Some((separator, rest)) = separators.split_first() else {
+        return match line.trim() {
+            "" => vec![],
+            value => vec![vec![value]],
+        };
+    };
+    let separator = match separator.trim() {
+        "" => " ",
+        _ => separator,
+    };
+
+    let mut res = Vec::new();
+    for (pos, _) in line.match_indices(separator) {
+        let value = line[..pos].trim();
+        if value.is_empty() {
+            continue;
+        }
+        for mut values in split(&line[pos + separator.len()..], rest) {
+            values.insert(0, value);
+            res.push(values);
+        }
+    }
+    res
+}
+
+fn default_language() -> String {
+    DEFAULT_LANGUAGE.to_string()
+}
+
+/// The set of known templates, by country
+#[derive(Debug, Clone, Default)]
+pub struct TemplateRegistry {
+    templates: HashMap<String, AddressTemplate>,
+}
+
+impl TemplateRegistry {
+    /// Creates a registry with the built-in templates
+    pub fn new() -> Self {
+        let mut registry = Self::default();
+        registry
+            .load(BUILTIN_TEMPLATES)
+            .expect("Built-in templates must be valid");
+        registry
+    }
+
+    /// Adds templates from a JSON array, replacing the ones of the same countries
+    pub fn load(&mut self, json: &str) -> Result<()> {
+        let templates: Vec<AddressTemplate> = serde_json::from_str(json)?;
+        for template in templates {
+            for line in &template.lines {
+                Pattern::parse(line)?;
+            }
+            self.templates
+                .insert(template.country.to_uppercase(), template);
+        }
+        Ok(())
+    }
+
+    /// Returns every template, in no particular order
+    pub fn templates(&self) -> impl Iterator<Item = &AddressTemplate> {
+        self.templates.values()
+    }
+
+    /// Returns the template of a country
+    pub fn get(&self, country: &str) -> Result<&AddressTemplate> {
+        self.templates
+            .get(&country.to_uppercase())
+            .ok_or_else(|| format!("No address template for country `{country}`").into())
+    }
+
+    /// Renders an address as sent from the `sender` country
+    ///
+    /// The country line is only added for international mail, written in capitals in
+    /// the language of the sender's country.
+    pub fn render(&self, address: &Address, sender: &str) -> Result<Vec<String>> {
+        let country = normalize_country(&address.country)?;
+        let mut lines = self.get(&country)?.render(address)?;
+
+        if !country.is_empty() && !country.eq_ignore_ascii_case(sender) {
+            let language = self
+                .get(sender)
+                .map(|x| x.language.as_str())
+                .unwrap_or(DEFAULT_LANGUAGE);
+            let name = country_name(&country, language)
+                .or_else(|| country_name(&country, DEFAULT_LANGUAGE))
+                .unwrap_or(&country);
+            lines.push(name.to_string());
+        }
+
+        Ok(lines)
+    }
+
+    /// Parses an address block received in the `sender` country
+    ///
+    /// The destination country is read from the country line, and defaults to the
+    /// sender's country for domestic mail.
+    pub fn parse(&self, s: &str, sender: &str) -> Result<Address> {
+        let mut lines: Vec<&str> = s
+            .lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+            .collect();
+
+        let country = match lines.last().and_then(|x| find_country(x)) {
+            Some(country) => {
+                lines.pop();
+                country.to_string()
+            }
+            None => sender.to_uppercase(),
+        };
+
+        self.get(&country)?.parse(&lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_domestic() {
+        let address = Address {
+            name: "Madame Isabelle RICHARD".to_string().into(),
+            street_name: "Villa Beau Soleil".to_string().into(),
+            post_code: "82500".to_string(),
+            town_name: "Auterive".to_string(),
+            town_location_name: "Le Village".to_string().into(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let lines = TemplateRegistry::new()
+            .render(&address, "FR")
+            .expect("error");
+        assert_eq!(
+            lines,
+            vec![
+                "Madame Isabelle RICHARD",
+                "VILLA BEAU SOLEIL",
+                "LE VILLAGE",
+                "82500 AUTERIVE"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_international() {
+        let address = Address {
+            name: "Max Mustermann".to_string().into(),
+            street_name: "Hauptstraße".to_string().into(),
+            building_number: "12".to_string().into(),
+            post_code: "10115".to_string(),
+            town_name: "Berlin".to_string(),
+            country: "DE".to_string(),
+            ..Default::default()
+        };
+
+        let lines = TemplateRegistry::new()
+            .render(&address, "FR")
+            .expect("error");
+        assert_eq!(
+            lines,
+            vec![
+                "Max Mustermann",
+                "Hauptstraße 12",
+                "10115 Berlin",
+                "ALLEMAGNE"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let registry = TemplateRegistry::new();
+
+        let us = registry
+            .parse(
+                "JOHN DOE\n100 MAIN ST\nNEW YORK NY 10001-1234\nÉTATS-UNIS",
+                "FR",
+            )
+            .expect("error");
+        assert_eq!(us.building_number.as_deref(), Some("100"));
+        assert_eq!(us.town_name, "NEW YORK");
+        assert_eq!(us.country_sub_division.as_deref(), Some("NY"));
+        assert_eq!(us.post_code, "10001-1234");
+        assert_eq!(us.country, "US");
+
+        let gb = registry
+            .parse(
+                "Jane Smith\nFlat 2\n10 Downing Street\nLONDON\nSW1A 2AA",
+                "GB",
+            )
+            .expect("error");
+        assert_eq!(gb.room.as_deref(), Some("Flat 2"));
+        assert_eq!(gb.street_name.as_deref(), Some("Downing Street"));
+        assert_eq!(gb.town_name, "LONDON");
+        assert_eq!(gb.post_code, "SW1A 2AA");
+
+        let fr = registry
+            .parse(
+                "Monsieur Jean DELHOURME\nChez Mireille COPEAU Appartement 2\nEntrée A Bâtiment Jonquille\n25 RUE DE L’EGLISE\nCAUDOS\n33380 MIOS",
+                "FR",
+            )
+            .expect("error");
+        let result = Address {
+            name: "Monsieur Jean DELHOURME".to_string().into(),
+            room: "Chez Mireille COPEAU Appartement 2".to_string().into(),
+            floor: "Entrée A Bâtiment Jonquille".to_string().into(),
+            building_number: "25".to_string().into(),
+            street_name: "RUE DE L’EGLISE".to_string().into(),
+            town_location_name: "CAUDOS".to_string().into(),
+            post_code: "33380".to_string(),
+            town_name: "MIOS".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(fr, result);
+    }
+
+    #[test]
+    fn test_custom_template() {
+        let mut registry = TemplateRegistry::new();
+        registry
+            .load(r#"[{ "country": "NL", "lines": ["{name}", "{street_name} {building_number}", "{post_code}  {town_name}"], "uppercase": ["town_name"], "post_code": ["9999 AA"] }]"#)
+            .expect("error");
+        assert!(registry
+            .load(r#"[{ "country": "NL", "lines": ["{name} }{town_name}"] }]"#)
+            .is_err());
+        assert!(registry
+            .load(r#"[{ "country": "NL", "lines": ["{name {town_name}"] }]"#)
+            .is_err());
+
+        let address = registry
+            .parse("J. de Vries\nDamrak 1\n1012 LG  AMSTERDAM", "NL")
+            .expect("error");
+        assert_eq!(address.post_code, "1012 LG");
+
+        let lines = registry.render(&address, "DE").expect("error");
+        assert_eq!(lines.last().map(|x| x.as_str()), Some("NETHERLANDS"));
+    }
+}
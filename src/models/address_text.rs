@@ -0,0 +1,385 @@
+use crate::common::{alpha2_to_country, find_country, Result};
+
+use super::address::Address;
+use super::address_template::{AddressTemplate, TemplateRegistry};
+use super::nf_lines::{address_lines, assign_lines, VOIE_TYPES};
+
+/// Number of alternatives returned by the parser
+const MAX_CANDIDATES: usize = 5;
+/// Country assumed, with a low confidence, when none is written
+const DEFAULT_COUNTRY: &str = "FR";
+/// Readings below this confidence are not returned
+const MIN_CONFIDENCE: f64 = 0.3;
+
+/// Street type words, by language; German ones are also found at the end of words
+const STREET_WORDS: &[(&str, &[&str])] = &[
+    ("fr", VOIE_TYPES),
+    (
+        "de",
+        &[
+            "STRASSE", "STR.", "WEG", "PLATZ", "ALLEE", "GASSE", "RING", "DAMM", "UFER",
+        ],
+    ),
+    (
+        "en",
+        &[
+            "STREET",
+            "ST",
+            "ROAD",
+            "RD",
+            "AVENUE",
+            "AVE",
+            "LANE",
+            "LN",
+            "DRIVE",
+            "DR",
+            "WAY",
+            "BOULEVARD",
+            "BLVD",
+            "PLACE",
+            "CLOSE",
+            "CRESCENT",
+            "SQUARE",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "CALLE",
+            "C/",
+            "AVENIDA",
+            "AVDA.",
+            "PLAZA",
+            "PASEO",
+            "CAMINO",
+            "CARRETERA",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "VIA", "VIALE", "PIAZZA", "CORSO", "VICOLO", "LARGO", "STRADA",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "RUA", "AVENIDA", "AV.", "TRAVESSA", "LARGO", "PRAÇA", "ESTRADA", "CALÇADA",
+        ],
+    ),
+];
+
+/// A possible reading of a free-text address
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextCandidate {
+    pub address: Address,
+    /// From 0 to 1
+    pub confidence: f64,
+}
+
+impl TextCandidate {
+    /// Parses a free-text address with the built-in templates, most likely reading first
+    ///
+    /// Components are separated by commas, semicolons or new lines, e.g.
+    /// `Monsieur Jean DURAND, 25D rue des Fleurs, 33500 Libourne, France`.
+    pub fn parse_all(s: &str) -> Result<Vec<Self>> {
+        Self::parse_all_with(s, &TemplateRegistry::new())
+    }
+
+    /// Parses a free-text address with the templates of a registry
+    ///
+    /// Each component is matched against the country templates, using their post code
+    /// formats, then against the NF Z10-011 line heuristics. When the text ends with a
+    /// country name, only this country is tried.
+    ///
+    /// Readings without a post code or a town, or below a minimum confidence, are
+    /// dropped, so the parsing fails when the text doesn't look like an address.
+    pub fn parse_all_with(s: &str, registry: &TemplateRegistry) -> Result<Vec<Self>> {
+        let mut segments: Vec<&str> = s
+            .split([',', ';', '\n'])
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+            .collect();
+
+        let country = segments.last().and_then(|x| detect_country(x));
+        if country.is_some() {
+            segments.pop();
+        }
+        if segments.is_empty() {
+            return Err("No address found in the text".into());
+        }
+
+        let templates: Vec<&AddressTemplate> = match country {
+            Some(country) => registry.get(country).ok().into_iter().collect(),
+            None => registry.templates().collect(),
+        };
+
+        let mut res = Vec::new();
+        for template in templates {
+            for lines in variants(&segments, template) {
+                let lines: Vec<&str> = lines.iter().map(|x| x.as_str()).collect();
+                if let Ok(address) = template.parse(&lines) {
+                    let confidence = confidence(&address, template, country.is_some());
+                    res.push(TextCandidate {
+                        address,
+                        confidence,
+                    });
+                }
+            }
+        }
+        res.extend(nf_candidate(&segments, country));
+        res.retain(|x| {
+            x.confidence >= MIN_CONFIDENCE
+                && !x.address.post_code.is_empty()
+                && !x.address.town_name.is_empty()
+        });
+
+        res.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        let mut seen = Vec::new();
+        res.retain(|x| match seen.contains(&x.address) {
+            true => false,
+            false => {
+                seen.push(x.address.clone());
+                true
+            }
+        });
+        res.truncate(MAX_CANDIDATES);
+
+        match res.is_empty() {
+            true => Err(format!("Cannot read an address from `{s}`").into()),
+            false => Ok(res),
+        }
+    }
+}
+
+/// Writes an address on a single line, with its components separated by commas
+pub fn to_text(address: &Address) -> String {
+    let mut parts: Vec<String> = address.name.iter().cloned().collect();
+    parts.extend(address_lines(address));
+    parts.push(format!("{} {}", address.post_code, address.town_name));
+    if !address.country.is_empty() {
+        parts.push(
+            alpha2_to_country(&address.country)
+                .unwrap_or(&address.country)
+                .to_string(),
+        );
+    }
+
+    parts
+        .iter()
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Finds the country code of a country line, written as a name in any language or
+/// a common abbreviation, such as `UK`
+fn detect_country(segment: &str) -> Option<&'static str> {
+    find_country(segment)
+}
+
+/// Ways of splitting the text into template lines
+///
+/// Besides the components themselves, the last two components may share a line
+/// (`New York, NY 10001`) and a trailing post code may stand on its own line
+/// (`London SW1A 2AA`).
+fn variants(segments: &[&str], template: &AddressTemplate) -> Vec<Vec<String>> {
+    let lines: Vec<String> = segments.iter().map(|x| x.to_string()).collect();
+    let mut res = vec![lines.clone()];
+
+    if let [rest @ .., before, last] = segments {
+        let mut merged: Vec<String> = rest.iter().map(|x| x.to_string()).collect();
+        merged.push(format!("{before} {last}"));
+        res.push(merged);
+    }
+
+    if let Some((last, rest)) = lines.split_last() {
+        let tokens: Vec<&str> = last.split_whitespace().collect();
+        for n in [1, 2] {
+            if tokens.len() <= n {
+                continue;
+            }
+            let post_code = tokens[tokens.len() - n..].join(" ");
+            if template.is_post_code(&post_code) {
+                let mut split = rest.to_vec();
+                split.push(tokens[..tokens.len() - n].join(" "));
+                split.push(post_code);
+                res.push(split);
+            }
+        }
+    }
+
+    res
+}
+
+/// Rates a reading of the text with a template
+fn confidence(address: &Address, template: &AddressTemplate, explicit_country: bool) -> f64 {
+    let mut res = 0.0;
+
+    if explicit_country {
+        res += 0.3;
+    } else if template.country == DEFAULT_COUNTRY {
+        res += 0.05;
+    }
+    if template.is_post_code(&address.post_code) {
+        res += 0.3;
+    }
+    if !address.town_name.is_empty() {
+        res += 0.1;
+    }
+    if address.building_number.is_some() {
+        res += 0.15;
+    }
+    if address
+        .street_name
+        .as_deref()
+        .is_some_and(|x| has_street_word(x, &template.language))
+    {
+        res += 0.15;
+    }
+
+    f64::min(res, 1.0)
+}
+
+/// Checks whether a street name contains a street type of the language
+fn has_street_word(street: &str, language: &str) -> bool {
+    let Some((_, words)) = STREET_WORDS.iter().find(|(x, _)| *x == language) else {
+        return false;
+    };
+    street.to_uppercase().split_whitespace().any(|word| {
+        words.contains(&word) || (language == "de" && words.iter().any(|x| word.ends_with(x)))
+    })
+}
+
+/// Reads the text with the NF Z10-011 line heuristics: name, free lines, town line
+fn nf_candidate(segments: &[&str], country: Option<&str>) -> Option<TextCandidate> {
+    let town = segments
+        .iter()
+        .rposition(|x| x.chars().any(|c| c.is_ascii_digit()))?;
+    // The post code is made of the leading words with digits, e.g. `114 51`
+    let words: Vec<&str> = segments[town].split_whitespace().collect();
+    let n = words
+        .iter()
+        .take_while(|x| x.chars().any(|c| c.is_ascii_digit()))
+        .count();
+    if n == 0 || n == words.len() {
+        return None;
+    }
+    let (post_code, town_name) = (words[..n].join(" "), words[n..].join(" "));
+
+    let mut address = Address {
+        post_code,
+        town_name,
+        country: country.unwrap_or(DEFAULT_COUNTRY).to_string(),
+        ..Address::default()
+    };
+
+    let lines: Vec<String> = match segments[..town].split_first() {
+        Some((name, lines)) if !lines.is_empty() => {
+            address.name = Some(name.to_string());
+            lines.iter().map(|x| x.to_string()).collect()
+        }
+        _ => segments[..town].iter().map(|x| x.to_string()).collect(),
+    };
+    assign_lines(&lines, &mut address);
+
+    let confidence = match country {
+        Some(_) => 0.4,
+        None => 0.1,
+    };
+    Some(TextCandidate {
+        address,
+        confidence,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_all() {
+        let candidates = TextCandidate::parse_all(
+            "Monsieur Jean DURAND, 25D rue des Fleurs, 33500 Libourne, France",
+        )
+        .expect("Cannot parse text!");
+
+        let result = Address {
+            name: "Monsieur Jean DURAND".to_string().into(),
+            building_number: "25D".to_string().into(),
+            street_name: "rue des Fleurs".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "Libourne".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(candidates[0].address, result);
+        assert!(candidates[0].confidence > 0.9);
+        assert!(candidates
+            .windows(2)
+            .all(|x| x[0].confidence >= x[1].confidence));
+    }
+
+    #[test]
+    fn test_to_text() {
+        let text = "Monsieur Jean DURAND, 25D rue des Fleurs, 33500 Libourne, FRANCE";
+        let candidates = TextCandidate::parse_all(text).expect("Cannot parse text!");
+        assert_eq!(to_text(&candidates[0].address), text);
+    }
+
+    #[test]
+    fn test_parse_all_without_country() {
+        let candidates = TextCandidate::parse_all("Max Mustermann\nHauptstraße 12\n10115 Berlin")
+            .expect("Cannot parse text!");
+        assert_eq!(candidates[0].address.country, "DE");
+        assert_eq!(candidates[0].address.building_number.as_deref(), Some("12"));
+
+        let candidates = TextCandidate::parse_all("10 Downing Street, London SW1A 2AA, UK")
+            .expect("Cannot parse text!");
+        assert_eq!(candidates[0].address.post_code, "SW1A 2AA");
+        assert_eq!(candidates[0].address.town_name, "London");
+    }
+
+    #[test]
+    fn test_parse_all_countries() {
+        let candidates =
+            TextCandidate::parse_all("Jan Jansen, Damrak 1, 1012 LG Amsterdam, Netherlands")
+                .expect("Cannot parse text!");
+
+        let result = Address {
+            name: "Jan Jansen".to_string().into(),
+            building_number: "1".to_string().into(),
+            street_name: "Damrak".to_string().into(),
+            post_code: "1012 LG".to_string(),
+            town_name: "Amsterdam".to_string(),
+            country: "NL".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(candidates[0].address, result);
+
+        let candidates = TextCandidate::parse_all("Storgatan 1, 114 51 Stockholm, Sweden")
+            .expect("Cannot parse text!");
+        assert_eq!(candidates[0].address.country, "SE");
+        assert_eq!(candidates[0].address.post_code, "114 51");
+        assert_eq!(candidates[0].address.town_name, "Stockholm");
+    }
+
+    #[test]
+    fn test_parse_all_not_address() {
+        assert!(TextCandidate::parse_all("foo").is_err());
+        assert!(TextCandidate::parse_all("foo, bar").is_err());
+        assert!(TextCandidate::parse_all("France").is_err());
+    }
+
+    #[test]
+    fn test_parse_all_alternatives() {
+        let candidates =
+            TextCandidate::parse_all("12 rue de la Paix, 75002 Paris").expect("Cannot parse text!");
+
+        assert_eq!(candidates[0].address.country, "FR");
+        assert!(candidates.len() > 1);
+        assert!(candidates[1].confidence < candidates[0].confidence);
+    }
+}
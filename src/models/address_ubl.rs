@@ -0,0 +1,467 @@
+use quick_xml::events::Event;
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::se::Serializer;
+use quick_xml::NsReader;
+use serde::Deserialize;
+use serde::Serialize;
+
+use std::error::Error;
+use std::str::FromStr;
+
+use crate::common::Result;
+
+use super::address::Address;
+use super::validate::Validate;
+
+/// Namespace of the UBL aggregate components (`cac:`)
+pub const CAC_NAMESPACE: &str =
+    "urn:oasis:names:specification:ubl:schema:xsd:CommonAggregateComponents-2";
+/// Namespace of the UBL basic components (`cbc:`)
+pub const CBC_NAMESPACE: &str =
+    "urn:oasis:names:specification:ubl:schema:xsd:CommonBasicComponents-2";
+
+/// A party of a UBL invoice that carries a postal address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UBL_Party {
+    /// `cac:AccountingSupplierParty`
+    Seller,
+    /// `cac:AccountingCustomerParty`
+    Buyer,
+}
+
+impl UBL_Party {
+    fn element(&self) -> &'static [u8] {
+        match self {
+            UBL_Party::Seller => b"AccountingSupplierParty",
+            UBL_Party::Buyer => b"AccountingCustomerParty",
+        }
+    }
+}
+
+/// A free address line (`cac:AddressLine`)
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UBL_AddressLine {
+    /// Line
+    #[serde(rename(serialize = "cbc:Line", deserialize = "Line"))]
+    pub Line: String,
+}
+
+/// A country (`cac:Country`)
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UBL_Country {
+    /// ISO 3166-1 alpha-2 code
+    #[serde(rename(
+        serialize = "cbc:IdentificationCode",
+        deserialize = "IdentificationCode"
+    ))]
+    pub IdentificationCode: String,
+}
+
+/// The UBL 2.1 `cac:PostalAddress`, as used by Peppol BIS and Factur-X
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename = "cac:PostalAddress")]
+pub struct UBL_PostalAddress {
+    /// `cac` namespace declaration
+    #[serde(rename = "@xmlns:cac", skip_deserializing, default = "cac_namespace")]
+    pub xmlns_cac: String,
+    /// `cbc` namespace declaration
+    #[serde(rename = "@xmlns:cbc", skip_deserializing, default = "cbc_namespace")]
+    pub xmlns_cbc: String,
+    /// Post Box
+    #[serde(
+        rename(serialize = "cbc:Postbox", deserialize = "Postbox"),
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub Postbox: Option<String>,
+    /// Floor
+    #[serde(
+        rename(serialize = "cbc:Floor", deserialize = "Floor"),
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub Floor: Option<String>,
+    /// Street Name
+    #[serde(
+        rename(serialize = "cbc:StreetName", deserialize = "StreetName"),
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub StreetName: Option<String>,
+    /// Additional Street Name
+    #[serde(
+        rename(
+            serialize = "cbc:AdditionalStreetName",
+            deserialize = "AdditionalStreetName"
+        ),
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub AdditionalStreetName: Option<String>,
+    /// Building Number
+    #[serde(
+        rename(serialize = "cbc:BuildingNumber", deserialize = "BuildingNumber"),
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub BuildingNumber: Option<String>,
+    /// City Name
+    #[serde(
+        rename(serialize = "cbc:CityName", deserialize = "CityName"),
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub CityName: Option<String>,
+    /// Postal Zone
+    #[serde(
+        rename(serialize = "cbc:PostalZone", deserialize = "PostalZone"),
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub PostalZone: Option<String>,
+    /// Country Subentity
+    #[serde(
+        rename(serialize = "cbc:CountrySubentity", deserialize = "CountrySubentity"),
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub CountrySubentity: Option<String>,
+    /// District
+    #[serde(
+        rename(serialize = "cbc:District", deserialize = "District"),
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub District: Option<String>,
+    /// Address Lines
+    #[serde(
+        rename(serialize = "cac:AddressLine", deserialize = "AddressLine"),
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub AddressLine: Vec<UBL_AddressLine>,
+    /// Country
+    #[serde(
+        rename(serialize = "cac:Country", deserialize = "Country"),
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub Country: Option<UBL_Country>,
+}
+
+impl Default for UBL_PostalAddress {
+    fn default() -> Self {
+        UBL_PostalAddress {
+            xmlns_cac: cac_namespace(),
+            xmlns_cbc: cbc_namespace(),
+            Postbox: None,
+            Floor: None,
+            StreetName: None,
+            AdditionalStreetName: None,
+            BuildingNumber: None,
+            CityName: None,
+            PostalZone: None,
+            CountrySubentity: None,
+            District: None,
+            AddressLine: Vec::new(),
+            Country: None,
+        }
+    }
+}
+
+impl UBL_PostalAddress {
+    /// Extracts the seller and buyer postal addresses of a UBL invoice or credit note
+    pub fn extract_invoice(xml: &str) -> Result<Vec<(UBL_Party, Self)>> {
+        let mut res = Vec::new();
+        for party in [UBL_Party::Seller, UBL_Party::Buyer] {
+            if let Some((start, end)) = find_postal_address(xml, party)? {
+                res.push((party, xml[start..end].parse()?));
+            }
+        }
+        Ok(res)
+    }
+
+    /// Replaces the postal address of the given party in a UBL invoice or credit note
+    ///
+    /// The new element declares the `cac` and `cbc` namespaces itself, so it stays valid
+    /// whatever prefixes the invoice uses.
+    pub fn rewrite_invoice(xml: &str, party: UBL_Party, address: &Self) -> Result<String> {
+        let (start, end) = find_postal_address(xml, party)?
+            .ok_or("The invoice has no postal address for this party")?;
+
+        let mut buffer = String::new();
+        address.serialize(Serializer::new(&mut buffer))?;
+
+        Ok(format!("{}{}{}", &xml[..start], buffer, &xml[end..]))
+    }
+
+    /// Parses a `cac:PostalAddress` element, or every party address of a UBL invoice
+    pub fn parse_all(s: &str) -> Result<Vec<Self>> {
+        let parties = Self::extract_invoice(s)?;
+        if parties.is_empty() {
+            Ok(vec![s.parse()?])
+        } else {
+            Ok(parties.into_iter().map(|(_, address)| address).collect())
+        }
+    }
+}
+
+impl Validate for UBL_PostalAddress {
+    fn validate(&self) -> Result<()> {
+        if self.AddressLine.len() > 1 {
+            return Err("At most one `AddressLine` is allowed".into());
+        }
+        match &self.Country {
+            Some(country) if country.IdentificationCode.len() == 2 => Ok(()),
+            Some(_) => Err("The field `IdentificationCode` must have 2 chars".into()),
+            None => Err("The field `Country` is mandatory".into()),
+        }
+    }
+}
+
+impl FromStr for UBL_PostalAddress {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        quick_xml::de::from_str(s).map_err(|e| Box::new(e) as Box<dyn Error>)
+    }
+}
+
+impl TryFrom<Address> for UBL_PostalAddress {
+    type Error = Box<dyn Error>;
+
+    /// The room goes to `cac:AddressLine`, limited to one by Peppol BIS, the post box,
+    /// floor and town location name to `cbc:Postbox`, `cbc:Floor` and `cbc:District`
+    fn try_from(value: Address) -> Result<Self> {
+        let optional = |x: String| Some(x).filter(|x| !x.is_empty());
+
+        Ok(UBL_PostalAddress {
+            Postbox: value.post_box.and_then(optional),
+            Floor: value.floor.and_then(optional),
+            StreetName: value.street_name,
+            BuildingNumber: value.building_number,
+            CityName: optional(value.town_name),
+            PostalZone: optional(value.post_code),
+            CountrySubentity: value.country_sub_division,
+            District: value.town_location_name.and_then(optional),
+            AddressLine: value
+                .room
+                .and_then(optional)
+                .map(|Line| UBL_AddressLine { Line })
+                .into_iter()
+                .collect(),
+            Country: optional(value.country)
+                .map(|IdentificationCode| UBL_Country { IdentificationCode }),
+            ..Default::default()
+        })
+    }
+}
+
+fn cac_namespace() -> String {
+    CAC_NAMESPACE.to_string()
+}
+
+fn cbc_namespace() -> String {
+    CBC_NAMESPACE.to_string()
+}
+
+/// Returns the byte range of the `cac:PostalAddress` of a party, if any
+fn find_postal_address(xml: &str, party: UBL_Party) -> Result<Option<(usize, usize)>> {
+    let cac = ResolveResult::Bound(Namespace(CAC_NAMESPACE.as_bytes()));
+
+    let mut reader = NsReader::from_str(xml);
+    let mut stack: Vec<Vec<u8>> = Vec::new();
+    let mut start = None;
+
+    loop {
+        let position = reader.buffer_position() as usize;
+        let (ns, event) = reader.read_resolved_event()?;
+
+        match event {
+            Event::Start(e) => {
+                let name = e.local_name().as_ref().to_vec();
+                let in_party = stack.iter().any(|x| x == party.element());
+                if start.is_none() && in_party && ns == cac && name == b"PostalAddress" {
+                    start = Some((position, stack.len()));
+                }
+                stack.push(name);
+            }
+            Event::End(_) => {
+                stack.pop();
+                if let Some((begin, depth)) = start {
+                    if stack.len() == depth {
+                        return Ok(Some((begin, reader.buffer_position() as usize)));
+                    }
+                }
+            }
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quick_xml::se::to_string;
+
+    use super::*;
+
+    const INVOICE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Invoice xmlns="urn:oasis:names:specification:ubl:schema:xsd:Invoice-2"
+    xmlns:a="urn:oasis:names:specification:ubl:schema:xsd:CommonAggregateComponents-2"
+    xmlns:b="urn:oasis:names:specification:ubl:schema:xsd:CommonBasicComponents-2">
+    <b:ID>F-2024-001</b:ID>
+    <a:AccountingSupplierParty>
+        <a:Party>
+            <a:PostalAddress>
+                <b:StreetName>22BIS RUE DES FLEURS</b:StreetName>
+                <b:CityName>LIBOURNE</b:CityName>
+                <b:PostalZone>33506</b:PostalZone>
+                <a:AddressLine><b:Line>BP 40122</b:Line></a:AddressLine>
+                <a:Country><b:IdentificationCode>FR</b:IdentificationCode></a:Country>
+            </a:PostalAddress>
+        </a:Party>
+    </a:AccountingSupplierParty>
+    <a:AccountingCustomerParty>
+        <a:Party>
+            <a:PostalAddress>
+                <b:StreetName>56 RUE EMILE ZOLA</b:StreetName>
+                <b:CityName>MONTPELLIER</b:CityName>
+                <b:PostalZone>34092</b:PostalZone>
+                <a:Country><b:IdentificationCode>FR</b:IdentificationCode></a:Country>
+            </a:PostalAddress>
+        </a:Party>
+    </a:AccountingCustomerParty>
+</Invoice>"#;
+
+    #[test]
+    fn test_parse() {
+        let content = r#"<cac:PostalAddress
+            xmlns:cac="urn:oasis:names:specification:ubl:schema:xsd:CommonAggregateComponents-2"
+            xmlns:cbc="urn:oasis:names:specification:ubl:schema:xsd:CommonBasicComponents-2">
+                <cbc:StreetName>RUE DES FLEURS</cbc:StreetName>
+                <cbc:BuildingNumber>25D</cbc:BuildingNumber>
+                <cbc:CityName>LIBOURNE</cbc:CityName>
+                <cbc:PostalZone>33500</cbc:PostalZone>
+                <cac:Country><cbc:IdentificationCode listID="ISO3166-1:Alpha2">FR</cbc:IdentificationCode></cac:Country>
+            </cac:PostalAddress>
+        "#;
+
+        let result = UBL_PostalAddress {
+            StreetName: "RUE DES FLEURS".to_string().into(),
+            BuildingNumber: "25D".to_string().into(),
+            CityName: "LIBOURNE".to_string().into(),
+            PostalZone: "33500".to_string().into(),
+            Country: UBL_Country {
+                IdentificationCode: "FR".to_string(),
+            }
+            .into(),
+            ..Default::default()
+        };
+
+        let parse: UBL_PostalAddress = content.parse().expect("Cannot parse XML!");
+        assert_eq!(parse, result);
+    }
+
+    #[test]
+    fn test_xml() {
+        let content = r#"<cac:PostalAddress xmlns:cac="urn:oasis:names:specification:ubl:schema:xsd:CommonAggregateComponents-2" xmlns:cbc="urn:oasis:names:specification:ubl:schema:xsd:CommonBasicComponents-2"><cbc:StreetName>25D RUE DES FLEURS</cbc:StreetName><cbc:CityName>LIBOURNE</cbc:CityName><cbc:PostalZone>33500</cbc:PostalZone><cac:Country><cbc:IdentificationCode>FR</cbc:IdentificationCode></cac:Country></cac:PostalAddress>"#;
+
+        let result = UBL_PostalAddress {
+            StreetName: "25D RUE DES FLEURS".to_string().into(),
+            CityName: "LIBOURNE".to_string().into(),
+            PostalZone: "33500".to_string().into(),
+            Country: UBL_Country {
+                IdentificationCode: "FR".to_string(),
+            }
+            .into(),
+            ..Default::default()
+        };
+
+        let xml = to_string(&result).expect("Can Error");
+        assert_eq!(xml, content);
+    }
+
+    #[test]
+    fn test_extract_invoice() {
+        let parties = UBL_PostalAddress::extract_invoice(INVOICE).expect("error");
+
+        assert_eq!(parties.len(), 2);
+        assert_eq!(parties[0].0, UBL_Party::Seller);
+        assert_eq!(parties[0].1.AddressLine[0].Line, "BP 40122");
+        assert_eq!(parties[1].0, UBL_Party::Buyer);
+        assert_eq!(parties[1].1.PostalZone.as_deref(), Some("34092"));
+    }
+
+    #[test]
+    fn test_rewrite_invoice() {
+        let address = UBL_PostalAddress {
+            StreetName: "1 PLACE DE LA COMEDIE".to_string().into(),
+            CityName: "MONTPELLIER".to_string().into(),
+            PostalZone: "34000".to_string().into(),
+            ..Default::default()
+        };
+
+        let invoice =
+            UBL_PostalAddress::rewrite_invoice(INVOICE, UBL_Party::Buyer, &address).expect("error");
+        let parties = UBL_PostalAddress::extract_invoice(&invoice).expect("error");
+
+        assert_eq!(parties[0].1.PostalZone.as_deref(), Some("33506"));
+        assert_eq!(parties[1].1, address);
+        assert!(invoice.contains("<b:ID>F-2024-001</b:ID>"));
+    }
+
+    #[test]
+    fn test_address_lines() {
+        let address = Address {
+            room: "Chez Mireille COPEAU Appartement 2".to_string().into(),
+            floor: "Entrée A Bâtiment Jonquille".to_string().into(),
+            street_name: "25 RUE DE L’EGLISE".to_string().into(),
+            post_box: "BP 12".to_string().into(),
+            town_location_name: "CAUDOS".to_string().into(),
+            post_code: "33380".to_string(),
+            town_name: "MIOS".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let ubl = UBL_PostalAddress::try_from(address.clone()).expect("error");
+        assert_eq!(ubl.AddressLine.len(), 1);
+        assert_eq!(
+            ubl.AddressLine[0].Line,
+            "Chez Mireille COPEAU Appartement 2"
+        );
+        assert_eq!(ubl.Floor.as_deref(), Some("Entrée A Bâtiment Jonquille"));
+        assert_eq!(ubl.Postbox.as_deref(), Some("BP 12"));
+        assert_eq!(ubl.District.as_deref(), Some("CAUDOS"));
+        assert_eq!(ubl.AdditionalStreetName, None);
+        assert!(ubl.validate().is_ok());
+
+        let xml = to_string(&ubl).expect("error");
+        let parse: UBL_PostalAddress = xml.parse().expect("Cannot parse XML!");
+        assert_eq!(Address::try_from(parse).expect("error"), address);
+    }
+
+    #[test]
+    fn test_validate() {
+        let result = UBL_PostalAddress {
+            CityName: "LIBOURNE".to_string().into(),
+            ..Default::default()
+        };
+
+        assert!(result.validate().is_err());
+
+        let line = UBL_AddressLine {
+            Line: "BP 40122".to_string(),
+        };
+        let result = UBL_PostalAddress {
+            AddressLine: vec![line.clone(), line],
+            Country: UBL_Country {
+                IdentificationCode: "FR".to_string(),
+            }
+            .into(),
+            ..Default::default()
+        };
+        assert!(result.validate().is_err());
+    }
+}
@@ -0,0 +1,623 @@
+use std::fmt;
+use std::str::FromStr;
+
+use std::error::Error;
+
+use crate::common::{find_country, matches_pattern, normalize_country, Result};
+
+use super::address::Address;
+use super::validate::Validate;
+
+/// Maximum length of the delivery address line
+const MAX_LENGTH: usize = 64;
+
+/// Directionals (Publication 28, Appendix B)
+const DIRECTIONALS: &[(&str, &[&str])] = &[
+    ("N", &["NORTH"]),
+    ("S", &["SOUTH"]),
+    ("E", &["EAST"]),
+    ("W", &["WEST"]),
+    ("NE", &["NORTHEAST"]),
+    ("NW", &["NORTHWEST"]),
+    ("SE", &["SOUTHEAST"]),
+    ("SW", &["SOUTHWEST"]),
+];
+
+/// Street suffixes and their common variants (Publication 28, Appendix C1)
+const SUFFIXES: &[(&str, &[&str])] = &[
+    ("ALY", &["ALLEY", "ALLEE", "ALLY"]),
+    ("ANX", &["ANNEX", "ANEX", "ANNX"]),
+    ("AVE", &["AVENUE", "AV", "AVEN", "AVENU", "AVN", "AVNUE"]),
+    ("BCH", &["BEACH"]),
+    ("BLVD", &["BOULEVARD", "BOUL", "BOULV"]),
+    ("BND", &["BEND"]),
+    ("BR", &["BRANCH", "BRNCH"]),
+    ("BRG", &["BRIDGE", "BRDGE"]),
+    ("BYP", &["BYPASS", "BYPA", "BYPAS", "BYPS"]),
+    ("CIR", &["CIRCLE", "CIRC", "CIRCL", "CRCL", "CRCLE"]),
+    ("CLF", &["CLIFF"]),
+    ("CLB", &["CLUB"]),
+    ("CMN", &["COMMON"]),
+    ("COR", &["CORNER"]),
+    ("CRK", &["CREEK"]),
+    ("CRES", &["CRESCENT", "CRSENT", "CRSNT"]),
+    ("CSWY", &["CAUSEWAY", "CAUSWA"]),
+    ("CT", &["COURT"]),
+    (
+        "CTR",
+        &["CENTER", "CEN", "CENT", "CENTR", "CENTRE", "CNTER", "CNTR"],
+    ),
+    ("CV", &["COVE"]),
+    ("CYN", &["CANYON", "CANYN", "CNYN"]),
+    ("DR", &["DRIVE", "DRIV", "DRV"]),
+    ("EST", &["ESTATE"]),
+    ("EXPY", &["EXPRESSWAY", "EXP", "EXPR", "EXPRESS", "EXPW"]),
+    ("EXT", &["EXTENSION", "EXTN", "EXTNSN"]),
+    ("FLDS", &["FIELDS"]),
+    ("FLS", &["FALLS"]),
+    ("FRK", &["FORK"]),
+    ("FRST", &["FOREST", "FORESTS"]),
+    ("FRY", &["FERRY", "FRRY"]),
+    ("FWY", &["FREEWAY", "FREEWY", "FRWAY", "FRWY"]),
+    ("GDNS", &["GARDENS", "GARDNS"]),
+    ("GLN", &["GLEN"]),
+    ("GRN", &["GREEN"]),
+    ("GRV", &["GROVE", "GROV"]),
+    ("HBR", &["HARBOR", "HARB", "HARBR", "HRBOR"]),
+    ("HL", &["HILL"]),
+    ("HLS", &["HILLS"]),
+    ("HOLW", &["HOLLOW", "HLLW", "HOLLOWS", "HOLWS"]),
+    ("HTS", &["HEIGHTS", "HT"]),
+    ("HWY", &["HIGHWAY", "HIGHWY", "HIWAY", "HIWY", "HWAY"]),
+    ("IS", &["ISLAND", "ISLND"]),
+    ("JCT", &["JUNCTION", "JCTION", "JCTN", "JUNCTN", "JUNCTON"]),
+    ("KY", &["KEY"]),
+    ("LK", &["LAKE"]),
+    ("LKS", &["LAKES"]),
+    ("LN", &["LANE"]),
+    ("LNDG", &["LANDING", "LNDNG"]),
+    ("LOOP", &["LOOPS"]),
+    ("MALL", &[]),
+    ("MDW", &["MEADOW"]),
+    ("MDWS", &["MEADOWS", "MEDOWS"]),
+    ("MNR", &["MANOR"]),
+    ("MT", &["MOUNT", "MNT"]),
+    ("MTN", &["MOUNTAIN", "MNTAIN", "MNTN", "MOUNTIN", "MTIN"]),
+    ("OVAL", &["OVL"]),
+    ("PARK", &["PRK", "PARKS"]),
+    ("PATH", &["PATHS"]),
+    ("PIKE", &["PIKES"]),
+    (
+        "PKWY",
+        &["PARKWAY", "PARKWY", "PKWAY", "PKY", "PARKWAYS", "PKWYS"],
+    ),
+    ("PL", &["PLACE"]),
+    ("PLZ", &["PLAZA", "PLZA"]),
+    ("PNE", &["PINE"]),
+    ("PT", &["POINT"]),
+    ("RD", &["ROAD"]),
+    ("RDG", &["RIDGE", "RDGE"]),
+    ("RIV", &["RIVER", "RVR", "RIVR"]),
+    ("RTE", &["ROUTE"]),
+    ("RUN", &[]),
+    ("SHR", &["SHORE", "SHOAR"]),
+    ("SQ", &["SQUARE", "SQR", "SQRE", "SQU"]),
+    ("ST", &["STREET", "STRT", "STR"]),
+    ("STA", &["STATION", "STATN", "STN"]),
+    ("TER", &["TERRACE", "TERR"]),
+    ("TPKE", &["TURNPIKE", "TRNPK", "TURNPK"]),
+    ("TRCE", &["TRACE", "TRACES"]),
+    ("TRL", &["TRAIL", "TRAILS", "TRLS"]),
+    ("VLG", &["VILLAGE", "VILL", "VILLAG", "VILLG", "VILLIAGE"]),
+    ("VIS", &["VISTA", "VIST", "VST", "VSTA"]),
+    ("VW", &["VIEW"]),
+    ("WAY", &["WY"]),
+    ("XING", &["CROSSING", "CRSSNG"]),
+];
+
+/// Secondary unit designators (Publication 28, Appendix C2)
+const UNITS: &[(&str, &[&str])] = &[
+    ("APT", &["APARTMENT"]),
+    ("BLDG", &["BUILDING"]),
+    ("BSMT", &["BASEMENT"]),
+    ("DEPT", &["DEPARTMENT"]),
+    ("FL", &["FLOOR"]),
+    ("FRNT", &["FRONT"]),
+    ("HNGR", &["HANGAR"]),
+    ("KEY", &[]),
+    ("LBBY", &["LOBBY"]),
+    ("LOT", &[]),
+    ("LOWR", &["LOWER"]),
+    ("OFC", &["OFFICE"]),
+    ("PH", &["PENTHOUSE"]),
+    ("PIER", &[]),
+    ("REAR", &[]),
+    ("RM", &["ROOM"]),
+    ("SIDE", &[]),
+    ("SLIP", &[]),
+    ("SPC", &["SPACE"]),
+    ("STE", &["SUITE"]),
+    ("STOP", &[]),
+    ("TRLR", &["TRAILER"]),
+    ("UNIT", &[]),
+    ("UPPR", &["UPPER"]),
+    ("#", &[]),
+];
+
+/// Designators that are used without a secondary number
+const UNITS_WITHOUT_NUMBER: &[&str] = &[
+    "BSMT", "FRNT", "LBBY", "LOWR", "OFC", "PH", "REAR", "SIDE", "UPPR",
+];
+
+/// State and territory codes, with their names
+const STATES: &[(&str, &str)] = &[
+    ("AL", "ALABAMA"),
+    ("AK", "ALASKA"),
+    ("AZ", "ARIZONA"),
+    ("AR", "ARKANSAS"),
+    ("CA", "CALIFORNIA"),
+    ("CO", "COLORADO"),
+    ("CT", "CONNECTICUT"),
+    ("DE", "DELAWARE"),
+    ("DC", "DISTRICT OF COLUMBIA"),
+    ("FL", "FLORIDA"),
+    ("GA", "GEORGIA"),
+    ("HI", "HAWAII"),
+    ("ID", "IDAHO"),
+    ("IL", "ILLINOIS"),
+    ("IN", "INDIANA"),
+    ("IA", "IOWA"),
+    ("KS", "KANSAS"),
+    ("KY", "KENTUCKY"),
+    ("LA", "LOUISIANA"),
+    ("ME", "MAINE"),
+    ("MD", "MARYLAND"),
+    ("MA", "MASSACHUSETTS"),
+    ("MI", "MICHIGAN"),
+    ("MN", "MINNESOTA"),
+    ("MS", "MISSISSIPPI"),
+    ("MO", "MISSOURI"),
+    ("MT", "MONTANA"),
+    ("NE", "NEBRASKA"),
+    ("NV", "NEVADA"),
+    ("NH", "NEW HAMPSHIRE"),
+    ("NJ", "NEW JERSEY"),
+    ("NM", "NEW MEXICO"),
+    ("NY", "NEW YORK"),
+    ("NC", "NORTH CAROLINA"),
+    ("ND", "NORTH DAKOTA"),
+    ("OH", "OHIO"),
+    ("OK", "OKLAHOMA"),
+    ("OR", "OREGON"),
+    ("PA", "PENNSYLVANIA"),
+    ("RI", "RHODE ISLAND"),
+    ("SC", "SOUTH CAROLINA"),
+    ("SD", "SOUTH DAKOTA"),
+    ("TN", "TENNESSEE"),
+    ("TX", "TEXAS"),
+    ("UT", "UTAH"),
+    ("VT", "VERMONT"),
+    ("VA", "VIRGINIA"),
+    ("WA", "WASHINGTON"),
+    ("WV", "WEST VIRGINIA"),
+    ("WI", "WISCONSIN"),
+    ("WY", "WYOMING"),
+    ("AS", "AMERICAN SAMOA"),
+    ("GU", "GUAM"),
+    ("MP", "NORTHERN MARIANA ISLANDS"),
+    ("PR", "PUERTO RICO"),
+    ("VI", "VIRGIN ISLANDS"),
+    ("FM", "FEDERATED STATES OF MICRONESIA"),
+    ("MH", "MARSHALL ISLANDS"),
+    ("PW", "PALAU"),
+    ("AA", "ARMED FORCES AMERICAS"),
+    ("AE", "ARMED FORCES EUROPE"),
+    ("AP", "ARMED FORCES PACIFIC"),
+];
+
+/// Returns the standard abbreviation of a word from an abbreviation table
+fn abbreviate(table: &[(&'static str, &[&str])], word: &str) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(abbr, variants)| *abbr == word || variants.contains(&word))
+        .map(|(abbr, _)| *abbr)
+}
+
+/// Returns the two letter code of a state, from its code or its name
+///
+/// # Example
+///
+/// ```
+/// use address::models::address_usps::state_code;
+/// assert_eq!(state_code("California"), Some("CA"));
+/// assert_eq!(state_code("ny"), Some("NY"));
+/// assert_eq!(state_code("XX"), None);
+/// ```
+pub fn state_code(state: &str) -> Option<&'static str> {
+    let state = state.trim().to_uppercase();
+    STATES
+        .iter()
+        .find(|(code, name)| *code == state || *name == state)
+        .map(|(code, _)| *code)
+}
+
+/// A US address, standardized following USPS Publication 28
+///
+/// ```text
+/// JOHN DOE
+/// 102 N MAIN ST SE APT 12
+/// SPRINGFIELD IL 62704-1234
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct USPS_Address {
+    /// Recipient line
+    pub name: String,
+    /// Primary address number
+    pub primary_number: String,
+    /// Pre-directional (`N`, `SW`, ...)
+    pub pre_directional: String,
+    /// Street name
+    pub street_name: String,
+    /// Street suffix abbreviation (`ST`, `AVE`, ...)
+    pub suffix: String,
+    /// Post-directional
+    pub post_directional: String,
+    /// Secondary unit designator (`APT`, `STE`, `#`, ...)
+    pub unit: String,
+    /// Secondary unit number
+    pub unit_number: String,
+    /// City
+    pub city: String,
+    /// Two letter state code
+    pub state: String,
+    /// ZIP Code or ZIP+4 (`12345-6789`)
+    pub zip: String,
+}
+
+impl USPS_Address {
+    /// Returns the delivery address line
+    pub fn delivery_line(&self) -> String {
+        [
+            &self.primary_number,
+            &self.pre_directional,
+            &self.street_name,
+            &self.suffix,
+            &self.post_directional,
+            &self.unit,
+            &self.unit_number,
+        ]
+        .into_iter()
+        .filter(|x| !x.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+
+    /// Returns the last line: city, state and ZIP Code
+    pub fn last_line(&self) -> String {
+        [&self.city, &self.state, &self.zip]
+            .into_iter()
+            .filter(|x| !x.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns the secondary unit, e.g. `APT 12`
+    pub fn secondary(&self) -> String {
+        format!("{} {}", self.unit, self.unit_number)
+            .trim()
+            .to_string()
+    }
+
+    /// Parses and standardizes a delivery address line
+    pub fn parse_delivery_line(&mut self, line: &str) {
+        let mut tokens = tokenize(line);
+
+        // Secondary unit, at the end of the line
+        if let Some(i) = tokens.iter().rposition(|x| parse_unit(x).is_some()) {
+            let (unit, number) = parse_unit(&tokens[i]).unwrap_or_default();
+            let number = match number.is_empty() && i + 2 == tokens.len() {
+                true => tokens[i + 1].clone(),
+                false => number,
+            };
+            let is_last = i + 1 == tokens.len() || !number.is_empty();
+            // A street name must remain before it, `LOT` is the street of `123 LOT RD`
+            let has_primary = tokens[0].starts_with(|c: char| c.is_ascii_digit());
+            let has_street = i > usize::from(has_primary);
+            if has_street && is_last && (!number.is_empty() || UNITS_WITHOUT_NUMBER.contains(&unit))
+            {
+                self.unit = unit.to_string();
+                self.unit_number = number.trim_start_matches('#').to_string();
+                tokens.truncate(i);
+            }
+        }
+
+        let mut tokens = tokens.as_slice();
+        if let Some((first, rest)) = tokens.split_first() {
+            if first.starts_with(|c: char| c.is_ascii_digit()) {
+                self.primary_number = first.clone();
+                tokens = rest;
+            }
+        }
+        if let [first, rest @ ..] = tokens {
+            // In `WEST DR`, the directional is the street name
+            let is_name = match rest {
+                [] => true,
+                [last] => abbreviate(SUFFIXES, last).is_some(),
+                _ => false,
+            };
+            if let (Some(dir), false) = (abbreviate(DIRECTIONALS, first), is_name) {
+                self.pre_directional = dir.to_string();
+                tokens = rest;
+            }
+        }
+        if let [rest @ .., last] = tokens {
+            if let (Some(dir), false) = (abbreviate(DIRECTIONALS, last), rest.is_empty()) {
+                self.post_directional = dir.to_string();
+                tokens = rest;
+            }
+        }
+        if let [rest @ .., last] = tokens {
+            if let (Some(suffix), false) = (abbreviate(SUFFIXES, last), rest.is_empty()) {
+                self.suffix = suffix.to_string();
+                tokens = rest;
+            }
+        }
+
+        self.street_name = tokens.join(" ");
+    }
+
+    /// Parses a last line: `CITY ST 12345-6789` or `City, State 12345`
+    pub fn parse_last_line(&mut self, line: &str) -> Result<()> {
+        let mut tokens = tokenize(line);
+
+        if tokens
+            .last()
+            .is_some_and(|x| x.starts_with(|c: char| c.is_ascii_digit()))
+        {
+            self.zip = tokens.pop().unwrap_or_default();
+        }
+
+        // State names may have several words (`NEW YORK`)
+        for words in (1..=tokens.len().min(4)).rev() {
+            let state = tokens[tokens.len() - words..].join(" ");
+            if let Some(code) = state_code(&state).filter(|_| words < tokens.len()) {
+                self.state = code.to_string();
+                tokens.truncate(tokens.len() - words);
+                break;
+            }
+        }
+        if self.state.is_empty() {
+            return Err(format!("No state found in `{line}`").into());
+        }
+
+        self.city = tokens.join(" ");
+        Ok(())
+    }
+}
+
+/// Uppercases a line and removes the punctuation, except in numbers (`1/2`, `12-34`)
+fn tokenize(line: &str) -> Vec<String> {
+    line.to_uppercase()
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(|x| x.trim_matches('.').replace('.', ""))
+        .filter(|x| !x.is_empty())
+        .collect()
+}
+
+/// Parses a secondary unit designator, possibly glued to its number (`#12`)
+fn parse_unit(token: &str) -> Option<(&'static str, String)> {
+    if let Some(number) = token.strip_prefix('#') {
+        return Some(("#", number.to_string()));
+    }
+    abbreviate(UNITS, token).map(|unit| (unit, String::new()))
+}
+
+impl Validate for USPS_Address {
+    fn validate(&self) -> Result<()> {
+        if self.delivery_line().chars().count() > MAX_LENGTH {
+            return Err(format!(
+                "The delivery address line must have less than {MAX_LENGTH} chars"
+            )
+            .into());
+        }
+        if state_code(&self.state) != Some(self.state.as_str()) {
+            return Err(format!("`{}` is not a valid state code", self.state).into());
+        }
+        if !matches_pattern(&self.zip, "99999") && !matches_pattern(&self.zip, "99999-9999") {
+            return Err(format!("The ZIP Code `{}` must be 12345 or 12345-6789", self.zip).into());
+        }
+        if self.city.is_empty() {
+            return Err("The city is mandatory".into());
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for USPS_Address {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut lines: Vec<&str> = s
+            .lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+            .collect();
+
+        if lines
+            .last()
+            .is_some_and(|x| find_country(x).is_some() || x.eq_ignore_ascii_case("USA"))
+        {
+            lines.pop();
+        }
+
+        let [others @ .., delivery, last] = lines.as_slice() else {
+            return Err("Input must contain a delivery line and a last line".into());
+        };
+
+        let mut res = USPS_Address::default();
+        res.parse_last_line(last)?;
+        res.parse_delivery_line(delivery);
+
+        for (i, line) in others.iter().enumerate() {
+            let tokens = tokenize(line);
+            // The secondary unit may be written on the line above the delivery line
+            match tokens.first().and_then(|x| parse_unit(x)) {
+                Some((unit, number)) if i > 0 && res.unit.is_empty() => {
+                    res.unit = unit.to_string();
+                    res.unit_number = match number.is_empty() {
+                        true => tokens[1..].join(" "),
+                        false => number,
+                    };
+                }
+                _ if res.name.is_empty() => res.name = tokens.join(" "),
+                _ => {
+                    res.name.push(' ');
+                    res.name.push_str(&tokens.join(" "));
+                }
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+impl fmt::Display for USPS_Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = [
+            self.name.to_uppercase(),
+            self.delivery_line(),
+            self.last_line(),
+        ]
+        .into_iter()
+        .filter(|x| !x.is_empty())
+        .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl TryFrom<Address> for USPS_Address {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: Address) -> Result<Self> {
+        let country = normalize_country(&value.country)?;
+        if !country.is_empty() && country != "US" {
+            return Err(format!("USPS addresses must be in US, not `{country}`").into());
+        }
+
+        let delivery = [&value.building_number, &value.street_name, &value.room]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let state = value.country_sub_division.unwrap_or_default();
+
+        let mut res = USPS_Address {
+            name: value.name.unwrap_or_default().to_uppercase(),
+            city: tokenize(&value.town_name).join(" "),
+            state: state_code(&state).map(|x| x.to_string()).unwrap_or(state),
+            zip: value.post_code,
+            ..Default::default()
+        };
+        res.parse_delivery_line(&delivery);
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let content = "John Doe\n102 North Main Street Southeast, Apartment 12\nSpringfield, Illinois 62704-1234";
+
+        let result = USPS_Address {
+            name: "JOHN DOE".to_string(),
+            primary_number: "102".to_string(),
+            pre_directional: "N".to_string(),
+            street_name: "MAIN".to_string(),
+            suffix: "ST".to_string(),
+            post_directional: "SE".to_string(),
+            unit: "APT".to_string(),
+            unit_number: "12".to_string(),
+            city: "SPRINGFIELD".to_string(),
+            state: "IL".to_string(),
+            zip: "62704-1234".to_string(),
+        };
+
+        let parse: USPS_Address = content.parse().expect("Cannot parse USPS!");
+        assert_eq!(parse, result);
+        assert_eq!(
+            parse.to_string(),
+            "JOHN DOE\n102 N MAIN ST SE APT 12\nSPRINGFIELD IL 62704-1234"
+        );
+        assert!(parse.validate().is_ok());
+    }
+
+    #[test]
+    fn test_parse_delivery_line() {
+        let mut address = USPS_Address::default();
+        address.parse_delivery_line("1600 Pennsylvania Ave. NW #200");
+        assert_eq!(address.delivery_line(), "1600 PENNSYLVANIA AVE NW # 200");
+
+        let mut address = USPS_Address::default();
+        address.parse_delivery_line("25 West Drive Rear");
+        assert_eq!(address.delivery_line(), "25 WEST DR REAR");
+
+        let mut address = USPS_Address::default();
+        address.parse_delivery_line("123 Lot Rd");
+        assert_eq!(address.street_name, "LOT");
+        assert_eq!(address.suffix, "RD");
+        assert_eq!(address.unit, "");
+
+        let mut address = USPS_Address::default();
+        address.parse_delivery_line("100 Ocean Key");
+        assert_eq!(address.street_name, "OCEAN");
+        assert_eq!(address.suffix, "KY");
+        assert_eq!(address.delivery_line(), "100 OCEAN KY");
+
+        let mut address = USPS_Address::default();
+        address.parse_delivery_line("100 Ocean Key Lot 5");
+        assert_eq!(address.delivery_line(), "100 OCEAN KY LOT 5");
+    }
+
+    #[test]
+    fn test_from_address() {
+        let address = Address {
+            name: "Acme Inc".to_string().into(),
+            building_number: "350".to_string().into(),
+            street_name: "Fifth Avenue".to_string().into(),
+            room: "Suite 3300".to_string().into(),
+            post_code: "10118".to_string(),
+            town_name: "New York".to_string(),
+            country_sub_division: "New York".to_string().into(),
+            country: "US".to_string(),
+            ..Default::default()
+        };
+
+        let usps = USPS_Address::try_from(address).expect("error");
+        assert_eq!(
+            usps.to_string(),
+            "ACME INC\n350 FIFTH AVE STE 3300\nNEW YORK NY 10118"
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let address: USPS_Address = "100 MAIN ST\nSPRINGFIELD IL 6270".parse().expect("error");
+        assert!(address.validate().is_err());
+
+        let address = USPS_Address {
+            city: "SPRINGFIELD".to_string(),
+            state: "XX".to_string(),
+            zip: "62704".to_string(),
+            ..Default::default()
+        };
+        assert!(address.validate().is_err());
+    }
+}
@@ -0,0 +1,475 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::{json, Value};
+
+use crate::common::{alpha2_to_country, Result};
+
+use super::address::Address;
+
+/// The `ADR` property of a vCard 3.0/4.0 (RFC 6350)
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct VCard_ADR {
+    /// Post Office Box
+    pub pobox: String,
+    /// Extended Address (apartment or suite)
+    pub ext: String,
+    /// Street Address
+    pub street: String,
+    /// Locality
+    pub locality: String,
+    /// Region
+    pub region: String,
+    /// Postal Code
+    pub code: String,
+    /// Country Name
+    pub country: String,
+    /// Formatted label (`LABEL` parameter)
+    pub label: Option<String>,
+}
+
+impl VCard_ADR {
+    /// Parses every `ADR` property found in a vCard file, which may contain several contacts
+    pub fn parse_all(s: &str) -> Result<Vec<Self>> {
+        unfold(s)
+            .iter()
+            .filter_map(|line| ContentLine::parse(line))
+            .filter(|line| line.name.eq_ignore_ascii_case("ADR"))
+            .map(|line| Self::from_content_line(&line))
+            .collect()
+    }
+
+    /// Parses every `adr` property found in a jCard (RFC 7095), a list of jCards or a
+    /// single `adr` property
+    pub fn parse_all_jcard(value: &Value) -> Result<Vec<Self>> {
+        let mut res = Vec::new();
+        collect_jcard(value, &mut res)?;
+        Ok(res)
+    }
+
+    /// Parses a single jCard `adr` property
+    pub fn from_jcard(value: &Value) -> Result<Self> {
+        let prop = value.as_array().ok_or("jCard property must be an array")?;
+
+        match prop.first().and_then(Value::as_str) {
+            Some(name) if name.eq_ignore_ascii_case("adr") => {}
+            _ => return Err("jCard property is not an `adr`".into()),
+        }
+
+        let label = prop
+            .get(1)
+            .and_then(|params| params.get("label"))
+            .and_then(Value::as_str)
+            .map(|x| x.to_string());
+
+        let components: Vec<String> = match prop.get(3) {
+            Some(Value::Array(values)) => values.iter().map(jcard_component).collect(),
+            Some(Value::String(value)) => split_unescaped(value, ';'),
+            _ => return Err("jCard `adr` has no value".into()),
+        };
+
+        Ok(Self::from_components(components, label))
+    }
+
+    /// Converts the address into a jCard `adr` property
+    pub fn to_jcard(&self) -> Value {
+        let params = match &self.label {
+            Some(label) => json!({ "label": label }),
+            None => json!({}),
+        };
+
+        json!([
+            "adr",
+            params,
+            "text",
+            [
+                self.pobox,
+                self.ext,
+                self.street,
+                self.locality,
+                self.region,
+                self.code,
+                self.country
+            ]
+        ])
+    }
+
+    fn from_content_line(line: &ContentLine) -> Result<Self> {
+        let label = line
+            .params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("LABEL"))
+            .map(|(_, value)| value.clone());
+
+        Ok(Self::from_components(
+            split_unescaped(&line.value, ';'),
+            label,
+        ))
+    }
+
+    fn from_components(components: Vec<String>, label: Option<String>) -> Self {
+        let mut components = components.into_iter();
+        let mut next = || components.next().unwrap_or_default();
+
+        VCard_ADR {
+            pobox: next(),
+            ext: next(),
+            street: next(),
+            locality: next(),
+            region: next(),
+            code: next(),
+            country: next(),
+            label,
+        }
+    }
+}
+
+impl FromStr for VCard_ADR {
+    type Err = Box<dyn Error>;
+
+    /// Parses the first `ADR` property of a vCard
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_all(s)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| "No `ADR` property found".into())
+    }
+}
+
+impl fmt::Display for VCard_ADR {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ADR")?;
+        if let Some(label) = &self.label {
+            let label = label.replace('"', "'").replace('\n', "\\n");
+            write!(f, ";LABEL=\"{label}\"")?;
+        }
+
+        let components = [
+            &self.pobox,
+            &self.ext,
+            &self.street,
+            &self.locality,
+            &self.region,
+            &self.code,
+            &self.country,
+        ]
+        .map(|x| escape(x));
+
+        write!(f, ":{}", components.join(";"))
+    }
+}
+
+impl TryFrom<Address> for VCard_ADR {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: Address) -> Result<Self> {
+        let join = |parts: &[&Option<String>], sep: &str| {
+            parts
+                .iter()
+                .filter_map(|x| x.as_deref())
+                .filter(|x| !x.is_empty())
+                .collect::<Vec<_>>()
+                .join(sep)
+        };
+
+        // Only the room goes to the extended address, so that it reads back as the room;
+        // the floor and building name are kept in the label
+        let ext = value.room.clone().unwrap_or_default();
+        let building = join(&[&value.floor, &value.building_name], ", ");
+        let street = join(&[&value.building_number, &value.street_name], " ");
+        let pobox = value.post_box.clone().unwrap_or_default();
+        let country = match value.country.as_str() {
+            "" => String::new(),
+            code => alpha2_to_country(code)?.to_string(),
+        };
+
+        let town = format!("{} {}", value.post_code, value.town_name)
+            .trim()
+            .to_string();
+        let label = [
+            &value.name,
+            &Some(ext.clone()),
+            &Some(building),
+            &Some(street.clone()),
+            &value.post_box,
+            &Some(town),
+            &Some(country.clone()),
+        ];
+
+        Ok(VCard_ADR {
+            label: Some(join(&label, "\n")).filter(|x| !x.is_empty()),
+            pobox,
+            ext,
+            street,
+            locality: value.town_name,
+            region: value.country_sub_division.unwrap_or_default(),
+            code: value.post_code,
+            country,
+        })
+    }
+}
+
+/// A single vCard content line, `[group.]name *(;param) : value`
+struct ContentLine {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl ContentLine {
+    fn parse(line: &str) -> Option<Self> {
+        let mut in_quotes = false;
+        let mut parts = vec![String::new()];
+        let mut value = None;
+
+        for (i, c) in line.char_indices() {
+            match c {
+                '"' => in_quotes = !in_quotes,
+                ';' if !in_quotes => parts.push(String::new()),
+                ':' if !in_quotes => {
+                    value = Some(line[i + 1..].to_string());
+                    break;
+                }
+                c => parts.last_mut()?.push(c),
+            }
+        }
+
+        let mut parts = parts.into_iter();
+        let name = parts.next()?;
+        let name = match name.rsplit_once('.') {
+            Some((_group, name)) => name.to_string(),
+            None => name,
+        };
+        let params = parts
+            .filter_map(|param| {
+                let (key, value) = param.split_once('=')?;
+                let value = value.replace("^n", "\n").replace("\\n", "\n");
+                Some((key.to_string(), value))
+            })
+            .collect();
+
+        Some(ContentLine {
+            name,
+            params,
+            value: value?,
+        })
+    }
+}
+
+/// Joins folded lines back together (RFC 6350, section 3.2)
+fn unfold(s: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in s.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines
+}
+
+/// Splits a value on `sep`, ignoring escaped separators and unescaping the parts
+fn split_unescaped(s: &str, sep: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') | Some('N') => current.push('\n'),
+                Some(other) => current.push(other),
+                None => {}
+            },
+            c if c == sep => parts.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    parts.push(current);
+
+    parts
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace('\n', "\\n")
+}
+
+fn jcard_component(value: &Value) -> String {
+    match value {
+        Value::String(x) => x.clone(),
+        Value::Array(values) => values
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(","),
+        _ => String::new(),
+    }
+}
+
+fn collect_jcard(value: &Value, res: &mut Vec<VCard_ADR>) -> Result<()> {
+    let Some(items) = value.as_array() else {
+        return Err("jCard must be an array".into());
+    };
+
+    match items.first() {
+        Some(Value::String(name)) if name.eq_ignore_ascii_case("vcard") => {
+            let props = items
+                .get(1)
+                .and_then(Value::as_array)
+                .ok_or("jCard has no properties")?;
+            for prop in props {
+                if prop.get(0).and_then(Value::as_str) == Some("adr") {
+                    res.push(VCard_ADR::from_jcard(prop)?);
+                }
+            }
+        }
+        Some(Value::String(name)) if name.eq_ignore_ascii_case("adr") => {
+            res.push(VCard_ADR::from_jcard(value)?);
+        }
+        _ => {
+            for item in items {
+                collect_jcard(item, res)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let content = "ADR;TYPE=home;LABEL=\"25D RUE DES FLEURS\\n33500 LIBOURNE\\nFRANCE\":;;25D RUE DES FLEURS;LIBOURNE;;33500;France";
+
+        let result = VCard_ADR {
+            street: "25D RUE DES FLEURS".to_string(),
+            locality: "LIBOURNE".to_string(),
+            code: "33500".to_string(),
+            country: "France".to_string(),
+            label: Some("25D RUE DES FLEURS\n33500 LIBOURNE\nFRANCE".to_string()),
+            ..Default::default()
+        };
+
+        let parse: VCard_ADR = content.parse().expect("Cannot parse vCard!");
+        assert_eq!(parse, result);
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let content = "BEGIN:VCARD\r\n\
+            VERSION:4.0\r\n\
+            FN:Jean DURAND\r\n\
+            ADR;TYPE=home:;;25D RUE DES FLEURS;LIBOURNE;;33500;Fr\r\n \
+            ance\r\n\
+            END:VCARD\r\n\
+            BEGIN:VCARD\r\n\
+            VERSION:3.0\r\n\
+            FN:DURAND SA\r\n\
+            item1.ADR;TYPE=work:BP 40122;;22BIS RUE DES FLEURS\\, ZI;LIBOURNE;;33506;FRANCE\r\n\
+            END:VCARD\r\n";
+
+        let parse = VCard_ADR::parse_all(content).expect("Cannot parse vCard!");
+
+        assert_eq!(parse.len(), 2);
+        assert_eq!(parse[0].country, "France");
+        assert_eq!(parse[1].pobox, "BP 40122");
+        assert_eq!(parse[1].street, "22BIS RUE DES FLEURS, ZI");
+    }
+
+    #[test]
+    fn test_to_string() {
+        let address = Address {
+            name: "Monsieur Jean DURAND".to_string().into(),
+            street_name: "25D RUE DES FLEURS".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let vcard: VCard_ADR = address.try_into().expect("error");
+
+        assert_eq!(
+            vcard.to_string(),
+            "ADR;LABEL=\"Monsieur Jean DURAND\\n25D RUE DES FLEURS\\n33500 LIBOURNE\\nFRANCE\":;;25D RUE DES FLEURS;LIBOURNE;;33500;FRANCE"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let address = Address {
+            room: "Appartement 2".to_string().into(),
+            floor: "Entrée A".to_string().into(),
+            building_name: "Bâtiment Jonquille".to_string().into(),
+            street_name: "25 RUE DE L’EGLISE".to_string().into(),
+            post_code: "33380".to_string(),
+            town_name: "MIOS".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let vcard = VCard_ADR::try_from(address.clone()).expect("error");
+        assert_eq!(vcard.ext, "Appartement 2");
+        assert_eq!(
+            vcard.label.as_deref(),
+            Some("Appartement 2\nEntrée A, Bâtiment Jonquille\n25 RUE DE L’EGLISE\n33380 MIOS\nFRANCE")
+        );
+
+        let parse: VCard_ADR = vcard.to_string().parse().expect("Cannot parse vCard!");
+        let result = Address {
+            floor: None,
+            building_name: None,
+            ..address
+        };
+        assert_eq!(Address::try_from(parse).expect("error"), result);
+    }
+
+    #[test]
+    fn test_jcard() {
+        let vcard = VCard_ADR {
+            pobox: "BP 40122".to_string(),
+            street: "22BIS RUE DES FLEURS".to_string(),
+            locality: "LIBOURNE CEDEX".to_string(),
+            code: "33506".to_string(),
+            country: "FRANCE".to_string(),
+            ..Default::default()
+        };
+
+        let jcard = json!([
+            "vcard",
+            [
+                ["version", {}, "text", "4.0"],
+                [
+                    "adr",
+                    {},
+                    "text",
+                    [
+                        "BP 40122",
+                        "",
+                        "22BIS RUE DES FLEURS",
+                        "LIBOURNE CEDEX",
+                        "",
+                        "33506",
+                        "FRANCE"
+                    ]
+                ]
+            ]
+        ]);
+
+        let parse = VCard_ADR::parse_all_jcard(&jcard).expect("Cannot parse jCard!");
+        assert_eq!(parse, vec![vcard.clone()]);
+        assert_eq!(
+            VCard_ADR::from_jcard(&vcard.to_jcard()).expect("error"),
+            vcard
+        );
+    }
+}
@@ -0,0 +1,364 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::common::Result;
+
+use super::address::Address;
+use super::nf_lines::address_lines;
+use super::validate::Validate;
+
+/// Entity identifier code used when converting from an `Address` (ship to)
+const DEFAULT_ENTITY: &str = "ST";
+/// Identification code qualifier written when none is set (assigned by buyer)
+const DEFAULT_ID_QUALIFIER: &str = "92";
+/// Address lines of the two `N3` segments of a loop
+const MAX_ADDRESS_LINES: usize = 4;
+/// Element separator and segment terminator written by `Display`
+const SEPARATORS: [char; 2] = ['*', '~'];
+
+/// An ANSI X12 party loop: `N1` name, `N2` additional names, `N3` address lines and
+/// `N4` geographic location
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct X12_N1 {
+    /// Entity identifier code (N101)
+    pub entity: String,
+    /// Name (N102)
+    pub name: String,
+    /// Identification code qualifier (N103)
+    pub id_qualifier: String,
+    /// Identification code (N104)
+    pub id: String,
+    /// Additional names (N201, N202)
+    pub additional_names: Vec<String>,
+    /// Address lines (N301, N302)
+    pub address: Vec<String>,
+    /// City name (N401)
+    pub city: String,
+    /// State or province code (N402)
+    pub state: String,
+    /// Postal code (N403)
+    pub postal_code: String,
+    /// Country code (N404)
+    pub country: String,
+}
+
+impl X12_N1 {
+    /// Parses every `N1` loop of an interchange
+    ///
+    /// The element separator and segment terminator are read from the `ISA` segment
+    /// when present, otherwise `*` and `~` are used.
+    pub fn parse_all(s: &str) -> Result<Vec<Self>> {
+        let s = s.trim_start();
+        let (element, segment) = match (s.starts_with("ISA"), s.chars().nth(105)) {
+            (true, Some(segment)) => (s.chars().nth(3).unwrap_or('*'), segment),
+            _ => ('*', '~'),
+        };
+
+        let mut res: Vec<X12_N1> = Vec::new();
+        let mut in_loop = false;
+
+        for segment in s.split(segment).map(|x| x.trim()) {
+            let elements: Vec<String> = segment.split(element).map(|x| x.to_string()).collect();
+            let get = |i: usize| elements.get(i).cloned().unwrap_or_default();
+            let values = |range: std::ops::Range<usize>| -> Vec<String> {
+                range.map(get).filter(|x| !x.is_empty()).collect()
+            };
+
+            match (elements[0].as_str(), res.last_mut()) {
+                ("N1", _) => {
+                    in_loop = true;
+                    res.push(X12_N1 {
+                        entity: get(1),
+                        name: get(2),
+                        id_qualifier: get(3),
+                        id: get(4),
+                        ..Default::default()
+                    });
+                }
+                ("N2", Some(party)) if in_loop => party.additional_names.extend(values(1..3)),
+                ("N3", Some(party)) if in_loop => party.address.extend(values(1..3)),
+                ("N4", Some(party)) if in_loop => {
+                    party.city = get(1);
+                    party.state = get(2);
+                    party.postal_code = get(3);
+                    party.country = get(4);
+                }
+                // Other segments of the loop (REF, PER, ...) are ignored
+                ("REF" | "PER" | "FOB", _) => {}
+                _ => in_loop = false,
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+impl Validate for X12_N1 {
+    fn validate(&self) -> Result<()> {
+        let check = |x: &str, min_len: usize, max_len: usize, field_name: &str| {
+            let len = x.chars().count();
+            if len > 0 && (len < min_len || len > max_len) {
+                Err(format!(
+                    "The field `{field_name}` must have between {min_len} and {max_len} chars"
+                ))
+            } else {
+                Ok(())
+            }
+        };
+
+        check(&self.entity, 2, 3, "N101")?;
+        check(&self.name, 1, 60, "N102")?;
+        check(&self.id_qualifier, 1, 2, "N103")?;
+        check(&self.id, 2, 80, "N104")?;
+        for name in &self.additional_names {
+            check(name, 1, 60, "N201")?;
+        }
+        for line in &self.address {
+            check(line, 1, 55, "N301")?;
+        }
+        if self.address.len() > MAX_ADDRESS_LINES {
+            return Err("At most 2 `N3` segments are allowed".into());
+        }
+        check(&self.city, 2, 30, "N401")?;
+        check(&self.state, 2, 2, "N402")?;
+        check(&self.postal_code, 3, 15, "N403")?;
+        check(&self.country, 2, 3, "N404")?;
+
+        self.check_separators()
+    }
+}
+
+impl X12_N1 {
+    /// Checks that no element contains the separators written by `Display`, which
+    /// would split it into other elements or segments
+    fn check_separators(&self) -> Result<()> {
+        let fields = [
+            ("N101", &self.entity),
+            ("N102", &self.name),
+            ("N103", &self.id_qualifier),
+            ("N104", &self.id),
+            ("N401", &self.city),
+            ("N402", &self.state),
+            ("N403", &self.postal_code),
+            ("N404", &self.country),
+        ];
+        let names = self.additional_names.iter().map(|x| ("N201", x));
+        let lines = self.address.iter().map(|x| ("N301", x));
+
+        for (field_name, x) in fields.into_iter().chain(names).chain(lines) {
+            if x.contains(SEPARATORS) {
+                return Err(format!("The field `{field_name}` must not contain `*` or `~`").into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for X12_N1 {
+    type Err = Box<dyn Error>;
+
+    /// Parses the first `N1` loop of an interchange
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_all(s)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| "No `N1` segment found".into())
+    }
+}
+
+impl fmt::Display for X12_N1 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let segment = |mut elements: Vec<&str>| {
+            while elements.last().is_some_and(|x| x.is_empty()) {
+                elements.pop();
+            }
+            format!("{}~", elements.join("*"))
+        };
+
+        let id_qualifier = match (self.id.is_empty(), self.id_qualifier.as_str()) {
+            (true, _) => "",
+            (false, "") => DEFAULT_ID_QUALIFIER,
+            (false, qualifier) => qualifier,
+        };
+        let mut segments = vec![segment(vec![
+            "N1",
+            &self.entity,
+            &self.name,
+            id_qualifier,
+            &self.id,
+        ])];
+        for names in self.additional_names.chunks(2) {
+            let mut elements = vec!["N2"];
+            elements.extend(names.iter().map(|x| x.as_str()));
+            segments.push(segment(elements));
+        }
+        for lines in self.address.chunks(2) {
+            let mut elements = vec!["N3"];
+            elements.extend(lines.iter().map(|x| x.as_str()));
+            segments.push(segment(elements));
+        }
+        segments.push(segment(vec![
+            "N4",
+            &self.city,
+            &self.state,
+            &self.postal_code,
+            &self.country,
+        ]));
+
+        write!(f, "{}", segments.join("\n"))
+    }
+}
+
+impl TryFrom<Address> for X12_N1 {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: Address) -> Result<Self> {
+        let address = address_lines(&value);
+        if address.len() > MAX_ADDRESS_LINES {
+            return Err(format!(
+                "At most {MAX_ADDRESS_LINES} address lines fit in 2 `N3` segments, not {}",
+                address.len()
+            )
+            .into());
+        }
+
+        let party = X12_N1 {
+            entity: DEFAULT_ENTITY.to_string(),
+            address,
+            name: value.name.unwrap_or_default(),
+            additional_names: value.department.into_iter().collect(),
+            city: value.town_name,
+            state: value.country_sub_division.unwrap_or_default(),
+            postal_code: value.post_code,
+            country: value.country,
+            ..Default::default()
+        };
+        party.check_separators()?;
+
+        Ok(party)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_all() {
+        let content = "ISA*00*          *00*          *ZZ*SENDER         *ZZ*RECEIVER       *240101*1200*U*00401*000000001*0*P*>~\
+            GS*PO*SENDER*RECEIVER*20240101*1200*1*X*004010~\
+            ST*850*0001~\
+            N1*BT*DURAND SA*92*123~\
+            N2*Service achat~\
+            N3*22BIS RUE DES FLEURS*BP 40122~\
+            N4*LIBOURNE CEDEX**33506*FR~\
+            N1*ST*JOHN DOE~\
+            N3*100 MAIN ST~\
+            N4*SPRINGFIELD*IL*62701*US~\
+            SE*9*0001~";
+
+        let result = X12_N1 {
+            entity: "BT".to_string(),
+            name: "DURAND SA".to_string(),
+            id_qualifier: "92".to_string(),
+            id: "123".to_string(),
+            additional_names: vec!["Service achat".to_string()],
+            address: vec!["22BIS RUE DES FLEURS".to_string(), "BP 40122".to_string()],
+            city: "LIBOURNE CEDEX".to_string(),
+            postal_code: "33506".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let parse = X12_N1::parse_all(content).expect("Cannot parse X12!");
+        assert_eq!(parse.len(), 2);
+        assert_eq!(parse[0], result);
+        assert_eq!(parse[1].state, "IL");
+    }
+
+    #[test]
+    fn test_id_qualifier() {
+        let content = "N1*ST*DURAND SA*1*123456789~\nN4*LIBOURNE**33500*FR~";
+        let party: X12_N1 = content.parse().expect("error");
+        assert_eq!(party.id_qualifier, "1");
+        assert_eq!(party.to_string(), content);
+    }
+
+    #[test]
+    fn test_to_string() {
+        let address = Address {
+            name: "Monsieur Jean DURAND".to_string().into(),
+            street_name: "25D RUE DES FLEURS".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let party = X12_N1::try_from(address).expect("error");
+        assert_eq!(
+            party.to_string(),
+            "N1*ST*Monsieur Jean DURAND~\nN3*25D RUE DES FLEURS~\nN4*LIBOURNE**33500*FR~"
+        );
+        assert_eq!(party.to_string().parse::<X12_N1>().expect("error"), party);
+    }
+
+    #[test]
+    fn test_too_many_lines() {
+        let address = Address {
+            room: "Chez Mireille COPEAU Appartement 2".to_string().into(),
+            floor: "Entrée A Bâtiment Jonquille".to_string().into(),
+            street_name: "25 RUE DE L’EGLISE".to_string().into(),
+            post_box: "BP 12".to_string().into(),
+            town_location_name: "CAUDOS".to_string().into(),
+            post_code: "33380".to_string(),
+            town_name: "MIOS".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+        assert!(X12_N1::try_from(address.clone()).is_err());
+
+        let address = Address {
+            post_box: None,
+            ..address
+        };
+        let party = X12_N1::try_from(address).expect("error");
+        assert_eq!(party.to_string().matches("N3*").count(), 2);
+    }
+
+    #[test]
+    fn test_validate() {
+        let result = X12_N1 {
+            entity: "ST".to_string(),
+            state: "GIRONDE".to_string(),
+            ..Default::default()
+        };
+
+        assert!(result.validate().is_err());
+
+        let result = X12_N1 {
+            entity: "ST".to_string(),
+            address: vec!["25 RUE DES FLEURS~N1*BT".to_string()],
+            ..Default::default()
+        };
+        assert!(result.validate().is_err());
+    }
+
+    #[test]
+    fn test_separators() {
+        let address = Address {
+            name: "DURAND*FILS".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        let error = X12_N1::try_from(address).expect_err("`*` is a separator");
+        assert_eq!(
+            error.to_string(),
+            "The field `N102` must not contain `*` or `~`"
+        );
+    }
+}
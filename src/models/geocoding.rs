@@ -0,0 +1,676 @@
+use std::collections::{BTreeSet, HashMap};
+use std::f64::consts::SQRT_2;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::common::Result;
+
+use super::address::{Address, Coordinates};
+use super::laposte::normalize;
+
+/// Directory of the local index built from a Base Adresse Nationale extract
+pub const INDEX_PATH: &str = "ban_index";
+/// Post codes of each cell of the spatial grid, in the index directory
+const GRID_FILE: &str = "grid.csv";
+/// Post codes of each commune, in the index directory
+const TOWNS_FILE: &str = "towns.csv";
+
+/// Columns of the BAN CSV kept in the index
+const INDEX_COLUMNS: [&str; 8] = [
+    "numero",
+    "rep",
+    "nom_voie",
+    "nom_ld",
+    "code_postal",
+    "nom_commune",
+    "lon",
+    "lat",
+];
+
+/// Street and locality names closer than this are not considered a match
+const MIN_SIMILARITY: f64 = 0.5;
+
+/// Mean radius of the Earth, in meters
+const EARTH_RADIUS: f64 = 6_371_000.0;
+/// Side of the cells of the spatial grid, in degrees (about 1 km)
+const CELL_SIZE: f64 = 0.01;
+
+/// Abbreviations of street types, as written on mail
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("ALL", "ALLEE"),
+    ("AV", "AVENUE"),
+    ("BD", "BOULEVARD"),
+    ("CHE", "CHEMIN"),
+    ("CRS", "COURS"),
+    ("FG", "FAUBOURG"),
+    ("IMP", "IMPASSE"),
+    ("PL", "PLACE"),
+    ("QU", "QUAI"),
+    ("RTE", "ROUTE"),
+    ("SQ", "SQUARE"),
+];
+
+/// Most precise level reached by the geocoder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchLevel {
+    /// The building number was found in the street
+    HouseNumber,
+    /// Middle of the numbers known in the street
+    Street,
+    /// Middle of a lieu-dit
+    Locality,
+    /// Middle of the commune
+    Municipality,
+}
+
+/// Position found for an address
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GeocodeResult {
+    pub coordinates: Coordinates,
+    /// From 0 to 1
+    pub score: f64,
+    pub level: MatchLevel,
+}
+
+/// An address point of the BAN
+#[derive(Debug, Clone, PartialEq)]
+pub struct BanEntry {
+    pub number: String,
+    /// Suffix of the number, e.g. `bis` or `a`
+    pub rep: String,
+    pub street: String,
+    /// Lieu-dit, if any
+    pub locality: String,
+    pub post_code: String,
+    pub town: String,
+    pub coordinates: Coordinates,
+}
+
+impl BanEntry {
+    /// Returns the number with its suffix, e.g. `25D` or `12 BIS`
+    pub fn building_number(&self) -> String {
+        let rep = self.rep.to_uppercase();
+        match rep.chars().count() {
+            0 | 1 => format!("{}{rep}", self.number),
+            _ => format!("{} {rep}", self.number),
+        }
+    }
+
+    /// Returns the address of the point
+    pub fn address(&self) -> Address {
+        let optional = |x: &str| Some(x).filter(|x| !x.is_empty()).map(|x| x.to_string());
+
+        Address {
+            building_number: optional(&self.building_number()),
+            street_name: optional(&self.street),
+            town_location_name: optional(&self.locality),
+            post_code: self.post_code.clone(),
+            town_name: self.town.clone(),
+            country: "FR".to_string(),
+            coordinates: Some(self.coordinates),
+            ..Default::default()
+        }
+    }
+}
+
+/// A cell of the spatial grid, as latitude and longitude indices
+type Cell = (i32, i32);
+
+/// Offline geocoder over the address points of the Base Adresse Nationale
+#[derive(Debug, Clone, Default)]
+pub struct Geocoder {
+    entries: Vec<BanEntry>,
+    /// Entries of each post code
+    post_codes: HashMap<String, Vec<usize>>,
+    /// Entries of each cell of the spatial grid
+    grid: HashMap<Cell, Vec<usize>>,
+}
+
+/// Local geocoding index, saved in a directory by [`GeocodingIndex::build`]
+///
+/// The address points are split in a CSV file per post code, so that only the post
+/// codes of an address, or the ones around a position, are read.
+#[derive(Debug, Clone)]
+pub struct GeocodingIndex {
+    path: PathBuf,
+    /// Post codes of each cell of the spatial grid
+    grid: HashMap<Cell, Vec<String>>,
+    /// Post codes of each normalized commune name
+    towns: HashMap<String, Vec<String>>,
+}
+
+/// Uppercases a street name, removes accents and expands street type abbreviations
+fn normalize_street(street: &str) -> String {
+    normalize(street)
+        .split_whitespace()
+        .map(|x| {
+            ABBREVIATIONS
+                .iter()
+                .find(|(short, _)| *short == x)
+                .map_or(x, |(_, long)| long)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Writes a building number without spaces, e.g. `25 BIS` becomes `25BIS`
+fn normalize_number(number: &str) -> String {
+    number.split_whitespace().collect::<String>().to_uppercase()
+}
+
+/// Dice coefficient of the words of two normalized names
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<&str> = a.split_whitespace().collect();
+    let b: Vec<&str> = b.split_whitespace().collect();
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let common = a.iter().filter(|x| b.contains(x)).count();
+    2.0 * common as f64 / (a.len() + b.len()) as f64
+}
+
+/// Great-circle distance between two positions, in meters
+pub fn distance(a: Coordinates, b: Coordinates) -> f64 {
+    let (lat_a, lat_b) = (a.lat.to_radians(), b.lat.to_radians());
+    let d_lat = lat_b - lat_a;
+    let d_lon = (b.lon - a.lon).to_radians();
+
+    let h = (d_lat / 2.0).sin().powi(2) + lat_a.cos() * lat_b.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS * h.sqrt().asin()
+}
+
+/// Returns the grid cell of a position
+fn cell(coordinates: Coordinates) -> Cell {
+    (
+        (coordinates.lat / CELL_SIZE).floor() as i32,
+        (coordinates.lon / CELL_SIZE).floor() as i32,
+    )
+}
+
+/// Returns the cells of a grid that may hold the point closest to a position
+///
+/// The closest point of the nearest non-empty ring of cells is at most `ring + 1`
+/// cells away on each axis, so farther cells are only searched while they can hold
+/// a closer point, cells being narrower in longitude than in latitude.
+fn search_cells<T>(grid: &HashMap<Cell, T>, coordinates: Coordinates) -> Vec<Cell> {
+    let center = cell(coordinates);
+    let ring = |x: &Cell| (x.0 - center.0).abs().max((x.1 - center.1).abs());
+    let Some(nearest) = grid.keys().map(ring).min() else {
+        return Vec::new();
+    };
+
+    let cos = coordinates.lat.to_radians().cos().max(0.01);
+    let last = ((nearest + 1) as f64 * SQRT_2 / cos).ceil() as i32 + 1;
+    grid.keys().filter(|x| ring(x) <= last).copied().collect()
+}
+
+/// Middle of a set of entries
+fn centroid(entries: &[&BanEntry]) -> Coordinates {
+    let count = entries.len().max(1) as f64;
+    Coordinates {
+        lat: entries.iter().map(|x| x.coordinates.lat).sum::<f64>() / count,
+        lon: entries.iter().map(|x| x.coordinates.lon).sum::<f64>() / count,
+    }
+}
+
+impl Geocoder {
+    /// Parses a BAN CSV (`adresses-XX.csv`), separated by `;`
+    ///
+    /// Columns are found by their header name: `numero`, `rep`, `nom_voie`, `nom_ld`,
+    /// `code_postal`, `nom_commune`, `lon` and `lat`. Lines without coordinates are
+    /// skipped.
+    pub fn from_csv(content: &str) -> Result<Self> {
+        let mut lines = content.lines();
+        let header: Vec<&str> = lines
+            .next()
+            .ok_or("The BAN extract is empty")?
+            .split(';')
+            .collect();
+        let column = |name: &str| {
+            header
+                .iter()
+                .position(|x| *x == name)
+                .ok_or(format!("The column `{name}` is missing"))
+        };
+        let columns = INDEX_COLUMNS
+            .iter()
+            .map(|x| column(x))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut res = Geocoder::default();
+        for line in lines.filter(|x| !x.trim().is_empty()) {
+            let values: Vec<&str> = line.split(';').collect();
+            let value = |i: usize| values.get(columns[i]).copied().unwrap_or_default().trim();
+
+            let (Ok(lon), Ok(lat)) = (value(6).parse(), value(7).parse()) else {
+                continue;
+            };
+            res.push(BanEntry {
+                number: value(0).to_string(),
+                rep: value(1).to_string(),
+                street: value(2).to_string(),
+                locality: value(3).to_string(),
+                post_code: value(4).to_string(),
+                town: value(5).to_string(),
+                coordinates: Coordinates { lat, lon },
+            });
+        }
+
+        Ok(res)
+    }
+
+    /// Adds an address point
+    fn push(&mut self, entry: BanEntry) {
+        let i = self.entries.len();
+        self.post_codes
+            .entry(entry.post_code.clone())
+            .or_default()
+            .push(i);
+        self.grid
+            .entry(cell(entry.coordinates))
+            .or_default()
+            .push(i);
+        self.entries.push(entry);
+    }
+
+    /// Returns the address points of the index
+    pub fn entries(&self) -> &[BanEntry] {
+        &self.entries
+    }
+
+    /// Finds the position of an address, as precisely as the index allows
+    ///
+    /// The commune is found from the post code, or from the town name. The street is
+    /// then matched by name, and the building number in that street. When the street
+    /// is unknown, the `town_location_name` is matched against the lieux-dits.
+    pub fn geocode(&self, address: &Address) -> Result<GeocodeResult> {
+        let town = normalize(&address.town_name);
+
+        let mut candidates: Vec<&BanEntry> = self
+            .post_codes
+            .get(address.post_code.trim())
+            .into_iter()
+            .flatten()
+            .map(|i| &self.entries[*i])
+            .collect();
+        if candidates.is_empty() {
+            candidates = self
+                .entries
+                .iter()
+                .filter(|x| normalize(&x.town) == town)
+                .collect();
+        }
+        // A post code may serve several communes
+        let town_found = candidates.iter().any(|x| normalize(&x.town) == town);
+        if town_found {
+            candidates.retain(|x| normalize(&x.town) == town);
+        }
+        if candidates.is_empty() {
+            return Err(format!(
+                "`{} {}` is not in the geocoding index",
+                address.post_code, address.town_name
+            )
+            .into());
+        }
+
+        let (mut number, mut street) = (
+            address.building_number.clone().unwrap_or_default(),
+            address.street_name.clone().unwrap_or_default(),
+        );
+        // `25D RUE DES FLEURS`
+        if number.is_empty() {
+            if let Some((first, rest)) = street.split_once(' ') {
+                if first.starts_with(|c: char| c.is_ascii_digit()) {
+                    (number, street) = (first.to_string(), rest.to_string());
+                }
+            }
+        }
+
+        if let Some((name, score)) = best_match(&candidates, &street, |x| &x.street) {
+            let points: Vec<&BanEntry> = candidates
+                .iter()
+                .filter(|x| x.street == name)
+                .copied()
+                .collect();
+            let number = normalize_number(&number);
+            let found = points
+                .iter()
+                .find(|x| !number.is_empty() && normalize_number(&x.building_number()) == number);
+
+            return Ok(match found {
+                Some(point) => GeocodeResult {
+                    coordinates: point.coordinates,
+                    score,
+                    level: MatchLevel::HouseNumber,
+                },
+                None => GeocodeResult {
+                    coordinates: centroid(&points),
+                    score: 0.8 * score,
+                    level: MatchLevel::Street,
+                },
+            });
+        }
+
+        let locality = address.town_location_name.as_deref().unwrap_or_default();
+        if let Some((name, score)) = best_match(&candidates, locality, |x| &x.locality) {
+            let points: Vec<&BanEntry> = candidates
+                .iter()
+                .filter(|x| x.locality == name)
+                .copied()
+                .collect();
+            return Ok(GeocodeResult {
+                coordinates: centroid(&points),
+                score: 0.6 * score,
+                level: MatchLevel::Locality,
+            });
+        }
+
+        Ok(GeocodeResult {
+            coordinates: centroid(&candidates),
+            score: if town_found { 0.4 } else { 0.2 },
+            level: MatchLevel::Municipality,
+        })
+    }
+
+    /// Finds the address point closest to a position, with its distance in meters
+    ///
+    /// Only the points of the grid cells around the position are compared.
+    pub fn reverse(&self, coordinates: Coordinates) -> Option<(Address, f64)> {
+        search_cells(&self.grid, coordinates)
+            .iter()
+            .flat_map(|x| &self.grid[x])
+            .map(|i| &self.entries[*i])
+            .map(|x| (x, distance(coordinates, x.coordinates)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(entry, distance)| (entry.address(), distance))
+    }
+}
+
+impl GeocodingIndex {
+    /// Opens the index of a directory, reading its grid and communes only
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if !path.join(GRID_FILE).exists() {
+            return Err(
+                "No geocoding index, build it with `address geocode-index --file <BAN CSV>`".into(),
+            );
+        }
+
+        let mut res = GeocodingIndex {
+            grid: HashMap::new(),
+            towns: HashMap::new(),
+            path,
+        };
+        for line in fs::read_to_string(res.path.join(GRID_FILE))?
+            .lines()
+            .skip(1)
+        {
+            if let [lat, lon, post_code] = line.split(';').collect::<Vec<_>>()[..] {
+                res.grid
+                    .entry((lat.parse()?, lon.parse()?))
+                    .or_default()
+                    .push(post_code.to_string());
+            }
+        }
+        for line in fs::read_to_string(res.path.join(TOWNS_FILE))?
+            .lines()
+            .skip(1)
+        {
+            if let Some((town, post_code)) = line.split_once(';') {
+                res.towns
+                    .entry(town.to_string())
+                    .or_default()
+                    .push(post_code.to_string());
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Indexes a BAN CSV in a directory, replacing the previous index
+    ///
+    /// The columns used by the geocoder are saved in a file per post code, with the
+    /// post codes of each grid cell and of each commune. Points without a valid post
+    /// code are not indexed.
+    pub fn build(path: impl AsRef<Path>, content: &str) -> Result<usize> {
+        let path = path.as_ref();
+        let geocoder = Geocoder::from_csv(content)?;
+
+        if path.exists() {
+            fs::remove_dir_all(path)?;
+        }
+        fs::create_dir_all(path)?;
+
+        let mut towns = BTreeSet::new();
+        let mut count = 0;
+        for (post_code, entries) in &geocoder.post_codes {
+            let Ok(file) = Self::file(path, post_code) else {
+                continue;
+            };
+            count += entries.len();
+            let mut lines = vec![INDEX_COLUMNS.join(";")];
+            for entry in entries.iter().map(|i| &geocoder.entries[*i]) {
+                towns.insert(format!("{};{post_code}", normalize(&entry.town)));
+                lines.push(
+                    [
+                        entry.number.as_str(),
+                        &entry.rep,
+                        &entry.street,
+                        &entry.locality,
+                        &entry.post_code,
+                        &entry.town,
+                        &entry.coordinates.lon.to_string(),
+                        &entry.coordinates.lat.to_string(),
+                    ]
+                    .join(";"),
+                );
+            }
+            fs::write(file, lines.join("\n"))?;
+        }
+
+        let mut grid = BTreeSet::new();
+        for (cell, entries) in &geocoder.grid {
+            for entry in entries.iter().map(|i| &geocoder.entries[*i]) {
+                if Self::file(path, &entry.post_code).is_err() {
+                    continue;
+                }
+                grid.insert(format!("{};{};{}", cell.0, cell.1, entry.post_code));
+            }
+        }
+
+        let mut lines = vec!["lat;lon;code_postal".to_string()];
+        lines.extend(grid);
+        fs::write(path.join(GRID_FILE), lines.join("\n"))?;
+        let mut lines = vec!["nom_commune;code_postal".to_string()];
+        lines.extend(towns);
+        fs::write(path.join(TOWNS_FILE), lines.join("\n"))?;
+
+        Ok(count)
+    }
+
+    /// Returns the file of the address points of a post code
+    fn file(path: &Path, post_code: &str) -> Result<PathBuf> {
+        match !post_code.is_empty() && post_code.chars().all(|c| c.is_ascii_alphanumeric()) {
+            true => Ok(path.join(format!("{post_code}.csv"))),
+            false => Err(format!("Invalid post code `{post_code}` in the BAN extract").into()),
+        }
+    }
+
+    /// Reads the address points of some post codes
+    fn load<'a>(&self, post_codes: impl IntoIterator<Item = &'a String>) -> Result<Geocoder> {
+        let mut res = Geocoder::default();
+        for post_code in post_codes.into_iter().collect::<BTreeSet<_>>() {
+            let file = Self::file(&self.path, post_code)?;
+            if file.exists() {
+                for entry in Geocoder::from_csv(&fs::read_to_string(file)?)?.entries {
+                    res.push(entry);
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    /// Finds the position of an address, see [`Geocoder::geocode`]
+    ///
+    /// Only the points of the post code are read, or the ones of the communes named
+    /// as the town when the post code is unknown.
+    pub fn geocode(&self, address: &Address) -> Result<GeocodeResult> {
+        let post_code = address.post_code.trim().to_string();
+        let known = Self::file(&self.path, &post_code).is_ok_and(|x| x.exists());
+        let post_codes = match known {
+            true => vec![post_code],
+            false => self
+                .towns
+                .get(&normalize(&address.town_name))
+                .cloned()
+                .unwrap_or_default(),
+        };
+        self.load(&post_codes)?.geocode(address)
+    }
+
+    /// Finds the address point closest to a position, see [`Geocoder::reverse`]
+    ///
+    /// Only the post codes of the grid cells around the position are read.
+    pub fn reverse(&self, coordinates: Coordinates) -> Result<Option<(Address, f64)>> {
+        let cells = search_cells(&self.grid, coordinates);
+        let geocoder = self.load(cells.iter().flat_map(|x| &self.grid[x]))?;
+        Ok(geocoder.reverse(coordinates))
+    }
+}
+
+/// Finds the name closest to `name` among the entries, with its similarity
+fn best_match(
+    entries: &[&BanEntry],
+    name: &str,
+    field: impl Fn(&BanEntry) -> &String,
+) -> Option<(String, f64)> {
+    let name = normalize_street(name);
+
+    entries
+        .iter()
+        .map(|x| field(x))
+        .filter(|x| !x.is_empty())
+        .map(|x| (x, similarity(&name, &normalize_street(x))))
+        .filter(|(_, score)| *score >= MIN_SIMILARITY)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(x, score)| (x.clone(), score))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXTRACT: &str = "\
+id;id_fantoir;numero;rep;nom_voie;code_postal;code_insee;nom_commune;code_insee_ancienne_commune;nom_ancienne_commune;x;y;lon;lat;type_position;alias;nom_ld;libelle_acheminement;nom_afnor
+33243_0560_00025;33243_0560;25;d;Rue des Fleurs;33500;33243;Libourne;;;;;-0.2421;44.9153;entrée;;;LIBOURNE;RUE DES FLEURS
+33243_0560_00027;33243_0560;27;;Rue des Fleurs;33500;33243;Libourne;;;;;-0.2425;44.9155;entrée;;;LIBOURNE;RUE DES FLEURS
+33243_0120_00003;33243_0120;3;;Avenue de l'Épinette;33500;33243;Libourne;;;;;-0.2300;44.9250;entrée;;;LIBOURNE;AVENUE DE L EPINETTE
+33284_b001_00001;33284_b001;1;;Le Bourg;33380;33284;Mios;;;;;-0.9375;44.6051;entrée;;Le Bourg;MIOS;LE BOURG
+33284_b002_00012;33284_b002;12;;Route de Lacanau;33380;33284;Mios;;;;;-0.9101;44.6200;entrée;;Caudos;MIOS;ROUTE DE LACANAU
+";
+
+    fn address(street: &str, number: &str, post_code: &str, town: &str) -> Address {
+        let optional = |x: &str| Some(x).filter(|x| !x.is_empty()).map(|x| x.to_string());
+        Address {
+            street_name: optional(street),
+            building_number: optional(number),
+            post_code: post_code.to_string(),
+            town_name: town.to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_geocode() {
+        let geocoder = Geocoder::from_csv(EXTRACT).expect("Cannot read BAN!");
+        assert_eq!(geocoder.entries().len(), 5);
+
+        let result = geocoder
+            .geocode(&address("RUE DES FLEURS", "25D", "33500", "LIBOURNE"))
+            .expect("error");
+        assert_eq!(result.level, MatchLevel::HouseNumber);
+        assert_eq!(result.coordinates.lat, 44.9153);
+        assert_eq!(result.score, 1.0);
+
+        let result = geocoder
+            .geocode(&address("AV DE L EPINETTE", "", "33500", "Libourne"))
+            .expect("error");
+        assert_eq!(result.level, MatchLevel::Street);
+
+        let result = geocoder
+            .geocode(&address("", "", "33500", "LIBOURNE"))
+            .expect("error");
+        assert_eq!(result.level, MatchLevel::Municipality);
+
+        assert!(geocoder
+            .geocode(&address("", "", "75002", "PARIS"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_geocode_locality() {
+        let geocoder = Geocoder::from_csv(EXTRACT).expect("Cannot read BAN!");
+
+        let mut caudos = address("", "", "33380", "MIOS");
+        caudos.town_location_name = Some("CAUDOS".to_string());
+        let result = geocoder.geocode(&caudos).expect("error");
+        assert_eq!(result.level, MatchLevel::Locality);
+        assert_eq!(result.coordinates.lon, -0.9101);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let geocoder = Geocoder::from_csv(EXTRACT).expect("Cannot read BAN!");
+
+        let (address, distance) = geocoder
+            .reverse(Coordinates {
+                lat: 44.9154,
+                lon: -0.2422,
+            })
+            .expect("error");
+        assert_eq!(address.building_number.as_deref(), Some("25D"));
+        assert_eq!(address.street_name.as_deref(), Some("Rue des Fleurs"));
+        assert!(distance < 20.0);
+
+        let (address, _) = geocoder
+            .reverse(Coordinates {
+                lat: 44.62,
+                lon: -0.91,
+            })
+            .expect("error");
+        assert_eq!(address.town_location_name.as_deref(), Some("Caudos"));
+    }
+
+    #[test]
+    fn test_index() {
+        let path = std::env::temp_dir().join(format!("ban_index_{}", std::process::id()));
+        assert_eq!(GeocodingIndex::build(&path, EXTRACT).expect("error"), 5);
+        let index = GeocodingIndex::open(&path).expect("error");
+
+        let result = index
+            .geocode(&address("RUE DES FLEURS", "25D", "33500", "LIBOURNE"))
+            .expect("error");
+        assert_eq!(result.level, MatchLevel::HouseNumber);
+        let result = index
+            .geocode(&address("ROUTE DE LACANAU", "12", "33000", "Mios"))
+            .expect("error");
+        assert_eq!(result.coordinates.lon, -0.9101);
+        assert!(index.geocode(&address("", "", "75002", "PARIS")).is_err());
+
+        let (address, _) = index
+            .reverse(Coordinates {
+                lat: 44.9154,
+                lon: -0.2422,
+            })
+            .expect("error")
+            .expect("error");
+        assert_eq!(address.building_number.as_deref(), Some("25D"));
+
+        fs::remove_dir_all(&path).expect("error");
+        assert!(GeocodingIndex::open(&path).is_err());
+    }
+}
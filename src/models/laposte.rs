@@ -0,0 +1,455 @@
+use std::fs;
+use std::path::Path;
+
+use crate::common::Result;
+
+use super::address::Address;
+
+/// Excerpt of the La Poste "base officielle des codes postaux" covering a few large
+/// cities, without INSEE codes, replaced by the full file once imported
+const BUILTIN_DATASET: &str = include_str!("laposte_hexasmal.csv");
+
+/// Local copy of the dataset, written by [`PostcodeDatabase::import`]
+pub const FILE_PATH: &str = "laposte_hexasmal.csv";
+
+/// French départements, by code
+const DEPARTEMENTS: &[(&str, &str)] = &[
+    ("01", "Ain"),
+    ("02", "Aisne"),
+    ("03", "Allier"),
+    ("04", "Alpes-de-Haute-Provence"),
+    ("05", "Hautes-Alpes"),
+    ("06", "Alpes-Maritimes"),
+    ("07", "Ardèche"),
+    ("08", "Ardennes"),
+    ("09", "Ariège"),
+    ("10", "Aube"),
+    ("11", "Aude"),
+    ("12", "Aveyron"),
+    ("13", "Bouches-du-Rhône"),
+    ("14", "Calvados"),
+    ("15", "Cantal"),
+    ("16", "Charente"),
+    ("17", "Charente-Maritime"),
+    ("18", "Cher"),
+    ("19", "Corrèze"),
+    ("2A", "Corse-du-Sud"),
+    ("2B", "Haute-Corse"),
+    ("21", "Côte-d'Or"),
+    ("22", "Côtes-d'Armor"),
+    ("23", "Creuse"),
+    ("24", "Dordogne"),
+    ("25", "Doubs"),
+    ("26", "Drôme"),
+    ("27", "Eure"),
+    ("28", "Eure-et-Loir"),
+    ("29", "Finistère"),
+    ("30", "Gard"),
+    ("31", "Haute-Garonne"),
+    ("32", "Gers"),
+    ("33", "Gironde"),
+    ("34", "Hérault"),
+    ("35", "Ille-et-Vilaine"),
+    ("36", "Indre"),
+    ("37", "Indre-et-Loire"),
+    ("38", "Isère"),
+    ("39", "Jura"),
+    ("40", "Landes"),
+    ("41", "Loir-et-Cher"),
+    ("42", "Loire"),
+    ("43", "Haute-Loire"),
+    ("44", "Loire-Atlantique"),
+    ("45", "Loiret"),
+    ("46", "Lot"),
+    ("47", "Lot-et-Garonne"),
+    ("48", "Lozère"),
+    ("49", "Maine-et-Loire"),
+    ("50", "Manche"),
+    ("51", "Marne"),
+    ("52", "Haute-Marne"),
+    ("53", "Mayenne"),
+    ("54", "Meurthe-et-Moselle"),
+    ("55", "Meuse"),
+    ("56", "Morbihan"),
+    ("57", "Moselle"),
+    ("58", "Nièvre"),
+    ("59", "Nord"),
+    ("60", "Oise"),
+    ("61", "Orne"),
+    ("62", "Pas-de-Calais"),
+    ("63", "Puy-de-Dôme"),
+    ("64", "Pyrénées-Atlantiques"),
+    ("65", "Hautes-Pyrénées"),
+    ("66", "Pyrénées-Orientales"),
+    ("67", "Bas-Rhin"),
+    ("68", "Haut-Rhin"),
+    ("69", "Rhône"),
+    ("70", "Haute-Saône"),
+    ("71", "Saône-et-Loire"),
+    ("72", "Sarthe"),
+    ("73", "Savoie"),
+    ("74", "Haute-Savoie"),
+    ("75", "Paris"),
+    ("76", "Seine-Maritime"),
+    ("77", "Seine-et-Marne"),
+    ("78", "Yvelines"),
+    ("79", "Deux-Sèvres"),
+    ("80", "Somme"),
+    ("81", "Tarn"),
+    ("82", "Tarn-et-Garonne"),
+    ("83", "Var"),
+    ("84", "Vaucluse"),
+    ("85", "Vendée"),
+    ("86", "Vienne"),
+    ("87", "Haute-Vienne"),
+    ("88", "Vosges"),
+    ("89", "Yonne"),
+    ("90", "Territoire de Belfort"),
+    ("91", "Essonne"),
+    ("92", "Hauts-de-Seine"),
+    ("93", "Seine-Saint-Denis"),
+    ("94", "Val-de-Marne"),
+    ("95", "Val-d'Oise"),
+    ("971", "Guadeloupe"),
+    ("972", "Martinique"),
+    ("973", "Guyane"),
+    ("974", "La Réunion"),
+    ("976", "Mayotte"),
+];
+
+/// Returns the code of the département of a French post code
+///
+/// # Example
+///
+/// ```
+/// use address::models::laposte::departement_code;
+/// assert_eq!(departement_code("33380"), Some("33".to_string()));
+/// assert_eq!(departement_code("20200"), Some("2B".to_string()));
+/// assert_eq!(departement_code("97400"), Some("974".to_string()));
+/// ```
+pub fn departement_code(post_code: &str) -> Option<String> {
+    if post_code.len() != 5 || !post_code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(match &post_code[..2] {
+        "20" if post_code < "20200" => "2A".to_string(),
+        "20" => "2B".to_string(),
+        "97" | "98" => post_code[..3].to_string(),
+        code => code.to_string(),
+    })
+}
+
+/// Returns the name of the département of a French post code
+pub fn departement_name(post_code: &str) -> Option<&'static str> {
+    let code = departement_code(post_code)?;
+    DEPARTEMENTS
+        .iter()
+        .find(|(x, _)| *x == code)
+        .map(|(_, name)| *name)
+}
+
+/// Fills the `country_sub_division` of a French address with its département
+pub fn fill_departement(address: &mut Address) {
+    if address.country == "FR" && address.country_sub_division.is_none() {
+        address.country_sub_division = departement_name(&address.post_code).map(|x| x.to_string());
+    }
+}
+
+/// Uppercases a town name, removes accents and punctuation, and abbreviates `SAINT`
+pub(crate) fn normalize(town: &str) -> String {
+    let town: String = town
+        .to_uppercase()
+        .chars()
+        .map(|c| match c {
+            'À' | 'Â' | 'Ä' => 'A',
+            'Ç' => 'C',
+            'É' | 'È' | 'Ê' | 'Ë' => 'E',
+            'Î' | 'Ï' => 'I',
+            'Ô' | 'Ö' => 'O',
+            'Ù' | 'Û' | 'Ü' => 'U',
+            '-' | '\'' | '’' => ' ',
+            c => c,
+        })
+        .collect();
+
+    town.split_whitespace()
+        .map(|x| match x {
+            "SAINT" => "ST",
+            "SAINTE" => "STE",
+            x => x,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Removes a trailing `CEDEX` or `CEDEX 12` from a town name
+fn strip_cedex(town: &str) -> Option<&str> {
+    let town = town.trim();
+    let town = town
+        .trim_end_matches(|c: char| c.is_ascii_digit())
+        .trim_end();
+    let upper = town.to_uppercase();
+    upper
+        .ends_with(" CEDEX")
+        .then(|| town[..town.len() - " CEDEX".len()].trim_end())
+}
+
+/// A line of the La Poste dataset
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostcodeEntry {
+    /// INSEE code of the commune
+    pub insee: String,
+    /// Name of the commune
+    pub commune: String,
+    pub post_code: String,
+    /// Town name written on mail ("libellé d'acheminement")
+    pub label: String,
+    /// Locality inside the commune, if any
+    pub line_5: String,
+}
+
+/// Post codes and communes of France, from the La Poste "base officielle des codes
+/// postaux"
+#[derive(Debug, Clone, Default)]
+pub struct PostcodeDatabase {
+    entries: Vec<PostcodeEntry>,
+    /// Whether the entries come from the embedded excerpt
+    excerpt: bool,
+}
+
+impl PostcodeDatabase {
+    /// Loads the local copy of the dataset if it was imported, the embedded one otherwise
+    pub fn load() -> Result<Self> {
+        match Path::new(FILE_PATH).exists() {
+            true => Self::from_csv(&fs::read_to_string(FILE_PATH)?),
+            false => Ok(PostcodeDatabase {
+                excerpt: true,
+                ..Self::from_csv(BUILTIN_DATASET)?
+            }),
+        }
+    }
+
+    /// Parses the CSV published by La Poste, separated by `;` or `,`
+    ///
+    /// Columns: INSEE code, commune, post code, libellé d'acheminement, ligne 5, and
+    /// optionally the GPS coordinates.
+    pub fn from_csv(content: &str) -> Result<Self> {
+        let mut lines = content.lines();
+        let header = lines.next().ok_or("The dataset is empty")?;
+        let separator = if header.contains(';') { ';' } else { ',' };
+
+        let mut entries = Vec::new();
+        for (i, line) in lines.enumerate().filter(|(_, x)| !x.trim().is_empty()) {
+            let columns: Vec<&str> = line.split(separator).map(|x| x.trim_matches('"')).collect();
+            let [insee, commune, post_code, label, rest @ ..] = columns.as_slice() else {
+                return Err(format!("Line {} must have at least 4 columns", i + 2).into());
+            };
+            entries.push(PostcodeEntry {
+                insee: insee.to_string(),
+                commune: commune.to_string(),
+                post_code: post_code.to_string(),
+                label: label.to_string(),
+                line_5: rest.first().unwrap_or(&"").to_string(),
+            });
+        }
+
+        Ok(PostcodeDatabase {
+            entries,
+            excerpt: false,
+        })
+    }
+
+    /// Checks a CSV file and saves it as the local copy of the dataset
+    pub fn import(content: &str) -> Result<usize> {
+        let database = Self::from_csv(content)?;
+        fs::write(FILE_PATH, content)?;
+        Ok(database.entries.len())
+    }
+
+    /// Returns the entries of a post code
+    pub fn communes(&self, post_code: &str) -> Vec<&PostcodeEntry> {
+        self.entries
+            .iter()
+            .filter(|x| x.post_code == post_code)
+            .collect()
+    }
+
+    /// Returns the entries whose commune, libellé or locality is the given town
+    pub fn post_codes(&self, town: &str) -> Vec<&PostcodeEntry> {
+        let town = normalize(town);
+        self.entries
+            .iter()
+            .filter(|x| {
+                [&x.commune, &x.label, &x.line_5]
+                    .iter()
+                    .any(|name| !name.is_empty() && normalize(name) == town)
+            })
+            .collect()
+    }
+
+    /// Checks that the town of an address is served by its post code
+    ///
+    /// CEDEX post codes are not part of the dataset: a known town must then exist in the
+    /// département of the post code. On mismatch, the error lists the closest valid
+    /// `post code town` pairs. Post codes missing from the dataset are rejected too, and
+    /// so are CEDEX towns missing from the embedded excerpt, whose error asks to import
+    /// the full dataset. CEDEX labels that are not communes, like `PARIS LA DEFENSE`,
+    /// are accepted with the full dataset.
+    pub fn check(&self, address: &Address) -> Result<()> {
+        let post_code = address.post_code.trim();
+        let town = address.town_name.trim();
+
+        if let Some(town) = strip_cedex(town) {
+            let departement = departement_code(post_code);
+            let entries = self.post_codes(town);
+            if entries.is_empty() && self.excerpt {
+                return Err(self.not_covered(&format!("Town `{town}`")));
+            }
+            let known = entries.is_empty()
+                || entries
+                    .iter()
+                    .any(|x| departement_code(&x.post_code) == departement);
+            return match known {
+                true => Ok(()),
+                false => Err(self.mismatch(post_code, town)),
+            };
+        }
+
+        let communes = self.communes(post_code);
+        if communes.is_empty() {
+            return Err(self.not_covered(&format!("Post code `{post_code}`")));
+        }
+        let normalized = normalize(town);
+        let served = communes.iter().any(|x| {
+            [&x.commune, &x.label, &x.line_5]
+                .iter()
+                .any(|name| !name.is_empty() && normalize(name) == normalized)
+        });
+        match served {
+            true => Ok(()),
+            false => Err(self.mismatch(post_code, town)),
+        }
+    }
+
+    /// Suggests `(post code, town)` corrections: other post codes of the town, then
+    /// other towns of the post code
+    pub fn suggestions(&self, post_code: &str, town: &str) -> Vec<(String, String)> {
+        let mut res: Vec<(String, String)> = Vec::new();
+        let candidates = self
+            .post_codes(town)
+            .into_iter()
+            .chain(self.communes(post_code));
+
+        for entry in candidates {
+            let suggestion = (entry.post_code.clone(), entry.label.clone());
+            if !res.contains(&suggestion) {
+                res.push(suggestion);
+            }
+        }
+        res
+    }
+
+    fn not_covered(&self, what: &str) -> Box<dyn std::error::Error> {
+        match self.excerpt {
+            true => format!(
+                "{what} is not in the embedded excerpt of the La Poste dataset, import the full \
+                 dataset with `import-postcodes` to check it"
+            )
+            .into(),
+            false => format!("{what} is not in the La Poste dataset").into(),
+        }
+    }
+
+    fn mismatch(&self, post_code: &str, town: &str) -> Box<dyn std::error::Error> {
+        let suggestions = self
+            .suggestions(post_code, town)
+            .into_iter()
+            .map(|(code, town)| format!("`{code} {town}`"))
+            .collect::<Vec<_>>();
+
+        match suggestions.is_empty() {
+            true => format!("Unknown post code and town `{post_code} {town}`").into(),
+            false => format!(
+                "`{post_code} {town}` does not exist, did you mean {}?",
+                suggestions.join(", ")
+            )
+            .into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(post_code: &str, town_name: &str) -> Address {
+        Address {
+            post_code: post_code.to_string(),
+            town_name: town_name.to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let database = PostcodeDatabase::from_csv(BUILTIN_DATASET).expect("error");
+
+        assert!(database.check(&address("33380", "MIOS")).is_ok());
+        assert!(database.check(&address("06000", "Nice")).is_ok());
+        assert!(database.check(&address("93200", "Saint-Denis")).is_ok());
+
+        let error = database
+            .check(&address("93210", "SAINT-DENIS"))
+            .expect_err("93210 is not in the excerpt");
+        assert_eq!(
+            error.to_string(),
+            "Post code `93210` is not in the La Poste dataset"
+        );
+
+        let error = database
+            .check(&address("33500", "MIOS"))
+            .expect_err("MIOS is not in 33500");
+        assert!(error.to_string().contains("`33380 MIOS`"));
+        assert!(error.to_string().contains("`33500 LIBOURNE`"));
+    }
+
+    #[test]
+    fn test_check_cedex() {
+        let database = PostcodeDatabase::from_csv(BUILTIN_DATASET).expect("error");
+
+        assert!(database
+            .check(&address("34092", "MONTPELLIER CEDEX 5"))
+            .is_ok());
+        assert!(database.check(&address("33506", "LIBOURNE CEDEX")).is_ok());
+        assert!(database.check(&address("75506", "LIBOURNE CEDEX")).is_err());
+        assert!(database
+            .check(&address("92084", "PARIS LA DEFENSE CEDEX"))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_check_excerpt() {
+        let database = PostcodeDatabase {
+            excerpt: true,
+            ..PostcodeDatabase::from_csv(BUILTIN_DATASET).expect("error")
+        };
+
+        let error = database
+            .check(&address("59160", "LILLE"))
+            .expect_err("59160 is not in the excerpt");
+        assert!(error.to_string().contains("import-postcodes"));
+        assert!(database
+            .check(&address("92084", "PARIS LA DEFENSE CEDEX"))
+            .is_err());
+        assert!(database.check(&address("33506", "LIBOURNE CEDEX")).is_ok());
+    }
+
+    #[test]
+    fn test_fill_departement() {
+        let mut result = address("33380", "MIOS");
+        fill_departement(&mut result);
+        assert_eq!(result.country_sub_division.as_deref(), Some("Gironde"));
+    }
+}
@@ -1,5 +1,45 @@
 pub mod address;
+#[cfg(feature = "ctt")]
+pub mod address_ctt;
+#[cfg(feature = "din-5008")]
+pub mod address_din_5008;
+#[cfg(feature = "edifact")]
+pub mod address_edifact;
+#[cfg(feature = "fhir")]
+pub mod address_fhir;
+#[cfg(feature = "geojson")]
+pub mod address_geojson;
+#[cfg(feature = "iso20022")]
 pub mod address_iso_20022;
+#[cfg(feature = "nf-z10-011")]
 pub mod address_nf_z10_011_enterprise;
+#[cfg(feature = "nf-z10-011")]
 pub mod address_nf_z10_011_individual;
+#[cfg(feature = "osm")]
+pub mod address_osm;
+#[cfg(feature = "paf")]
+pub mod address_paf;
+#[cfg(feature = "repository")]
+pub mod address_patch;
+#[cfg(feature = "schema-org")]
+pub mod address_schema_org;
+#[cfg(feature = "swift-mt")]
+pub mod address_swift_mt;
+#[cfg(feature = "templates")]
+pub mod address_template;
+#[cfg(feature = "text")]
+pub mod address_text;
+#[cfg(feature = "ubl")]
+pub mod address_ubl;
+#[cfg(feature = "usps")]
+pub mod address_usps;
+#[cfg(feature = "vcard")]
+pub mod address_vcard;
+#[cfg(feature = "x12")]
+pub mod address_x12;
+#[cfg(feature = "geocoding")]
+pub mod geocoding;
+#[cfg(feature = "laposte")]
+pub mod laposte;
+pub mod nf_lines;
 pub mod validate;
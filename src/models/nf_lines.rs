@@ -0,0 +1,246 @@
+use super::address::Address;
+
+/// Street types ("types de voie") commonly found on French street lines
+pub const VOIE_TYPES: &[&str] = &[
+    "ALLEE",
+    "ALLÉE",
+    "AV",
+    "AVENUE",
+    "BD",
+    "BOULEVARD",
+    "CHE",
+    "CHEMIN",
+    "CHS",
+    "CHAUSSEE",
+    "CITE",
+    "CITÉ",
+    "CLOS",
+    "COURS",
+    "CRS",
+    "DOMAINE",
+    "ESPLANADE",
+    "FBG",
+    "FAUBOURG",
+    "HAMEAU",
+    "IMP",
+    "IMPASSE",
+    "LOT",
+    "LOTISSEMENT",
+    "MAIL",
+    "PASSAGE",
+    "PL",
+    "PLACE",
+    "PROMENADE",
+    "QUAI",
+    "QUARTIER",
+    "RES",
+    "RTE",
+    "ROUTE",
+    "RUE",
+    "RUELLE",
+    "SENTE",
+    "SENTIER",
+    "SQ",
+    "SQUARE",
+    "VILLA",
+    "VOIE",
+];
+
+/// Words announcing a delivery point inside a building (NF Z10-011 line 2)
+const ROOM_WORDS: &[&str] = &[
+    "APPARTEMENT",
+    "APPT",
+    "APT",
+    "CHEZ",
+    "C/O",
+    "ESCALIER",
+    "ETAGE",
+    "ÉTAGE",
+    "BOITE",
+    "BOÎTE",
+    "PORTE",
+    "CHAMBRE",
+    "BUREAU",
+];
+
+/// Words announcing a building or its access (NF Z10-011 line 3)
+const FLOOR_WORDS: &[&str] = &[
+    "ENTREE",
+    "ENTRÉE",
+    "BATIMENT",
+    "BÂTIMENT",
+    "BAT",
+    "BÂT",
+    "IMMEUBLE",
+    "RESIDENCE",
+    "RÉSIDENCE",
+    "TOUR",
+    "ZONE",
+    "ZI",
+    "ZA",
+    "ZAC",
+    "PARC",
+    "CENTRE",
+];
+
+/// Words announcing a special distribution service (NF Z10-011 line 5)
+const POST_BOX_WORDS: &[&str] = &["BP", "CS", "TSA", "CEDEX"];
+
+/// The kind of information carried by a free address line, following NF Z10-011 rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    /// Delivery point inside a building (line 2)
+    Room,
+    /// Building, entrance or residence (line 3)
+    Floor,
+    /// House number and street (line 4)
+    Street,
+    /// Post box or special distribution service (line 5)
+    PostBox,
+    /// Cannot be decided from the line alone
+    Unknown,
+}
+
+/// Guesses the kind of a free address line from its first word and leading number
+///
+/// # Example
+///
+/// ```
+/// use address::models::nf_lines::{classify_line, LineKind};
+/// assert_eq!(classify_line("25D RUE DES FLEURS"), LineKind::Street);
+/// assert_eq!(classify_line("Entrée A Bâtiment Jonquille"), LineKind::Floor);
+/// ```
+pub fn classify_line(line: &str) -> LineKind {
+    let upper = line.trim().to_uppercase();
+    let mut words = upper.split_whitespace();
+    let Some(first) = words.next() else {
+        return LineKind::Unknown;
+    };
+    let is = |list: &[&str], word: &str| list.contains(&word.trim_end_matches(['.', ',']));
+
+    if is(POST_BOX_WORDS, first) {
+        LineKind::PostBox
+    } else if is(ROOM_WORDS, first) {
+        LineKind::Room
+    } else if is(FLOOR_WORDS, first) {
+        LineKind::Floor
+    } else if first.starts_with(|c: char| c.is_ascii_digit()) || is(VOIE_TYPES, first) {
+        LineKind::Street
+    } else if words.next().is_some_and(|second| is(VOIE_TYPES, second)) {
+        // e.g. "25 BIS RUE ...", "BIS" is not a number but the street type follows
+        LineKind::Street
+    } else {
+        LineKind::Unknown
+    }
+}
+
+/// Fills `room`, `floor`, `street_name`, `post_box` and `town_location_name` of an
+/// `Address` from free address lines, as found between the name and the town line
+///
+/// Lines are classified with [`classify_line`]. Unknown lines above the street are
+/// treated as building information (closest first), unknown lines below the street
+/// as a locality (lieu-dit), as in NF Z10-011.
+pub fn assign_lines(lines: &[String], address: &mut Address) {
+    let lines: Vec<&str> = lines
+        .iter()
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .collect();
+    let kinds: Vec<LineKind> = lines.iter().map(|x| classify_line(x)).collect();
+
+    let street = kinds
+        .iter()
+        .rposition(|x| *x == LineKind::Street)
+        .or_else(|| kinds.iter().rposition(|x| *x == LineKind::Unknown));
+
+    let set = |field: &mut Option<String>, line: &str| match field {
+        Some(value) => {
+            value.push_str(", ");
+            value.push_str(line);
+        }
+        None => *field = Some(line.to_string()),
+    };
+
+    let mut above = Vec::new();
+    for (i, (line, kind)) in lines.iter().zip(&kinds).enumerate() {
+        match kind {
+            LineKind::Room => set(&mut address.room, line),
+            LineKind::Floor => set(&mut address.floor, line),
+            LineKind::PostBox => set(&mut address.post_box, line),
+            _ if Some(i) == street => set(&mut address.street_name, line),
+            _ if street.is_some_and(|street| i > street) => {
+                set(&mut address.town_location_name, line)
+            }
+            _ => above.push(*line),
+        }
+    }
+
+    for (i, line) in above.into_iter().rev().enumerate() {
+        if i == 0 && address.floor.is_none() {
+            address.floor = Some(line.to_string());
+        } else {
+            set(&mut address.room, line);
+        }
+    }
+}
+
+/// Builds the free address lines of an `Address`, in NF Z10-011 order
+pub fn address_lines(address: &Address) -> Vec<String> {
+    let street = [&address.building_number, &address.street_name]
+        .into_iter()
+        .flatten()
+        .filter(|x| !x.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    [
+        address.room.clone(),
+        address.floor.clone(),
+        Some(street),
+        address.post_box.clone(),
+        address.town_location_name.clone(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|x| !x.is_empty())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_line() {
+        assert_eq!(classify_line("25 RUE DE L’EGLISE"), LineKind::Street);
+        assert_eq!(classify_line("VILLA BEAU SOLEIL"), LineKind::Street);
+        assert_eq!(classify_line("Chez Mireille COPEAU"), LineKind::Room);
+        assert_eq!(classify_line("Résidence des Capucins"), LineKind::Floor);
+        assert_eq!(classify_line("BP 40122"), LineKind::PostBox);
+        assert_eq!(classify_line("CAUDOS"), LineKind::Unknown);
+    }
+
+    #[test]
+    fn test_assign_lines() {
+        let lines = [
+            "Chez Mireille COPEAU Appartement 2".to_string(),
+            "Entrée A Bâtiment Jonquille".to_string(),
+            "25 RUE DE L’EGLISE".to_string(),
+            "CAUDOS".to_string(),
+        ];
+
+        let result = Address {
+            room: "Chez Mireille COPEAU Appartement 2".to_string().into(),
+            floor: "Entrée A Bâtiment Jonquille".to_string().into(),
+            street_name: "25 RUE DE L’EGLISE".to_string().into(),
+            town_location_name: "CAUDOS".to_string().into(),
+            ..Default::default()
+        };
+
+        let mut address = Address::default();
+        assign_lines(&lines, &mut address);
+        assert_eq!(address, result);
+        assert_eq!(address_lines(&address), lines);
+    }
+}
@@ -1,18 +1,43 @@
+use address::models::address_ctt::CTT_Address;
+use address::models::address_din_5008::DIN_5008;
+use address::models::address_edifact::EDIFACT_NAD;
+use address::models::address_fhir::FHIR_Address;
+use address::models::address_geojson::{GeoJSON_Feature, GeoJSON_FeatureCollection};
 use address::models::address_nf_z10_011_enterprise::NF_Z10_011_Enterprise;
+use address::models::address_osm::OSM_Address;
+use address::models::address_paf::PAF_Address;
+use address::models::address_patch::AddressPatch;
+use address::models::address_schema_org::SchemaOrg_PostalAddress;
+use address::models::address_swift_mt::SWIFT_MT_Party;
+use address::models::address_template::TemplateRegistry;
+use address::models::address_text::{to_text, TextCandidate};
+use address::models::address_ubl::UBL_PostalAddress;
+use address::models::address_usps::USPS_Address;
+use address::models::address_vcard::VCard_ADR;
+use address::models::address_x12::X12_N1;
+use address::models::geocoding::{GeocodingIndex, INDEX_PATH};
+use address::models::laposte::{fill_departement, PostcodeDatabase};
 use address::models::validate::Validate;
+use address::render::merge::{MergeTemplate, Syntax};
+use address::render::{layout, pdf::to_pdf, svg::to_svg, FontFamily, RenderOptions, Sheet};
 use address::{
     models::{
-        address::Address, address_iso_20022::ISO_20022,
+        address::{Address, Coordinates},
+        address_iso_20022::ISO_20022,
         address_nf_z10_011_individual::NF_Z10_011_Individual,
     },
     repositories::{address_repository::AddressRepository, json_repository::JsonFileRepository},
 };
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use quick_xml::se::Serializer;
 use serde::Serialize;
 use std::error::Error;
 use std::fs;
 use uuid::Uuid;
+
+mod shell;
+mod tui;
+
 /// CLI for managing postal addresses
 #[derive(Parser)]
 #[command(name = "address-cli")]
@@ -90,8 +115,16 @@ enum Commands {
         country_sub_division: Option<String>,
         #[clap(long)]
         country: Option<String>,
+        /// Clear a field, can be repeated
+        #[clap(long, value_name = "FIELD")]
+        clear: Vec<String>,
+        /// JSON Merge Patch object or JSON Patch array, applied before the other options
+        #[clap(long, value_name = "FILE")]
+        patch: Option<String>,
         #[clap(long)]
         format: Option<Format>,
+        #[command(flatten)]
+        templates: TemplateArgs,
     },
     /// Add a Address from an existing file
     Save {
@@ -103,17 +136,126 @@ enum Commands {
         validate: bool,
         #[clap(long, action)]
         enterprise: bool,
+        #[command(flatten)]
+        templates: TemplateArgs,
+    },
+    /// Save every Address found in an existing file
+    Import {
+        #[clap(long)]
+        file: String,
+        #[clap(long)]
+        from: Format,
+        #[command(flatten)]
+        templates: TemplateArgs,
+    },
+    /// Check the post code and town of a French address against the La Poste dataset
+    Check {
+        id: Uuid,
+        /// Save the address with its département filled from the post code
+        #[clap(long, action)]
+        fill: bool,
+    },
+    /// Replace the La Poste post code dataset with a newer CSV export
+    ImportPostcodes {
+        #[clap(long)]
+        file: String,
+    },
+    /// Build the geocoding index from a Base Adresse Nationale CSV extract
+    GeocodeIndex {
+        #[clap(long)]
+        file: String,
+    },
+    /// Find the position of a saved address in the geocoding index
+    Geocode {
+        id: Uuid,
+        /// Save the coordinates with the address
+        #[clap(long, action)]
+        save: bool,
+    },
+    /// Find the address closest to a position in the geocoding index
+    Reverse {
+        #[clap(long, allow_hyphen_values = true)]
+        lat: f64,
+        #[clap(long, allow_hyphen_values = true)]
+        lon: f64,
+        #[clap(long)]
+        format: Option<Format>,
+        #[command(flatten)]
+        templates: TemplateArgs,
     },
     /// Retrieve an Address by Id
     Get {
         id: Uuid,
         #[clap(long)]
         format: Option<Format>,
+        #[command(flatten)]
+        templates: TemplateArgs,
     },
     /// List all saved addresses
-    List,
+    List {
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// Export saved addresses as a GeoJSON FeatureCollection
+    Export {
+        /// Output file, the standard output by default
+        #[clap(long)]
+        file: Option<String>,
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
     /// Delete an address
     Delete { id: Uuid },
+    /// Print addresses on envelopes or label sheets, as SVG or PDF
+    Render {
+        /// Addresses to print, all the saved addresses selected by `--filter` by default
+        ids: Vec<Uuid>,
+        /// Output file
+        #[clap(long)]
+        file: String,
+        #[clap(long, default_value = "pdf")]
+        to: Document,
+        #[clap(long, default_value = "dl")]
+        sheet: SheetKind,
+        #[clap(long, default_value = "helvetica")]
+        font: Font,
+        /// Font size, in points
+        #[clap(long, default_value_t = 10.0)]
+        font_size: f64,
+        /// Line of the sender block of envelopes, repeat for several lines
+        #[clap(long)]
+        sender_line: Vec<String>,
+        /// Format of the address blocks
+        #[clap(long, default_value = "nf")]
+        format: Format,
+        #[command(flatten)]
+        filter: FilterArgs,
+        #[command(flatten)]
+        templates: TemplateArgs,
+    },
+    /// Fill a text, HTML or Markdown template with saved addresses
+    Merge {
+        /// Template with placeholders such as `{{ address | nf }}` or `{{ town_name | upper }}`
+        #[clap(long)]
+        template: String,
+        /// Output file with every document, the standard output by default
+        #[clap(long, conflicts_with = "output_dir")]
+        file: Option<String>,
+        /// Directory receiving one document per address, named after its id
+        #[clap(long)]
+        output_dir: Option<String>,
+        #[command(flatten)]
+        filter: FilterArgs,
+        #[command(flatten)]
+        templates: TemplateArgs,
+    },
+    /// Start an interactive shell, with history and completion
+    Shell,
+    /// Browse and edit the saved addresses in a full-screen terminal UI
+    Tui {
+        #[command(flatten)]
+        templates: TemplateArgs,
+    },
     /// Convert between address formats
     Convert {
         #[clap(long)]
@@ -126,17 +268,157 @@ enum Commands {
         validate: bool,
         #[clap(long, action)]
         enterprise: bool,
+        #[command(flatten)]
+        templates: TemplateArgs,
     },
 }
 
+/// Options of the UPU S42 template format
+#[derive(Args)]
+struct TemplateArgs {
+    /// Country the mail is sent from, the country line is only written for other countries
+    #[clap(long, default_value = "FR")]
+    sender: String,
+    /// JSON file of additional address templates
+    #[clap(long)]
+    templates: Option<String>,
+}
+
+impl TemplateArgs {
+    fn registry(&self) -> Result<TemplateRegistry, Box<dyn Error>> {
+        let mut registry = TemplateRegistry::new();
+        if let Some(file) = &self.templates {
+            registry.load(&fs::read_to_string(file)?)?;
+        }
+        Ok(registry)
+    }
+}
+
+/// Selection of saved addresses
+#[derive(Args)]
+struct FilterArgs {
+    /// Only keep addresses whose field has a value, e.g. `country=FR`, case insensitive
+    #[clap(long, value_name = "FIELD=VALUE", value_parser = parse_filter)]
+    filter: Vec<(String, String)>,
+}
+
+impl FilterArgs {
+    fn matches(&self, address: &Address) -> Result<bool, Box<dyn Error>> {
+        for (field, value) in &self.filter {
+            let found = address.field(field)?.unwrap_or_default();
+            if !found.eq_ignore_ascii_case(value) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn select(&self, addresses: Vec<Address>) -> Result<Vec<Address>, Box<dyn Error>> {
+        let mut res = Vec::new();
+        for address in addresses {
+            if self.matches(&address)? {
+                res.push(address);
+            }
+        }
+        Ok(res)
+    }
+}
+
+fn parse_filter(s: &str) -> Result<(String, String), String> {
+    let (field, value) = s
+        .split_once('=')
+        .ok_or(format!("`{s}` must be written FIELD=VALUE"))?;
+    Ok((field.trim().to_string(), value.trim().to_string()))
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum Document {
+    Svg,
+    Pdf,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum SheetKind {
+    /// DL envelope (220 x 110 mm), window on the left
+    Dl,
+    /// C5 envelope (229 x 162 mm), window on the right
+    C5,
+    /// C4 envelope (324 x 229 mm)
+    C4,
+    /// A4 sheet of 3 x 8 labels
+    Avery3x8,
+}
+
+impl From<SheetKind> for Sheet {
+    fn from(value: SheetKind) -> Self {
+        match value {
+            SheetKind::Dl => Sheet::DL,
+            SheetKind::C5 => Sheet::C5,
+            SheetKind::C4 => Sheet::C4,
+            SheetKind::Avery3x8 => Sheet::Avery3x8,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum Font {
+    Helvetica,
+    Times,
+    Courier,
+}
+
+impl From<Font> for FontFamily {
+    fn from(value: Font) -> Self {
+        match value {
+            Font::Helvetica => FontFamily::Helvetica,
+            Font::Times => FontFamily::Times,
+            Font::Courier => FontFamily::Courier,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum Format {
     /// ISO 200022
     Iso,
     /// NF Z10-011
     Nf,
+    /// DIN 5008
+    Din,
+    /// CTT (Portugal)
+    Ctt,
+    /// Royal Mail PAF (United Kingdom)
+    Paf,
+    /// USPS Publication 28 (United States)
+    Usps,
     /// Json
     Json,
+    /// vCard ADR
+    Vcard,
+    /// jCard adr
+    Jcard,
+    /// schema.org PostalAddress (JSON-LD)
+    Jsonld,
+    /// HL7 FHIR Address
+    Fhir,
+    /// UBL PostalAddress
+    Ubl,
+    /// UN/EDIFACT NAD segment
+    Edifact,
+    /// ANSI X12 N1/N2/N3/N4 segments
+    X12,
+    /// SWIFT MT party field 50F/59F
+    Mt,
+    /// UPU S42 country template
+    Upu,
+    /// GeoJSON Feature
+    Geojson,
+    /// OpenStreetMap `addr:*` tags, as `key=value` lines
+    Osm,
+    /// OpenStreetMap XML, the `addr:*` tags of nodes
+    OsmXml,
+    /// Free text, e.g. `Jean DURAND, 25D rue des Fleurs, 33500 Libourne, France`
+    Text,
 }
 
 fn run_cli() -> Result<(), Box<dyn Error>> {
@@ -144,6 +426,22 @@ fn run_cli() -> Result<(), Box<dyn Error>> {
     let mut repository = JsonFileRepository::new();
 
     match cli.command {
+        Commands::Shell => shell::run(&mut repository),
+        Commands::Tui { templates } => tui::run(&mut repository, &templates),
+        command => run_command(command, &mut repository),
+    }
+}
+
+///
+///  Runs a command against a repository.
+///
+fn run_command(
+    command: Commands,
+    repository: &mut impl AddressRepository,
+) -> Result<(), Box<dyn Error>> {
+    match command {
+        Commands::Shell => return Err("The shell is already running".into()),
+        Commands::Tui { templates } => tui::run(repository, &templates)?,
         Commands::Add {
             name,
             department,
@@ -177,6 +475,7 @@ fn run_cli() -> Result<(), Box<dyn Error>> {
                 district_name,
                 country_sub_division,
                 country,
+                coordinates: None,
             };
             let id = repository.save(&address)?;
             eprintln!("Address saved at `{}`!", id);
@@ -198,60 +497,45 @@ fn run_cli() -> Result<(), Box<dyn Error>> {
             district_name,
             country_sub_division,
             country,
+            clear,
+            patch,
             format,
+            templates,
         } => {
-            let mut data = repository.get(id).ok_or("ID not Found")?;
-
-            if name.is_some() {
-                data.name = name;
-            }
-            if department.is_some() {
-                data.department = department;
-            }
-            if sub_department.is_some() {
-                data.sub_department = sub_department;
-            }
-            if street_name.is_some() {
-                data.street_name = street_name;
-            }
-            if building_number.is_some() {
-                data.building_number = building_number;
-            }
-            if building_name.is_some() {
-                data.building_name = building_name;
-            }
-            if floor.is_some() {
-                data.floor = floor;
-            }
-            if post_box.is_some() {
-                data.post_box = post_box;
-            }
-            if room.is_some() {
-                data.room = room;
-            }
-            if let Some(val) = post_code {
-                data.post_code = val;
-            }
-            if let Some(val) = town_name {
-                data.town_name = val;
-            }
-            if town_location_name.is_some() {
-                data.town_location_name = town_location_name;
-            }
-            if district_name.is_some() {
-                data.district_name = district_name;
-            }
-            if country_sub_division.is_some() {
-                data.country_sub_division = country_sub_division;
+            let mut changes = match patch {
+                Some(file) => fs::read_to_string(file)?.parse()?,
+                None => AddressPatch::new(),
+            };
+            let fields = [
+                ("name", name),
+                ("department", department),
+                ("sub_department", sub_department),
+                ("street_name", street_name),
+                ("building_number", building_number),
+                ("building_name", building_name),
+                ("floor", floor),
+                ("post_box", post_box),
+                ("room", room),
+                ("post_code", post_code),
+                ("town_name", town_name),
+                ("town_location_name", town_location_name),
+                ("district_name", district_name),
+                ("country_sub_division", country_sub_division),
+                ("country", country),
+            ];
+            for (field, value) in fields {
+                if value.is_some() {
+                    changes.set(field, value)?;
+                }
             }
-            if let Some(val) = country {
-                data.country = val;
+            for field in clear {
+                changes.clear(&field)?;
             }
 
-            repository.update(id, &data)?;
+            let data = repository.patch(id, &changes)?;
 
             let format = format.unwrap_or(Format::Json);
-            let content = str_from_address(&data, format)?;
+            let content = str_from_address(&data, format, &templates)?;
             println!("{}", content);
         }
 
@@ -260,24 +544,114 @@ fn run_cli() -> Result<(), Box<dyn Error>> {
             from,
             validate,
             enterprise,
+            templates,
         } => {
             let content = fs::read_to_string(file)?;
-            let address = address_from_str(&content, from, validate, enterprise)?;
+            let address = address_from_str(&content, from, validate, enterprise, &templates)?;
+            if validate {
+                check_post_code(&address)?;
+            }
             let id = repository.save(&address)?;
             eprintln!("Address saved at `{}`!", id);
         }
 
-        Commands::Get { id, format } => {
+        Commands::Import {
+            file,
+            from,
+            templates,
+        } => {
+            let content = fs::read_to_string(file)?;
+            for address in addresses_from_str(&content, from, &templates)? {
+                let id = repository.save(&address)?;
+                eprintln!("Address saved at `{}`!", id);
+            }
+        }
+
+        Commands::Check { id, fill } => {
+            let mut address = repository
+                .get(id)
+                .ok_or(format!("Address `{id}` not found! "))?;
+            PostcodeDatabase::load()?.check(&address)?;
+            eprintln!("Post code and town are consistent!");
+
+            if fill {
+                fill_departement(&mut address);
+                repository.update(id, &address)?;
+                eprintln!("Address updated!");
+            }
+        }
+
+        Commands::ImportPostcodes { file } => {
+            let count = PostcodeDatabase::import(&fs::read_to_string(file)?)?;
+            eprintln!("{count} post codes imported!");
+        }
+
+        Commands::GeocodeIndex { file } => {
+            let count = GeocodingIndex::build(INDEX_PATH, &fs::read_to_string(file)?)?;
+            eprintln!("{count} addresses indexed!");
+        }
+
+        Commands::Geocode { id, save } => {
+            let mut address = repository
+                .get(id)
+                .ok_or(format!("Address `{id}` not found! "))?;
+            let result = GeocodingIndex::open(INDEX_PATH)?.geocode(&address)?;
+            println!("{}", serde_json::to_string_pretty(&result)?);
+
+            if save {
+                address.coordinates = Some(result.coordinates);
+                repository.update(id, &address)?;
+                eprintln!("Address updated!");
+            }
+        }
+
+        Commands::Reverse {
+            lat,
+            lon,
+            format,
+            templates,
+        } => {
+            let (address, distance) = GeocodingIndex::open(INDEX_PATH)?
+                .reverse(Coordinates { lat, lon })?
+                .ok_or("The geocoding index is empty")?;
+            eprintln!("Closest address at {distance:.0} m");
+            let format = format.unwrap_or(Format::Json);
+            println!("{}", str_from_address(&address, format, &templates)?);
+        }
+
+        Commands::Get {
+            id,
+            format,
+            templates,
+        } => {
             let address = repository
                 .get(id)
                 .ok_or(format!("Address `{id}` not found! "))?;
             let format = format.unwrap_or(Format::Json);
-            let content = str_from_address(&address, format)?;
+            let content = str_from_address(&address, format, &templates)?;
             println!("{}", content);
         }
-        Commands::List => {
-            let addresses = serde_json::to_string_pretty(&repository.list())?;
-            println!("{}", addresses);
+        Commands::List { filter } => {
+            let addresses = filter.select(repository.list())?;
+            println!("{}", serde_json::to_string_pretty(&addresses)?);
+        }
+
+        Commands::Export { file, filter } => {
+            let mut addresses = Vec::new();
+            for (id, address) in repository.entries() {
+                if filter.matches(&address)? {
+                    addresses.push((id, address));
+                }
+            }
+            let collection = GeoJSON_FeatureCollection::new(&addresses);
+            let content = serde_json::to_string_pretty(&collection)?;
+            match file {
+                Some(file) => {
+                    fs::write(file, content)?;
+                    eprintln!("{} addresses exported!", addresses.len());
+                }
+                None => println!("{}", content),
+            }
         }
 
         Commands::Delete { id } => {
@@ -285,17 +659,123 @@ fn run_cli() -> Result<(), Box<dyn Error>> {
             eprintln!("Address deleted!");
         }
 
+        Commands::Render {
+            ids,
+            file,
+            to,
+            sheet,
+            font,
+            font_size,
+            sender_line,
+            format,
+            filter,
+            templates,
+        } => {
+            let addresses = match ids.is_empty() {
+                true => filter.select(repository.list())?,
+                false => ids
+                    .iter()
+                    .map(|id| {
+                        repository
+                            .get(*id)
+                            .ok_or(format!("Address `{id}` not found! "))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            };
+
+            let mut blocks = Vec::new();
+            for address in &addresses {
+                let content = str_from_address(address, format, &templates)?;
+                blocks.push(
+                    content
+                        .lines()
+                        .map(|x| x.trim().to_string())
+                        .filter(|x| !x.is_empty())
+                        .collect(),
+                );
+            }
+
+            let options = RenderOptions {
+                sheet: sheet.into(),
+                font: font.into(),
+                font_size,
+                sender: sender_line,
+            };
+            let pages = layout(&blocks, &options);
+            let content = match to {
+                Document::Svg => to_svg(&pages, &options),
+                Document::Pdf => to_pdf(&pages, &options),
+            };
+            fs::write(file, content)?;
+            eprintln!(
+                "{} addresses rendered on {} pages!",
+                blocks.len(),
+                pages.len()
+            );
+        }
+
+        Commands::Merge {
+            template,
+            file,
+            output_dir,
+            filter,
+            templates,
+        } => {
+            let syntax = Syntax::from_path(&template);
+            let template = MergeTemplate::parse(&fs::read_to_string(&template)?, syntax)?;
+            let registry = templates.registry()?;
+
+            let mut documents = Vec::new();
+            for (id, address) in repository.entries() {
+                if filter.matches(&address)? {
+                    let content =
+                        template.render(Some(id), &address, &registry, &templates.sender)?;
+                    documents.push((id, content));
+                }
+            }
+
+            match output_dir {
+                Some(dir) => {
+                    let extension = match syntax {
+                        Syntax::Text => "txt",
+                        Syntax::Html => "html",
+                        Syntax::Markdown => "md",
+                    };
+                    fs::create_dir_all(&dir)?;
+                    for (id, content) in &documents {
+                        fs::write(format!("{dir}/{id}.{extension}"), content)?;
+                    }
+                    eprintln!("{} documents written!", documents.len());
+                }
+                None => {
+                    let content = documents
+                        .into_iter()
+                        .map(|(_, content)| content)
+                        .collect::<Vec<_>>()
+                        .join(syntax.page_break());
+                    match file {
+                        Some(file) => fs::write(file, content)?,
+                        None => println!("{}", content),
+                    }
+                }
+            }
+        }
+
         Commands::Convert {
             file,
             validate,
             from,
             to,
             enterprise,
+            templates,
         } => {
             let content = fs::read_to_string(file)?;
 
-            let from: Address = address_from_str(&content, from, validate, enterprise)?;
-            let output = str_from_address(&from, to)?;
+            let from: Address = address_from_str(&content, from, validate, enterprise, &templates)?;
+            if validate {
+                check_post_code(&from)?;
+            }
+            let output = str_from_address(&from, to, &templates)?;
 
             println!("{}", output);
         }
@@ -311,11 +791,12 @@ fn address_from_str(
     from: Format,
     validate: bool,
     enterprise: bool,
+    templates: &TemplateArgs,
 ) -> Result<Address, Box<dyn Error>> {
     Ok(match from {
-        Format::Json => serde_json::from_str(&content)?,
+        Format::Json => serde_json::from_str(content)?,
         Format::Iso => {
-            let iso = quick_xml::de::from_str::<ISO_20022>(&content)?;
+            let iso = quick_xml::de::from_str::<ISO_20022>(content)?;
             if validate {
                 iso.validate()?;
             }
@@ -336,20 +817,171 @@ fn address_from_str(
                 nf.try_into()?
             }
         }
+        Format::Din => {
+            let din = content.parse::<DIN_5008>()?;
+            if validate {
+                din.validate()?;
+            }
+            din.try_into()?
+        }
+        Format::Ctt => {
+            let ctt = content.parse::<CTT_Address>()?;
+            if validate {
+                ctt.validate()?;
+            }
+            ctt.try_into()?
+        }
+        Format::Paf => {
+            let paf = content.parse::<PAF_Address>()?;
+            if validate {
+                paf.validate()?;
+            }
+            paf.try_into()?
+        }
+        Format::Usps => {
+            let usps = content.parse::<USPS_Address>()?;
+            if validate {
+                usps.validate()?;
+            }
+            usps.try_into()?
+        }
+        Format::Vcard => content.parse::<VCard_ADR>()?.try_into()?,
+        Format::Jcard => {
+            let jcard = serde_json::from_str(content)?;
+            VCard_ADR::parse_all_jcard(&jcard)?
+                .into_iter()
+                .next()
+                .ok_or("No `adr` property found")?
+                .try_into()?
+        }
+        Format::Jsonld => content.parse::<SchemaOrg_PostalAddress>()?.try_into()?,
+        Format::Fhir => content.parse::<FHIR_Address>()?.try_into()?,
+        Format::Ubl => {
+            let ubl = UBL_PostalAddress::parse_all(content)?
+                .into_iter()
+                .next()
+                .ok_or("No `PostalAddress` found")?;
+            if validate {
+                ubl.validate()?;
+            }
+            ubl.try_into()?
+        }
+        Format::Edifact => {
+            let nad = content.parse::<EDIFACT_NAD>()?;
+            if validate {
+                nad.validate()?;
+            }
+            nad.try_into()?
+        }
+        Format::X12 => {
+            let party = content.parse::<X12_N1>()?;
+            if validate {
+                party.validate()?;
+            }
+            party.try_into()?
+        }
+        Format::Mt => {
+            let party = content.parse::<SWIFT_MT_Party>()?;
+            if validate {
+                party.validate()?;
+            }
+            party.try_into()?
+        }
+        Format::Upu => templates.registry()?.parse(content, &templates.sender)?,
+        Format::Geojson => content.parse::<GeoJSON_Feature>()?.try_into()?,
+        Format::Osm | Format::OsmXml => content.parse::<OSM_Address>()?.try_into()?,
+        Format::Text => {
+            let mut candidates =
+                TextCandidate::parse_all_with(content, &templates.registry()?)?.into_iter();
+            let best = candidates.next().ok_or("No address found in the text")?;
+            for candidate in candidates {
+                eprintln!(
+                    "Alternative ({:.2}): {}",
+                    candidate.confidence,
+                    serde_json::to_string(&candidate.address)?
+                );
+            }
+            best.address
+        }
     })
 }
 
+///
+///  Checks the post code and town of French addresses.
+///
+fn check_post_code(address: &Address) -> Result<(), Box<dyn Error>> {
+    if address.country == "FR" {
+        PostcodeDatabase::load()?.check(address)?;
+    }
+    Ok(())
+}
+
+///
+///  Parses every address contained in a given string and format.
+///
+fn addresses_from_str(
+    content: &str,
+    from: Format,
+    templates: &TemplateArgs,
+) -> Result<Vec<Address>, Box<dyn Error>> {
+    match from {
+        Format::Vcard => try_into_all(VCard_ADR::parse_all(content)?),
+        Format::Jcard => try_into_all(VCard_ADR::parse_all_jcard(&serde_json::from_str(content)?)?),
+        Format::Jsonld => try_into_all(SchemaOrg_PostalAddress::parse_all(content)?),
+        Format::Fhir => try_into_all(FHIR_Address::parse_all(content)?),
+        Format::Ubl => try_into_all(UBL_PostalAddress::parse_all(content)?),
+        Format::Edifact => try_into_all(EDIFACT_NAD::parse_all(content)?),
+        Format::X12 => try_into_all(X12_N1::parse_all(content)?),
+        Format::Mt => try_into_all(SWIFT_MT_Party::parse_all(content)?),
+        Format::Geojson => {
+            let (features, skipped) = GeoJSON_FeatureCollection::parse_features(content)?;
+            for message in skipped {
+                eprintln!("Skipped: {message}");
+            }
+            try_into_all(features)
+        }
+        Format::Osm | Format::OsmXml => try_into_all(OSM_Address::parse_all(content)?),
+        _ => Ok(vec![address_from_str(
+            content, from, false, false, templates,
+        )?]),
+    }
+}
+
+///
+///  Converts a list of parsed addresses into `Address`.
+///
+fn try_into_all<T>(items: Vec<T>) -> Result<Vec<Address>, Box<dyn Error>>
+where
+    Address: TryFrom<T, Error = Box<dyn Error>>,
+{
+    items.into_iter().map(Address::try_from).collect()
+}
+
 ///
 ///  Converts an address into a formatted string representation.
 ///
-fn str_from_address(address: &Address, format: Format) -> Result<String, Box<dyn Error>> {
+fn str_from_address(
+    address: &Address,
+    format: Format,
+    templates: &TemplateArgs,
+) -> Result<String, Box<dyn Error>> {
     let address = address.clone();
     Ok(match format {
         Format::Json => serde_json::to_string_pretty(&address)?,
-        Format::Iso => {
-            let data = ISO_20022::try_from(address)?;
-
-            // indent XMl
+        Format::Iso => ISO_20022::try_from(address)?.to_xml()?,
+        Format::Nf => NF_Z10_011_Individual::try_from(address)?.lines.join("\n"),
+        Format::Din => DIN_5008::try_from(address)?.to_string(),
+        Format::Ctt => CTT_Address::try_from(address)?.to_string(),
+        Format::Paf => PAF_Address::try_from(address)?.to_string(),
+        Format::Usps => USPS_Address::try_from(address)?.to_string(),
+        Format::Vcard => VCard_ADR::try_from(address)?.to_string(),
+        Format::Jcard => serde_json::to_string_pretty(&VCard_ADR::try_from(address)?.to_jcard())?,
+        Format::Jsonld => {
+            serde_json::to_string_pretty(&SchemaOrg_PostalAddress::try_from(address)?)?
+        }
+        Format::Fhir => serde_json::to_string_pretty(&FHIR_Address::try_from(address)?)?,
+        Format::Ubl => {
+            let data = UBL_PostalAddress::try_from(address)?;
 
             let mut buffer = String::new();
 
@@ -359,12 +991,23 @@ fn str_from_address(address: &Address, format: Format) -> Result<String, Box<dyn
 
             buffer
         }
-        Format::Nf => NF_Z10_011_Individual::try_from(address)?.lines.join("\n"),
+        Format::Edifact => EDIFACT_NAD::try_from(address)?.to_string(),
+        Format::X12 => X12_N1::try_from(address)?.to_string(),
+        Format::Mt => SWIFT_MT_Party::try_from(address)?.to_string(),
+        Format::Upu => templates
+            .registry()?
+            .render(&address, &templates.sender)?
+            .join("\n"),
+        Format::Geojson => serde_json::to_string_pretty(&GeoJSON_Feature::try_from(address)?)?,
+        Format::Osm => OSM_Address::try_from(address)?.to_string(),
+        Format::OsmXml => OSM_Address::try_from(address)?.to_xml()?,
+        Format::Text => to_text(&address),
     })
 }
 
 fn main() {
     if let Err(e) = run_cli() {
         eprintln!("error: {}", e);
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,383 @@
+use std::borrow::Cow;
+use std::error::Error;
+
+use address::common::{country_name, find_country};
+use address::models::address::Address;
+use address::models::address_iso_20022::ISO_20022;
+use address::models::address_template::TemplateRegistry;
+use address::models::validate::Validate;
+use address::repositories::address_repository::AddressRepository;
+use clap::{CommandFactory, Parser};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use crate::{check_post_code, run_command, Cli};
+
+/// History of the shell, kept in the working directory like the storage
+const HISTORY_PATH: &str = ".address_history";
+const PROMPT: &str = "address> ";
+
+/// Commands of the shell that are not CLI commands
+const SHELL_COMMANDS: &[&str] = &["wizard", "exit", "quit"];
+/// Options followed by a field name
+const FIELD_OPTIONS: &[&str] = &["--filter", "--clear"];
+
+/// Order of the fields in the wizard, the country first to check the post code
+const WIZARD_FIELDS: [&str; 15] = [
+    "country",
+    "name",
+    "department",
+    "sub_department",
+    "building_number",
+    "street_name",
+    "building_name",
+    "floor",
+    "room",
+    "post_box",
+    "town_location_name",
+    "district_name",
+    "post_code",
+    "town_name",
+    "country_sub_division",
+];
+
+/// Completion of the commands and ids, and live validation in the wizard
+struct ShellHelper {
+    /// Ids of the saved addresses
+    ids: Vec<String>,
+    registry: TemplateRegistry,
+    /// Field being entered in the wizard, with the address entered so far
+    wizard: Option<(&'static str, Address)>,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        if self.wizard.is_some() {
+            return Ok((pos, Vec::new()));
+        }
+
+        let start = line[..pos].rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let words: Vec<&str> = line[..start].split_whitespace().collect();
+        let word = &line[start..pos];
+
+        let candidates = candidates(&words, word, &self.ids)
+            .into_iter()
+            .filter(|x| x.starts_with(word))
+            .map(|x| Pair {
+                display: x.clone(),
+                replacement: x,
+            })
+            .collect();
+
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+
+    fn hint(&self, line: &str, _pos: usize, _ctx: &Context<'_>) -> Option<String> {
+        let (field, address) = self.wizard.as_ref()?;
+        if line.trim().is_empty() {
+            return None;
+        }
+        check_field(field, line, address, &self.registry)
+            .err()
+            .map(|e| format!("  ← {e}"))
+    }
+}
+
+impl Highlighter for ShellHelper {
+    fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
+        Cow::Owned(format!("\x1b[31m{hint}\x1b[0m"))
+    }
+}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+type ShellEditor = Editor<ShellHelper, DefaultHistory>;
+
+/// Words that may follow `words`: command names, their options, field names or ids
+fn candidates(words: &[&str], word: &str, ids: &[String]) -> Vec<String> {
+    let command = Cli::command();
+
+    let Some(name) = words.first() else {
+        return command
+            .get_subcommands()
+            .map(|x| x.get_name().to_string())
+            .chain(SHELL_COMMANDS.iter().map(|x| x.to_string()))
+            .collect();
+    };
+
+    if word.starts_with('-') {
+        return command
+            .find_subcommand(name)
+            .into_iter()
+            .flat_map(|x| x.get_arguments())
+            .filter_map(|x| x.get_long())
+            .map(|x| format!("--{x}"))
+            .collect();
+    }
+
+    match words.last() {
+        Some(option) if FIELD_OPTIONS.contains(option) => {
+            Address::FIELDS.iter().map(|x| x.to_string()).collect()
+        }
+        _ => ids.to_vec(),
+    }
+}
+
+/// Splits a command line into words, keeping quoted text together
+fn split_words(line: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (Some('"') | None, '\\') => {
+                let escaped = chars.next().ok_or("The line ends with `\\`")?;
+                word.get_or_insert_with(String::new).push(escaped);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (_, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err("A quote is not closed".into());
+    }
+    words.extend(word);
+
+    Ok(words)
+}
+
+/// Checks a value entered in the wizard
+fn check_field(
+    field: &str,
+    value: &str,
+    address: &Address,
+    registry: &TemplateRegistry,
+) -> Result<(), Box<dyn Error>> {
+    let value = value.trim();
+
+    if field == "country" {
+        let known = value.len() == 2 && country_name(value, "en").is_some();
+        return match known || find_country(value).is_some() {
+            true => Ok(()),
+            false => Err(format!("Unknown country `{value}`").into()),
+        };
+    }
+    if field == "post_code" {
+        if let Ok(template) = registry.get(&address.country) {
+            if !template.is_post_code(value) {
+                return Err(format!(
+                    "Post codes of {} are written {}",
+                    address.country,
+                    template.post_code.join(" or ")
+                )
+                .into());
+            }
+        }
+    }
+
+    let mut address = address.clone();
+    address.set_field(field, Some(value.to_string()))?;
+    ISO_20022::try_from(address)?.validate()
+}
+
+/// Asks a value until it is valid, `None` when the field is left empty
+fn ask_field(
+    editor: &mut ShellEditor,
+    field: &'static str,
+    address: &Address,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let required = matches!(field, "country" | "post_code" | "town_name");
+
+    loop {
+        if let Some(helper) = editor.helper_mut() {
+            helper.wizard = Some((field, address.clone()));
+        }
+        let marker = if required { "*" } else { "" };
+        let value = editor.readline(&format!("{field}{marker}: "))?;
+        let value = value.trim();
+
+        if value.is_empty() {
+            match required {
+                true => eprintln!("The field `{field}` is mandatory"),
+                false => return Ok(None),
+            }
+            continue;
+        }
+
+        let registry = &editor.helper().ok_or("The shell has no helper")?.registry;
+        match check_field(field, value, address, registry) {
+            Ok(()) if field == "country" => {
+                let code = find_country(value).unwrap_or(value);
+                return Ok(Some(code.to_uppercase()));
+            }
+            Ok(()) => return Ok(Some(value.to_string())),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+}
+
+/// Enters a new address field by field, and saves it once confirmed
+fn wizard(
+    editor: &mut ShellEditor,
+    repository: &mut impl AddressRepository,
+) -> Result<(), Box<dyn Error>> {
+    eprintln!("New address, fields marked with * are mandatory, leave a field empty to skip it");
+
+    let mut address = Address::default();
+    let result = WIZARD_FIELDS.iter().try_for_each(|field| {
+        let value = ask_field(editor, field, &address)?;
+        address.set_field(field, value)
+    });
+    if let Some(helper) = editor.helper_mut() {
+        helper.wizard = None;
+    }
+    result?;
+
+    println!("{}", serde_json::to_string_pretty(&address)?);
+    if let Err(e) = check_post_code(&address) {
+        eprintln!("warning: {e}");
+    }
+
+    let answer = editor.readline("Save this address? [Y/n] ")?;
+    if answer.trim().is_empty() || answer.trim().eq_ignore_ascii_case("y") {
+        let id = repository.save(&address)?;
+        eprintln!("Address saved at `{}`!", id);
+    }
+
+    Ok(())
+}
+
+/// Runs a line of the shell as a CLI command
+fn execute(line: &str, repository: &mut impl AddressRepository) -> Result<(), Box<dyn Error>> {
+    let words = split_words(line)?;
+    match Cli::try_parse_from(std::iter::once("address".to_string()).chain(words)) {
+        Ok(cli) => run_command(cli.command, repository),
+        // Help and usage errors are printed by clap
+        Err(e) => Ok(e.print()?),
+    }
+}
+
+/// Runs the interactive shell until `exit` or Ctrl-D
+pub fn run(repository: &mut impl AddressRepository) -> Result<(), Box<dyn Error>> {
+    let mut editor: ShellEditor = Editor::new()?;
+    editor.set_helper(Some(ShellHelper {
+        ids: Vec::new(),
+        registry: TemplateRegistry::new(),
+        wizard: None,
+    }));
+    let _ = editor.load_history(HISTORY_PATH);
+
+    eprintln!("Type `help` for the commands, `wizard` to enter an address, `exit` to quit");
+    loop {
+        if let Some(helper) = editor.helper_mut() {
+            helper.ids = repository
+                .entries()
+                .into_iter()
+                .map(|(id, _)| id.to_string())
+                .collect();
+        }
+
+        let line = match editor.readline(PROMPT) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line)?;
+
+        let result = match line {
+            "exit" | "quit" => break,
+            "wizard" => wizard(&mut editor, repository),
+            line => execute(line, repository),
+        };
+        match result {
+            Err(e) if e.downcast_ref::<ReadlineError>().is_some() => eprintln!("Cancelled"),
+            Err(e) => eprintln!("error: {}", e),
+            Ok(()) => {}
+        }
+    }
+
+    editor.save_history(HISTORY_PATH)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split_words(
+                r#"add --name "Jean DURAND" --street-name 'RUE DE L"EGLISE' --floor \"2\""#
+            )
+            .expect("error"),
+            vec![
+                "add",
+                "--name",
+                "Jean DURAND",
+                "--street-name",
+                "RUE DE L\"EGLISE",
+                "--floor",
+                "\"2\""
+            ]
+        );
+        assert_eq!(split_words("get ''").expect("error"), vec!["get", ""]);
+        assert!(split_words("get \"abc").is_err());
+    }
+
+    #[test]
+    fn test_candidates() {
+        let ids = vec!["f7ce52bd-8a04-4c42-a064-6f60b2401ef7".to_string()];
+
+        assert!(candidates(&[], "", &ids).contains(&"convert".to_string()));
+        assert!(candidates(&[], "", &ids).contains(&"wizard".to_string()));
+        assert!(candidates(&["add"], "--", &ids).contains(&"--street-name".to_string()));
+        assert!(candidates(&["list", "--filter"], "", &ids).contains(&"post_code".to_string()));
+        assert_eq!(candidates(&["get"], "f7", &ids), ids);
+    }
+
+    #[test]
+    fn test_check_field() {
+        let registry = TemplateRegistry::new();
+        let address = Address {
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+
+        assert!(check_field("country", "France", &address, &registry).is_ok());
+        assert!(check_field("country", "XX", &address, &registry).is_err());
+        assert!(check_field("post_code", "33500", &address, &registry).is_ok());
+        assert!(check_field("post_code", "3350", &address, &registry).is_err());
+        assert!(check_field("building_number", &"1".repeat(17), &address, &registry).is_err());
+    }
+}
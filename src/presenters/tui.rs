@@ -0,0 +1,491 @@
+use std::error::Error;
+
+use address::common::alpha2_to_country;
+use address::models::address::Address;
+use address::models::address_iso_20022::ISO_20022;
+use address::models::laposte::PostcodeDatabase;
+use address::models::validate::Validate;
+use address::repositories::address_repository::AddressRepository;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+use uuid::Uuid;
+
+use crate::{str_from_address, Format, TemplateArgs};
+
+/// What the keys do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Moving in the table
+    Browse,
+    /// Typing the search text
+    Search,
+    /// Choosing the field to edit
+    Form,
+    /// Typing the value of a field
+    Edit,
+    /// Waiting for the confirmation of a deletion
+    ConfirmDelete,
+}
+
+/// State of the terminal UI
+struct App {
+    entries: Vec<(Uuid, Address)>,
+    search: String,
+    table: TableState,
+    mode: Mode,
+    /// Index of the edited field in `Address::FIELDS`
+    field: usize,
+    input: String,
+    status: String,
+    postcodes: PostcodeDatabase,
+}
+
+impl App {
+    fn new(repository: &impl AddressRepository) -> Result<Self, Box<dyn Error>> {
+        let mut app = App {
+            entries: Vec::new(),
+            search: String::new(),
+            table: TableState::default(),
+            mode: Mode::Browse,
+            field: 0,
+            input: String::new(),
+            status: String::new(),
+            postcodes: PostcodeDatabase::load()?,
+        };
+        app.reload(repository);
+        Ok(app)
+    }
+
+    fn reload(&mut self, repository: &impl AddressRepository) {
+        self.entries = repository.entries();
+        self.entries.sort_by(|a, b| {
+            (&a.1.name, &a.1.town_name, a.0).cmp(&(&b.1.name, &b.1.town_name, b.0))
+        });
+        self.select(self.table.selected().unwrap_or(0));
+    }
+
+    /// Addresses matching the search, any field containing the text
+    fn visible(&self) -> Vec<&(Uuid, Address)> {
+        let search = self.search.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|(id, address)| {
+                search.is_empty()
+                    || id.to_string().starts_with(&search)
+                    || Address::FIELDS.iter().any(|x| {
+                        address
+                            .field(x)
+                            .ok()
+                            .flatten()
+                            .is_some_and(|x| x.to_lowercase().contains(&search))
+                    })
+            })
+            .collect()
+    }
+
+    fn select(&mut self, index: usize) {
+        let count = self.visible().len();
+        self.table.select(match count {
+            0 => None,
+            count => Some(index.min(count - 1)),
+        });
+    }
+
+    fn selected(&self) -> Option<(Uuid, Address)> {
+        self.visible()
+            .get(self.table.selected()?)
+            .map(|x| (*x).clone())
+    }
+
+    /// The selected address, with the value being typed
+    fn edited(&self) -> Option<Address> {
+        let (_, mut address) = self.selected()?;
+        if self.mode == Mode::Edit {
+            let value = Some(self.input.trim().to_string()).filter(|x| !x.is_empty());
+            address.set_field(Address::FIELDS[self.field], value).ok()?;
+        }
+        Some(address)
+    }
+
+    fn validate(&self, address: &Address) -> Result<(), Box<dyn Error>> {
+        for field in Address::REQUIRED_FIELDS {
+            if address.field(field)?.is_none() {
+                return Err(format!("The field `{field}` is mandatory").into());
+            }
+        }
+        alpha2_to_country(&address.country)?;
+        ISO_20022::try_from(address.clone())?.validate()?;
+        if address.country == "FR" {
+            self.postcodes.check(address)?;
+        }
+        Ok(())
+    }
+
+    /// Text of a preview pane, the error when the address cannot be written in `format`
+    fn preview(&self, format: Format, templates: &TemplateArgs) -> String {
+        match self.edited() {
+            Some(address) => str_from_address(&address, format, templates)
+                .unwrap_or_else(|e| format!("error: {e}")),
+            None => String::new(),
+        }
+    }
+
+    /// Handles a key, returns `true` to quit
+    fn handle_key(
+        &mut self,
+        key: KeyCode,
+        repository: &mut impl AddressRepository,
+    ) -> Result<bool, Box<dyn Error>> {
+        let selected = self.table.selected().unwrap_or(0);
+
+        match (self.mode, key) {
+            (Mode::Browse, KeyCode::Char('q') | KeyCode::Esc) => return Ok(true),
+            (Mode::Browse, KeyCode::Down | KeyCode::Char('j')) => self.select(selected + 1),
+            (Mode::Browse, KeyCode::Up | KeyCode::Char('k')) => {
+                self.select(selected.saturating_sub(1))
+            }
+            (Mode::Browse, KeyCode::Char('/')) => self.mode = Mode::Search,
+            (Mode::Browse, KeyCode::Char('e') | KeyCode::Enter) if self.selected().is_some() => {
+                self.mode = Mode::Form
+            }
+            (Mode::Browse, KeyCode::Char('d')) if self.selected().is_some() => {
+                self.mode = Mode::ConfirmDelete
+            }
+
+            (Mode::Search, KeyCode::Enter | KeyCode::Esc) => self.mode = Mode::Browse,
+            (Mode::Search, KeyCode::Backspace) => {
+                self.search.pop();
+                self.select(0);
+            }
+            (Mode::Search, KeyCode::Char(c)) => {
+                self.search.push(c);
+                self.select(0);
+            }
+
+            (Mode::Form, KeyCode::Esc) => self.mode = Mode::Browse,
+            (Mode::Form, KeyCode::Down | KeyCode::Char('j')) => {
+                self.field = (self.field + 1).min(Address::FIELDS.len() - 1)
+            }
+            (Mode::Form, KeyCode::Up | KeyCode::Char('k')) => {
+                self.field = self.field.saturating_sub(1)
+            }
+            (Mode::Form, KeyCode::Enter) => {
+                let (_, address) = self.selected().ok_or("No address selected")?;
+                self.input = address
+                    .field(Address::FIELDS[self.field])?
+                    .unwrap_or_default()
+                    .to_string();
+                self.mode = Mode::Edit;
+            }
+
+            (Mode::Edit, KeyCode::Esc) => self.mode = Mode::Form,
+            (Mode::Edit, KeyCode::Backspace) => {
+                self.input.pop();
+            }
+            (Mode::Edit, KeyCode::Char(c)) => self.input.push(c),
+            (Mode::Edit, KeyCode::Enter) => {
+                let (id, _) = self.selected().ok_or("No address selected")?;
+                let address = self.edited().ok_or("No address selected")?;
+                match self.validate(&address) {
+                    Ok(()) => {
+                        repository.update(id, &address)?;
+                        self.status = "Address updated!".to_string();
+                        self.mode = Mode::Form;
+                        self.reload(repository);
+                    }
+                    Err(e) => self.status = e.to_string(),
+                }
+            }
+
+            (Mode::ConfirmDelete, KeyCode::Char('y')) => {
+                let (id, _) = self.selected().ok_or("No address selected")?;
+                repository.delete(id)?;
+                self.status = "Address deleted!".to_string();
+                self.mode = Mode::Browse;
+                self.reload(repository);
+            }
+            (Mode::ConfirmDelete, _) => self.mode = Mode::Browse,
+
+            _ => {}
+        }
+
+        Ok(false)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, templates: &TemplateArgs) {
+        let [search, table, details, status] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Percentage(35),
+            Constraint::Min(10),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let search_style = match self.mode {
+            Mode::Search => Style::default().fg(Color::Yellow),
+            _ => Style::default(),
+        };
+        frame.render_widget(
+            Paragraph::new(self.search.as_str())
+                .style(search_style)
+                .block(Block::default().borders(Borders::ALL).title(" Search (/) ")),
+            search,
+        );
+
+        let rows: Vec<Row> = self
+            .visible()
+            .iter()
+            .map(|(_, x)| {
+                Row::new([
+                    x.name.clone().unwrap_or_default(),
+                    [&x.building_number, &x.street_name]
+                        .into_iter()
+                        .flatten()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    x.post_code.clone(),
+                    x.town_name.clone(),
+                    x.country.clone(),
+                ])
+            })
+            .collect();
+        let widths = [
+            Constraint::Percentage(25),
+            Constraint::Percentage(35),
+            Constraint::Length(10),
+            Constraint::Percentage(25),
+            Constraint::Length(7),
+        ];
+        let title = format!(" Addresses ({}/{}) ", rows.len(), self.entries.len());
+        let rows = Table::new(rows, widths)
+            .header(
+                Row::new(["Name", "Street", "Post code", "Town", "Country"])
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_stateful_widget(rows, table, &mut self.table);
+
+        let [left, middle, right] = Layout::horizontal([Constraint::Ratio(1, 3); 3]).areas(details);
+        let address = self.edited();
+        let render = |format| self.preview(format, templates);
+
+        let left_pane = match self.mode {
+            Mode::Form | Mode::Edit => {
+                let lines: Vec<Line> = Address::FIELDS
+                    .iter()
+                    .enumerate()
+                    .map(|(i, name)| {
+                        let value = match (i == self.field, self.mode) {
+                            (true, Mode::Edit) => format!("{}▏", self.input),
+                            _ => address
+                                .as_ref()
+                                .and_then(|x| x.field(name).ok().flatten())
+                                .unwrap_or_default()
+                                .to_string(),
+                        };
+                        let style = match i == self.field {
+                            true => Style::default().add_modifier(Modifier::REVERSED),
+                            false => Style::default(),
+                        };
+                        Line::styled(format!("{name:>20}: {value}"), style)
+                    })
+                    .collect();
+                Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" Edit "))
+            }
+            _ => Paragraph::new(render(Format::Json))
+                .block(Block::default().borders(Borders::ALL).title(" JSON ")),
+        };
+        frame.render_widget(left_pane, left);
+        frame.render_widget(
+            Paragraph::new(render(Format::Iso))
+                .block(Block::default().borders(Borders::ALL).title(" ISO 20022 ")),
+            middle,
+        );
+        frame.render_widget(
+            Paragraph::new(render(Format::Nf))
+                .block(Block::default().borders(Borders::ALL).title(" NF Z10-011 ")),
+            right,
+        );
+
+        let (text, style) = match self.mode {
+            Mode::Edit => match address.as_ref().map(|x| self.validate(x)) {
+                Some(Err(e)) => (e.to_string(), Style::default().fg(Color::Red)),
+                _ => (
+                    "Valid, Enter to save, Esc to cancel".to_string(),
+                    Style::default().fg(Color::Green),
+                ),
+            },
+            Mode::ConfirmDelete => (
+                "Delete this address? (y/n)".to_string(),
+                Style::default().fg(Color::Yellow),
+            ),
+            Mode::Form => (
+                "↑↓ field, Enter edit, Esc back".to_string(),
+                Style::default(),
+            ),
+            Mode::Search => (
+                "Enter or Esc to stop searching".to_string(),
+                Style::default(),
+            ),
+            Mode::Browse => (
+                format!(
+                    "↑↓ move, / search, e edit, d delete, q quit  {}",
+                    self.status
+                ),
+                Style::default(),
+            ),
+        };
+        frame.render_widget(Paragraph::new(text).style(style), status);
+    }
+}
+
+/// Runs the terminal UI until `q`
+pub fn run(
+    repository: &mut impl AddressRepository,
+    templates: &TemplateArgs,
+) -> Result<(), Box<dyn Error>> {
+    let mut app = App::new(repository)?;
+    let mut terminal = ratatui::init();
+
+    let result = loop {
+        if let Err(e) = terminal.draw(|frame| app.draw(frame, templates)) {
+            break Err(e.into());
+        }
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                match app.handle_key(key.code, repository) {
+                    Ok(true) => break Ok(()),
+                    Ok(false) => {}
+                    Err(e) => app.status = e.to_string(),
+                }
+            }
+            Ok(_) => {}
+            Err(e) => break Err(e.into()),
+        }
+    };
+
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use address::repositories::in_memory_repository::InMemoryRepository;
+
+    use super::*;
+
+    fn repository() -> InMemoryRepository {
+        let mut repository = InMemoryRepository::new();
+        for (name, post_code, town) in [
+            ("Monsieur Jean DURAND", "33500", "LIBOURNE"),
+            ("Madame Lucie MARTIN", "33380", "MIOS"),
+        ] {
+            repository
+                .save(&Address {
+                    name: Some(name.to_string()),
+                    post_code: post_code.to_string(),
+                    town_name: town.to_string(),
+                    country: "FR".to_string(),
+                    ..Default::default()
+                })
+                .expect("error");
+        }
+        repository
+    }
+
+    fn press(app: &mut App, repository: &mut InMemoryRepository, keys: &str) {
+        for c in keys.chars() {
+            let key = match c {
+                '\n' => KeyCode::Enter,
+                '\x1b' => KeyCode::Esc,
+                '\x08' => KeyCode::Backspace,
+                c => KeyCode::Char(c),
+            };
+            app.handle_key(key, repository).expect("error");
+        }
+    }
+
+    #[test]
+    fn test_search() {
+        let mut repository = repository();
+        let mut app = App::new(&repository).expect("error");
+        assert_eq!(app.visible().len(), 2);
+
+        press(&mut app, &mut repository, "/mios\n");
+        assert_eq!(app.visible().len(), 1);
+        assert_eq!(app.selected().expect("error").1.town_name, "MIOS");
+    }
+
+    #[test]
+    fn test_edit() {
+        let mut repository = repository();
+        let mut app = App::new(&repository).expect("error");
+        let (id, _) = app.selected().expect("error");
+
+        // Select `floor`, then type a value
+        press(&mut app, &mut repository, "ejjjjjj\n3E ETAGE\n");
+        assert_eq!(app.mode, Mode::Form);
+        assert_eq!(
+            repository.get(id).expect("error").floor.as_deref(),
+            Some("3E ETAGE")
+        );
+
+        // Clearing the post code is refused
+        press(&mut app, &mut repository, "jjj\n\x08\x08\x08\x08\x08\n");
+        assert_eq!(app.mode, Mode::Edit);
+        assert_eq!(app.status, "The field `post_code` is mandatory");
+        assert!(repository.get(id).expect("error").post_code.len() == 5);
+    }
+
+    #[test]
+    fn test_edit_country() {
+        let mut repository = repository();
+        let mut app = App::new(&repository).expect("error");
+        let templates = TemplateArgs {
+            sender: "FR".to_string(),
+            templates: None,
+        };
+        let (id, _) = app.selected().expect("error");
+
+        // Select `country`, then type it again
+        let keys = format!("e{}\n\x08\x08N", "j".repeat(Address::FIELDS.len() - 1));
+        press(&mut app, &mut repository, &keys);
+        assert_eq!(app.input, "N");
+        assert!(app.preview(Format::Nf, &templates).starts_with("error: "));
+        press(&mut app, &mut repository, "\n");
+        assert_eq!(app.mode, Mode::Edit);
+        assert_eq!(app.status, "Unknown country code `N`");
+
+        press(&mut app, &mut repository, "L");
+        assert!(app.preview(Format::Nf, &templates).ends_with("NETHERLANDS"));
+        press(&mut app, &mut repository, "\n");
+        assert_eq!(repository.get(id).expect("error").country, "NL");
+
+        // A stored address with an unknown country is still shown
+        let mut address = repository.get(id).expect("error");
+        address.country = "ZZ".to_string();
+        repository.update(id, &address).expect("error");
+        press(&mut app, &mut repository, "\x1b\x1b");
+        app.reload(&repository);
+        assert!(app.preview(Format::Nf, &templates).starts_with("error: "));
+        assert!(app.preview(Format::Json, &templates).contains("\"ZZ\""));
+    }
+
+    #[test]
+    fn test_delete() {
+        let mut repository = repository();
+        let mut app = App::new(&repository).expect("error");
+
+        press(&mut app, &mut repository, "dn");
+        assert_eq!(repository.list().len(), 2);
+        press(&mut app, &mut repository, "dy");
+        assert_eq!(repository.list().len(), 1);
+        assert_eq!(app.visible().len(), 1);
+    }
+}
@@ -0,0 +1,279 @@
+use std::path::Path;
+
+use uuid::Uuid;
+
+use crate::common::Result;
+use crate::models::address::Address;
+use crate::models::address_ctt::CTT_Address;
+use crate::models::address_din_5008::DIN_5008;
+use crate::models::address_nf_z10_011_individual::NF_Z10_011_Individual;
+use crate::models::address_osm::OSM_Address;
+use crate::models::address_paf::PAF_Address;
+use crate::models::address_template::TemplateRegistry;
+use crate::models::address_text::to_text;
+use crate::models::address_usps::USPS_Address;
+
+/// Filters changing the case or spacing of a value
+const TEXT_FILTERS: &[&str] = &["upper", "lower", "capitalize", "trim", "oneline"];
+/// Filters rendering the `address` as a block of lines
+const BLOCK_FILTERS: &[&str] = &["nf", "din", "ctt", "paf", "usps", "upu", "osm", "text"];
+
+/// Markup of a template, values are escaped accordingly
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Syntax {
+    Text,
+    /// Values are escaped and line breaks become `<br>`
+    Html,
+    /// Line breaks become hard breaks
+    Markdown,
+}
+
+impl Syntax {
+    /// Guesses the markup from the file extension, plain text by default
+    pub fn from_path(path: &str) -> Self {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|x| x.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+        match extension.as_str() {
+            "html" | "htm" => Syntax::Html,
+            "md" | "markdown" => Syntax::Markdown,
+            _ => Syntax::Text,
+        }
+    }
+
+    /// Separator between the documents of a concatenated output
+    pub fn page_break(&self) -> &'static str {
+        match self {
+            Syntax::Text => "\n\u{c}\n",
+            Syntax::Html => "\n<div style=\"page-break-after: always\"></div>\n",
+            Syntax::Markdown => "\n\n---\n\n",
+        }
+    }
+
+    fn escape(&self, value: &str) -> String {
+        match self {
+            Syntax::Text => value.to_string(),
+            Syntax::Html => value
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;")
+                .replace('\n', "<br>\n"),
+            Syntax::Markdown => value.replace('\n', "  \n"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Text(String),
+    /// `{{ name | filter | ... }}`
+    Value {
+        name: String,
+        filters: Vec<String>,
+    },
+}
+
+/// A text, HTML or Markdown document with `{{ ... }}` placeholders
+///
+/// A placeholder is an `Address` field, `id` or `address`, followed by filters:
+/// `{{ town_name | upper }}`, `{{ address | nf }}`. The `address` is rendered with the
+/// block formats `nf` (default), `din`, `ctt`, `paf`, `usps`, `upu`, `osm` or `text`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeTemplate {
+    pub syntax: Syntax,
+    parts: Vec<Part>,
+}
+
+impl MergeTemplate {
+    /// Parses a template, checking the names and filters of the placeholders
+    pub fn parse(template: &str, syntax: Syntax) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+
+        while let Some(start) = rest.find("{{") {
+            parts.push(Part::Text(rest[..start].to_string()));
+            let end = rest[start..]
+                .find("}}")
+                .ok_or("A `{{` placeholder is not closed")?;
+
+            let mut items = rest[start + 2..start + end].split('|').map(|x| x.trim());
+            let name = items.next().unwrap_or_default().to_string();
+            let filters: Vec<String> = items.map(|x| x.to_string()).collect();
+
+            if name != "id" && name != "address" {
+                Address::default().field(&name)?;
+            }
+            for filter in &filters {
+                let is_block = BLOCK_FILTERS.contains(&filter.as_str());
+                if is_block && name != "address" {
+                    return Err(format!("The `{filter}` filter only applies to `address`").into());
+                }
+                if !is_block && !TEXT_FILTERS.contains(&filter.as_str()) {
+                    return Err(format!("Unknown filter `{filter}`").into());
+                }
+            }
+
+            parts.push(Part::Value { name, filters });
+            rest = &rest[start + end + 2..];
+        }
+        parts.push(Part::Text(rest.to_string()));
+        parts.retain(|x| *x != Part::Text(String::new()));
+
+        Ok(MergeTemplate { syntax, parts })
+    }
+
+    /// Fills the template with an address, `registry` and `sender` being used by the
+    /// `upu` block
+    pub fn render(
+        &self,
+        id: Option<Uuid>,
+        address: &Address,
+        registry: &TemplateRegistry,
+        sender: &str,
+    ) -> Result<String> {
+        let mut res = String::new();
+
+        for part in &self.parts {
+            let (name, filters) = match part {
+                Part::Text(text) => {
+                    res += text;
+                    continue;
+                }
+                Part::Value { name, filters } => (name, filters),
+            };
+
+            let mut value = match name.as_str() {
+                "id" => id.map(|x| x.to_string()).unwrap_or_default(),
+                "address" if !filters.iter().any(|x| BLOCK_FILTERS.contains(&x.as_str())) => {
+                    block(address, "nf", registry, sender)?
+                }
+                "address" => String::new(),
+                name => address.field(name)?.unwrap_or_default().to_string(),
+            };
+
+            for filter in filters {
+                value = match filter.as_str() {
+                    "upper" => value.to_uppercase(),
+                    "lower" => value.to_lowercase(),
+                    "capitalize" => capitalize(&value),
+                    "trim" => value.trim().to_string(),
+                    "oneline" => value
+                        .lines()
+                        .map(|x| x.trim())
+                        .filter(|x| !x.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    format => block(address, format, registry, sender)?,
+                };
+            }
+
+            res += &self.syntax.escape(&value);
+        }
+
+        Ok(res)
+    }
+}
+
+/// Renders an address in a block format, without empty lines
+fn block(
+    address: &Address,
+    format: &str,
+    registry: &TemplateRegistry,
+    sender: &str,
+) -> Result<String> {
+    let address = address.clone();
+    let lines = match format {
+        "nf" => NF_Z10_011_Individual::try_from(address)?.lines.to_vec(),
+        "din" => DIN_5008::try_from(address)?.lines,
+        "ctt" => vec![CTT_Address::try_from(address)?.to_string()],
+        "paf" => PAF_Address::try_from(address)?.lines(),
+        "usps" => vec![USPS_Address::try_from(address)?.to_string()],
+        "upu" => registry.render(&address, sender)?,
+        "osm" => vec![OSM_Address::try_from(address)?.to_string()],
+        _ => vec![to_text(&address)],
+    };
+
+    Ok(lines
+        .iter()
+        .flat_map(|x| x.lines())
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Capitalizes the first letter of each word, `jean-pierre` becomes `Jean-Pierre`
+fn capitalize(value: &str) -> String {
+    let mut res = String::new();
+    let mut start = true;
+    for c in value.chars() {
+        match start {
+            true => res.extend(c.to_uppercase()),
+            false => res.extend(c.to_lowercase()),
+        }
+        start = !c.is_alphanumeric();
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address() -> Address {
+        Address {
+            name: "Monsieur Jean DURAND".to_string().into(),
+            street_name: "25D RUE DES FLEURS".to_string().into(),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let template = MergeTemplate::parse(
+            "{{ address | nf }}\n\nLibourne, le 1er mars\n\nBonjour {{name}} de {{ town_name | capitalize }} ({{ post_code }}){{ floor }}.",
+            Syntax::Text,
+        )
+        .expect("Cannot parse template!");
+
+        let registry = TemplateRegistry::new();
+        assert_eq!(
+            template
+                .render(None, &address(), &registry, "FR")
+                .expect("error"),
+            "Monsieur Jean DURAND\n25D RUE DES FLEURS\n33500 LIBOURNE\nFRANCE\n\nLibourne, le 1er mars\n\nBonjour Monsieur Jean DURAND de Libourne (33500)."
+        );
+    }
+
+    #[test]
+    fn test_render_html() {
+        let template = MergeTemplate::parse(
+            "<p>{{ address | upu }}</p><p>{{ address | oneline }}</p>",
+            Syntax::Html,
+        )
+        .expect("Cannot parse template!");
+
+        let mut address = address();
+        address.name = Some("Jean & Marie DURAND".to_string());
+        let registry = TemplateRegistry::new();
+        assert_eq!(
+            template.render(None, &address, &registry, "FR").expect("error"),
+            "<p>Jean &amp; Marie DURAND<br>\n25D RUE DES FLEURS<br>\n33500 LIBOURNE</p><p>Jean &amp; Marie DURAND, 25D RUE DES FLEURS, 33500 LIBOURNE, FRANCE</p>"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(MergeTemplate::parse("{{ town_name", Syntax::Text).is_err());
+        assert!(MergeTemplate::parse("{{ city }}", Syntax::Text).is_err());
+        assert!(MergeTemplate::parse("{{ town_name | nf }}", Syntax::Text).is_err());
+        assert!(MergeTemplate::parse("{{ address | bold }}", Syntax::Text).is_err());
+        assert_eq!(Syntax::from_path("letter.HTML"), Syntax::Html);
+    }
+}
@@ -0,0 +1,256 @@
+pub mod merge;
+pub mod pdf;
+pub mod svg;
+
+/// Points in a millimeter
+pub const PT_PER_MM: f64 = 72.0 / 25.4;
+
+/// Line height, relative to the font size
+const LINE_HEIGHT: f64 = 1.2;
+/// Distance between the edge of the window or label and the text, in mm
+const PADDING: f64 = 5.0;
+/// Position of the sender block on envelopes, in mm from the top left corner
+const SENDER_POSITION: (f64, f64) = (10.0, 10.0);
+/// Size of the sender block font, relative to the address font
+const SENDER_SCALE: f64 = 0.8;
+
+/// Envelope or label sheet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sheet {
+    /// 220 x 110 mm envelope, window on the left
+    DL,
+    /// 229 x 162 mm envelope, window on the right
+    C5,
+    /// 324 x 229 mm envelope, window facing the address of an unfolded A4 letter
+    C4,
+    /// A4 sheet of 3 x 8 labels of 70 x 37 mm
+    Avery3x8,
+}
+
+/// A rectangle, in mm from the top left corner of the page
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Area {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Sheet {
+    /// Width and height of the page, in mm
+    pub fn size(&self) -> (f64, f64) {
+        match self {
+            Sheet::DL => (220.0, 110.0),
+            Sheet::C5 => (229.0, 162.0),
+            Sheet::C4 => (324.0, 229.0),
+            Sheet::Avery3x8 => (210.0, 297.0),
+        }
+    }
+
+    /// Areas where addresses are printed on a page: the window of an envelope, or
+    /// the labels from left to right and top to bottom
+    pub fn slots(&self) -> Vec<Area> {
+        let window = |x, y| {
+            vec![Area {
+                x,
+                y,
+                width: 100.0,
+                height: 45.0,
+            }]
+        };
+
+        match self {
+            Sheet::DL => window(20.0, 50.0),
+            Sheet::C5 => window(109.0, 102.0),
+            Sheet::C4 => window(20.0, 50.0),
+            Sheet::Avery3x8 => (0..24)
+                .map(|i| Area {
+                    x: (i % 3) as f64 * 70.0,
+                    y: 0.5 + (i / 3) as f64 * 37.0,
+                    width: 70.0,
+                    height: 37.0,
+                })
+                .collect(),
+        }
+    }
+
+    /// Checks whether the sheet is an envelope, which has room for a sender block
+    pub fn is_envelope(&self) -> bool {
+        !matches!(self, Sheet::Avery3x8)
+    }
+}
+
+/// One of the PDF standard fonts, available without embedding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FontFamily {
+    #[default]
+    Helvetica,
+    Times,
+    Courier,
+}
+
+impl FontFamily {
+    /// Name of the PDF standard font
+    pub fn pdf_name(&self) -> &'static str {
+        match self {
+            FontFamily::Helvetica => "Helvetica",
+            FontFamily::Times => "Times-Roman",
+            FontFamily::Courier => "Courier",
+        }
+    }
+
+    /// CSS font family, with fallbacks
+    pub fn svg_name(&self) -> &'static str {
+        match self {
+            FontFamily::Helvetica => "Helvetica, Arial, sans-serif",
+            FontFamily::Times => "'Times New Roman', Times, serif",
+            FontFamily::Courier => "'Courier New', Courier, monospace",
+        }
+    }
+}
+
+/// Options of the rendering
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderOptions {
+    pub sheet: Sheet,
+    pub font: FontFamily,
+    /// Font size of the address, in points
+    pub font_size: f64,
+    /// Lines of the sender block, printed in the top left corner of envelopes
+    pub sender: Vec<String>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            sheet: Sheet::DL,
+            font: FontFamily::default(),
+            font_size: 10.0,
+            sender: Vec::new(),
+        }
+    }
+}
+
+/// Lines of text, `x` and `y` being the top left corner in mm
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextBlock {
+    pub x: f64,
+    pub y: f64,
+    /// Font size, in points
+    pub size: f64,
+    pub lines: Vec<String>,
+}
+
+impl TextBlock {
+    /// Returns the lines with the position of their baseline, in mm from the top left
+    /// corner of the page
+    pub fn baselines(&self) -> impl Iterator<Item = (f64, f64, &str)> {
+        let size = self.size / PT_PER_MM;
+        self.lines.iter().enumerate().map(move |(i, line)| {
+            let y = self.y + size + i as f64 * size * LINE_HEIGHT;
+            (self.x, y, line.as_str())
+        })
+    }
+}
+
+/// A page of the document, its size in mm
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page {
+    pub width: f64,
+    pub height: f64,
+    pub blocks: Vec<TextBlock>,
+}
+
+/// Lays out address blocks: one envelope per address, or as many labels as fit on
+/// each sheet
+pub fn layout(addresses: &[Vec<String>], options: &RenderOptions) -> Vec<Page> {
+    let (width, height) = options.sheet.size();
+    let slots = options.sheet.slots();
+
+    addresses
+        .chunks(slots.len())
+        .map(|chunk| {
+            let mut blocks = Vec::new();
+            if options.sheet.is_envelope() && !options.sender.is_empty() {
+                blocks.push(TextBlock {
+                    x: SENDER_POSITION.0,
+                    y: SENDER_POSITION.1,
+                    size: options.font_size * SENDER_SCALE,
+                    lines: options.sender.clone(),
+                });
+            }
+            for (lines, slot) in chunk.iter().zip(&slots) {
+                blocks.push(TextBlock {
+                    x: slot.x + PADDING,
+                    y: slot.y + PADDING,
+                    size: options.font_size,
+                    lines: lines.clone(),
+                });
+            }
+
+            Page {
+                width,
+                height,
+                blocks,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addresses(count: usize) -> Vec<Vec<String>> {
+        (0..count)
+            .map(|i| vec![format!("Address {i}"), "33500 LIBOURNE".to_string()])
+            .collect()
+    }
+
+    #[test]
+    fn test_layout_envelope() {
+        let options = RenderOptions {
+            sender: vec!["ACME".to_string(), "75002 PARIS".to_string()],
+            ..Default::default()
+        };
+
+        let pages = layout(&addresses(2), &options);
+        assert_eq!(pages.len(), 2);
+        assert_eq!((pages[0].width, pages[0].height), (220.0, 110.0));
+        assert_eq!(pages[1].blocks.len(), 2);
+        assert_eq!(pages[1].blocks[0].lines[0], "ACME");
+        assert_eq!((pages[1].blocks[1].x, pages[1].blocks[1].y), (25.0, 55.0));
+    }
+
+    #[test]
+    fn test_layout_labels() {
+        let options = RenderOptions {
+            sheet: Sheet::Avery3x8,
+            sender: vec!["ACME".to_string()],
+            ..Default::default()
+        };
+
+        let pages = layout(&addresses(25), &options);
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].blocks.len(), 24);
+        assert_eq!(pages[1].blocks.len(), 1);
+
+        let last = &pages[0].blocks[23];
+        assert!(last.x + 70.0 - PADDING <= 210.0);
+        assert!(last.y + 37.0 - PADDING <= 297.0);
+    }
+
+    #[test]
+    fn test_baselines() {
+        let block = TextBlock {
+            x: 10.0,
+            y: 10.0,
+            size: 72.0,
+            lines: vec!["A".to_string(), "B".to_string()],
+        };
+
+        let baselines: Vec<_> = block.baselines().collect();
+        assert_eq!(baselines[0], (10.0, 35.4, "A"));
+        assert!((baselines[1].1 - (35.4 + 25.4 * 1.2)).abs() < 1e-9);
+    }
+}
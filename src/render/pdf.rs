@@ -0,0 +1,152 @@
+use super::{Page, RenderOptions, PT_PER_MM};
+
+/// Characters of the WinAnsi encoding outside of Latin-1
+const WIN_ANSI: &[(char, u8)] = &[
+    ('€', 0x80),
+    ('‚', 0x82),
+    ('„', 0x84),
+    ('…', 0x85),
+    ('Œ', 0x8C),
+    ('‘', 0x91),
+    ('’', 0x92),
+    ('“', 0x93),
+    ('”', 0x94),
+    ('–', 0x96),
+    ('—', 0x97),
+    ('œ', 0x9C),
+    ('Ÿ', 0x9F),
+];
+
+/// Writes a PDF string literal in the WinAnsi encoding, other characters become `?`
+fn pdf_string(text: &str) -> String {
+    let mut res = String::from("(");
+    for c in text.chars() {
+        let byte = match c as u32 {
+            0x20..=0x7E | 0xA0..=0xFF => c as u32 as u8,
+            _ => WIN_ANSI
+                .iter()
+                .find(|(x, _)| *x == c)
+                .map_or(b'?', |(_, byte)| *byte),
+        };
+        match byte {
+            b'(' | b')' | b'\\' => {
+                res.push('\\');
+                res.push(byte as char);
+            }
+            0x20..=0x7E => res.push(byte as char),
+            _ => res += &format!("\\{byte:03o}"),
+        }
+    }
+    res.push(')');
+    res
+}
+
+/// Writes the pages as a PDF document, with one of the standard fonts
+///
+/// The document only uses ASCII characters, accents are written as octal escapes.
+pub fn to_pdf(pages: &[Page], options: &RenderOptions) -> String {
+    // 1: catalog, 2: page tree, 3: font, then a page and its content for each page
+    let page_id = |i: usize| 4 + 2 * i;
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            (0..pages.len())
+                .map(|i| format!("{} 0 R", page_id(i)))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        ),
+        format!(
+            "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+            options.font.pdf_name()
+        ),
+    ];
+
+    for (i, page) in pages.iter().enumerate() {
+        let (width, height) = (page.width * PT_PER_MM, page.height * PT_PER_MM);
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {width:.2} {height:.2}] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            page_id(i) + 1
+        ));
+
+        let mut content = String::new();
+        for block in &page.blocks {
+            for (x, y, line) in block.baselines() {
+                content += &format!(
+                    "BT /F1 {:.2} Tf {:.2} {:.2} Td {} Tj ET\n",
+                    block.size,
+                    x * PT_PER_MM,
+                    (page.height - y) * PT_PER_MM,
+                    pdf_string(line)
+                );
+            }
+        }
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{content}endstream",
+            content.len()
+        ));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf += &format!("{} 0 obj\n{object}\nendobj\n", i + 1);
+    }
+
+    let xref = pdf.len();
+    pdf += &format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        pdf += &format!("{offset:010} 00000 n \n");
+    }
+    pdf += &format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+        objects.len() + 1
+    );
+
+    pdf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{layout, Sheet};
+    use super::*;
+
+    #[test]
+    fn test_pdf_string() {
+        assert_eq!(
+            pdf_string("RUE DE L’ÉGLISE (1)"),
+            "(RUE DE L\\222\\311GLISE \\(1\\))"
+        );
+    }
+
+    #[test]
+    fn test_to_pdf() {
+        let options = RenderOptions {
+            sheet: Sheet::Avery3x8,
+            ..Default::default()
+        };
+        let addresses: Vec<Vec<String>> = (0..30)
+            .map(|i| vec![format!("Client {i}"), "33500 LIBOURNE".to_string()])
+            .collect();
+
+        let pdf = to_pdf(&layout(&addresses, &options), &options);
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.contains("/Count 2"));
+        assert!(pdf.contains("/BaseFont /Helvetica"));
+        assert!(pdf.contains("(Client 29) Tj"));
+
+        // The cross-reference table points to the objects
+        let start: usize = pdf
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|x| x.lines().next())
+            .and_then(|x| x.parse().ok())
+            .expect("startxref is missing");
+        assert!(pdf[start..].starts_with("xref\n0 8\n"));
+        let fourth = pdf[start..].lines().nth(6).expect("error");
+        let offset: usize = fourth[..10].parse().expect("error");
+        assert!(pdf[offset..].starts_with("4 0 obj\n<< /Type /Page "));
+    }
+}
@@ -0,0 +1,72 @@
+use quick_xml::escape::escape;
+
+use super::{Page, RenderOptions};
+
+/// Space between two pages, in mm
+const PAGE_GAP: f64 = 10.0;
+
+/// Writes the pages as a single SVG document, one below the other
+pub fn to_svg(pages: &[Page], options: &RenderOptions) -> String {
+    let width = pages.iter().map(|x| x.width).fold(0.0, f64::max);
+    let height = pages.iter().map(|x| x.height + PAGE_GAP).sum::<f64>() - PAGE_GAP;
+    let height = height.max(0.0);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}mm\" height=\"{height}mm\" viewBox=\"0 0 {width} {height}\" font-family=\"{}\">\n",
+        options.font.svg_name()
+    );
+
+    let mut top = 0.0;
+    for page in pages {
+        svg += &format!(
+            "  <g transform=\"translate(0 {top})\">\n    <rect width=\"{}\" height=\"{}\" fill=\"white\" stroke=\"#ccc\" stroke-width=\"0.2\"/>\n",
+            page.width, page.height
+        );
+        for block in &page.blocks {
+            // Font sizes are in points, the user unit is the millimeter
+            let size = block.size / super::PT_PER_MM;
+            for (x, y, line) in block.baselines() {
+                svg += &format!(
+                    "    <text x=\"{x:.2}\" y=\"{y:.2}\" font-size=\"{size:.2}\">{}</text>\n",
+                    escape(line)
+                );
+            }
+        }
+        svg += "  </g>\n";
+        top += page.height + PAGE_GAP;
+    }
+
+    svg += "</svg>\n";
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{layout, Sheet};
+    use super::*;
+
+    #[test]
+    fn test_to_svg() {
+        let options = RenderOptions {
+            sheet: Sheet::C5,
+            ..Default::default()
+        };
+        let pages = layout(
+            &[
+                vec!["Monsieur Jean DURAND".to_string()],
+                vec!["Café <Le Fleuri> & Co".to_string()],
+            ],
+            &options,
+        );
+
+        let svg = to_svg(&pages, &options);
+        assert!(svg.starts_with(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"229mm\" height=\"334mm\""
+        ));
+        assert_eq!(svg.matches("<g transform").count(), 2);
+        assert!(svg.contains(
+            "<text x=\"114.00\" y=\"110.53\" font-size=\"3.53\">Monsieur Jean DURAND</text>"
+        ));
+        assert!(svg.contains("Café &lt;Le Fleuri&gt; &amp; Co"));
+    }
+}
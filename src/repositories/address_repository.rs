@@ -3,6 +3,7 @@ use uuid::Uuid;
 use crate::common::Result;
 
 use crate::models::address::Address;
+use crate::models::address_patch::AddressPatch;
 
 pub trait AddressRepository {
     /// Saves a new address and returns its unique identifier
@@ -61,4 +62,29 @@ pub trait AddressRepository {
     /// * `Vec<Address>` - List of addresses
     ///
     fn list(&self) -> Vec<Address>;
+
+    /// Lists all stored addresses with their UUID
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(Uuid, Address)>` - List of identifiers and addresses
+    ///
+    fn entries(&self) -> Vec<(Uuid, Address)>;
+
+    /// Applies a patch to an address identified by its UUID
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The UUID of the address to be patched
+    /// * `patch` - The changes of the fields
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Address>` - The updated address, or an error if a change fails
+    ///
+    fn patch(&mut self, id: Uuid, patch: &AddressPatch) -> Result<Address> {
+        let address = patch.apply(&self.get(id).ok_or("ID not Found")?)?;
+        self.update(id, &address)?;
+        Ok(address)
+    }
 }
@@ -1 +1,204 @@
-todo!("Implement Database Repository");
+use super::address_repository::AddressRepository;
+use crate::common::Result;
+use crate::models::address::{Address, Coordinates};
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, OptionalExtension, Row};
+use std::path::Path;
+use uuid::Uuid;
+
+const FILE_PATH: &str = "addresses.db";
+
+/// A repository implementation that stores address data in a SQLite database.
+///
+/// Each address is a row of the `addresses` table, with a column for each field of
+/// `Address::FIELDS` and the `lat` and `lon` coordinates.
+pub struct SqliteRepository {
+    connection: Connection,
+}
+
+impl SqliteRepository {
+    /// Opens the `addresses.db` database, created if it doesn't exist
+    pub fn new() -> Result<Self> {
+        Self::open(FILE_PATH)
+    }
+
+    /// Opens a database file, created if it doesn't exist
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    /// Opens a database kept in memory
+    pub fn in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    /// Creates the `addresses` table if it doesn't exist
+    fn init(connection: Connection) -> Result<Self> {
+        let columns: Vec<String> = Address::FIELDS
+            .iter()
+            .map(|x| format!("{x} TEXT"))
+            .collect();
+        connection.execute(
+            &format!(
+                "CREATE TABLE IF NOT EXISTS addresses (id TEXT PRIMARY KEY, {}, lat REAL, lon REAL)",
+                columns.join(", ")
+            ),
+            (),
+        )?;
+        Ok(Self { connection })
+    }
+
+    /// Values of the columns of an address, after its id
+    fn values(address: &Address) -> Result<Vec<Value>> {
+        let mut values = Vec::new();
+        for name in Address::FIELDS {
+            values.push(match address.field(name)? {
+                Some(value) => Value::Text(value.to_string()),
+                None => Value::Null,
+            });
+        }
+        let (lat, lon) = match address.coordinates {
+            Some(x) => (Value::Real(x.lat), Value::Real(x.lon)),
+            None => (Value::Null, Value::Null),
+        };
+        values.extend([lat, lon]);
+        Ok(values)
+    }
+
+    /// Reads an address from the columns of `select`, after its id
+    fn address(row: &Row) -> rusqlite::Result<Address> {
+        let mut address = Address::default();
+        for (i, name) in Address::FIELDS.iter().enumerate() {
+            // The names come from `Address::FIELDS`, so they are known
+            let _ = address.set_field(name, row.get(i + 1)?);
+        }
+        let lat: Option<f64> = row.get(Address::FIELDS.len() + 1)?;
+        let lon: Option<f64> = row.get(Address::FIELDS.len() + 2)?;
+        address.coordinates = lat.zip(lon).map(|(lat, lon)| Coordinates { lat, lon });
+        Ok(address)
+    }
+
+    fn select(condition: &str) -> String {
+        format!(
+            "SELECT id, {}, lat, lon FROM addresses {condition}",
+            Address::FIELDS.join(", ")
+        )
+    }
+}
+
+impl AddressRepository for SqliteRepository {
+    fn save(&mut self, address: &Address) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        let placeholders: Vec<String> = (1..=Address::FIELDS.len() + 3)
+            .map(|i| format!("?{i}"))
+            .collect();
+        let sql = format!(
+            "INSERT INTO addresses (id, {}, lat, lon) VALUES ({})",
+            Address::FIELDS.join(", "),
+            placeholders.join(", ")
+        );
+
+        let mut values = vec![Value::Text(id.to_string())];
+        values.extend(Self::values(address)?);
+        self.connection.execute(&sql, params_from_iter(values))?;
+        Ok(id)
+    }
+
+    fn update(&mut self, id: Uuid, new_address: &Address) -> Result<()> {
+        let columns: Vec<String> = Address::FIELDS
+            .iter()
+            .chain(&["lat", "lon"])
+            .enumerate()
+            .map(|(i, x)| format!("{x} = ?{}", i + 2))
+            .collect();
+        let sql = format!("UPDATE addresses SET {} WHERE id = ?1", columns.join(", "));
+
+        let mut values = vec![Value::Text(id.to_string())];
+        values.extend(Self::values(new_address)?);
+        match self.connection.execute(&sql, params_from_iter(values))? {
+            0 => Err("Address not found".into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn delete(&mut self, id: Uuid) -> Result<()> {
+        let sql = "DELETE FROM addresses WHERE id = ?1";
+        match self.connection.execute(sql, [id.to_string()])? {
+            0 => Err("Address not found".into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn get(&self, id: Uuid) -> Option<Address> {
+        self.connection
+            .query_row(&Self::select("WHERE id = ?1"), [id.to_string()], |row| {
+                Self::address(row)
+            })
+            .optional()
+            .unwrap_or_default() // Return None on any error
+    }
+
+    fn list(&self) -> Vec<Address> {
+        self.entries()
+            .into_iter()
+            .map(|(_, address)| address)
+            .collect()
+    }
+
+    fn entries(&self) -> Vec<(Uuid, Address)> {
+        let entries = || -> Result<Vec<(Uuid, Address)>> {
+            let mut statement = self.connection.prepare(&Self::select("ORDER BY rowid"))?;
+            let rows = statement.query_map((), |row| {
+                let id: String = row.get(0)?;
+                Ok((id, Self::address(row)?))
+            })?;
+
+            let mut res = Vec::new();
+            for row in rows {
+                let (id, address) = row?;
+                res.push((Uuid::parse_str(&id)?, address));
+            }
+            Ok(res)
+        };
+        entries().unwrap_or_default() // Return empty on any error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqlite_repository() {
+        let mut repo = SqliteRepository::in_memory().expect("error");
+
+        let address = Address {
+            name: Some("Monsieur Jean DURAND".to_string()),
+            street_name: Some("25D RUE DES FLEURS".to_string()),
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            coordinates: Some(Coordinates {
+                lat: 44.9153,
+                lon: -0.2421,
+            }),
+            ..Default::default()
+        };
+        let id = repo.save(&address).expect("error");
+        assert_eq!(repo.get(id), Some(address.clone()));
+
+        let moved = Address {
+            town_name: "MIOS".to_string(),
+            coordinates: None,
+            ..address.clone()
+        };
+        repo.update(id, &moved).expect("error");
+        assert_eq!(repo.entries(), vec![(id, moved)]);
+        assert!(repo.update(Uuid::new_v4(), &address).is_err());
+
+        repo.delete(id).expect("error");
+        assert!(repo.list().is_empty());
+        assert!(repo.delete(id).is_err());
+        assert_eq!(repo.get(id), None);
+    }
+}
@@ -50,4 +50,11 @@ impl AddressRepository for InMemoryRepository {
     fn list(&self) -> Vec<Address> {
         self.storage.values().cloned().collect()
     }
+
+    fn entries(&self) -> Vec<(Uuid, Address)> {
+        self.storage
+            .iter()
+            .map(|(id, address)| (*id, address.clone()))
+            .collect()
+    }
 }
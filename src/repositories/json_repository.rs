@@ -13,6 +13,7 @@ const FILE_PATH: &str = "addresses.json";
 /// A repository implementation that stores address data in a JSON file.
 ///
 /// This repository reads and writes to a FILE in JSON to persist address data.
+#[derive(Default)]
 pub struct JsonFileRepository;
 
 pub type Storage = HashMap<Uuid, Address>;
@@ -83,4 +84,9 @@ impl AddressRepository for JsonFileRepository {
         let storage = Self::read();
         storage.values().cloned().collect()
     }
+
+    fn entries(&self) -> Vec<(Uuid, Address)> {
+        let storage = Self::read();
+        storage.into_iter().collect()
+    }
 }
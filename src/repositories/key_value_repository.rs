@@ -0,0 +1,157 @@
+use super::address_repository::AddressRepository;
+use crate::common::Result;
+use crate::models::address::Address;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Prefix of the keys of the addresses, other keys of the store are ignored
+const KEY_PREFIX: &str = "address:";
+
+/// Strings stored by key, e.g. the `localStorage` of a browser
+pub trait KeyValueStore {
+    /// Returns the value of a key, `None` when the key is not set
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Sets the value of a key
+    fn set(&mut self, key: &str, value: &str) -> Result<()>;
+
+    /// Removes a key
+    fn remove(&mut self, key: &str) -> Result<()>;
+
+    /// Lists every key of the store
+    fn keys(&self) -> Vec<String>;
+}
+
+impl KeyValueStore for HashMap<String, String> {
+    fn get(&self, key: &str) -> Option<String> {
+        HashMap::get(self, key).cloned()
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        self.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<()> {
+        HashMap::remove(self, key);
+        Ok(())
+    }
+
+    fn keys(&self) -> Vec<String> {
+        HashMap::keys(self).cloned().collect()
+    }
+}
+
+/// A repository implementation that stores each address as JSON in a key-value store.
+///
+/// The addresses are saved under `address:<id>`, so the store may be shared.
+#[derive(Debug, Default)]
+pub struct KeyValueRepository<S: KeyValueStore> {
+    store: S,
+}
+
+impl<S: KeyValueStore> KeyValueRepository<S> {
+    /// Creates a new instance of `KeyValueRepository` over a store
+    pub fn new(store: S) -> Self {
+        Self { store }
+    }
+
+    /// Returns the underlying store
+    pub fn into_inner(self) -> S {
+        self.store
+    }
+
+    fn key(id: Uuid) -> String {
+        format!("{KEY_PREFIX}{id}")
+    }
+
+    /// Writes an address, existing or not
+    fn write(&mut self, id: Uuid, address: &Address) -> Result<()> {
+        let json = serde_json::to_string(address)?;
+        self.store.set(&Self::key(id), &json)
+    }
+}
+
+impl<S: KeyValueStore> AddressRepository for KeyValueRepository<S> {
+    fn save(&mut self, address: &Address) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        self.write(id, address)?;
+        Ok(id)
+    }
+
+    fn update(&mut self, id: Uuid, new_address: &Address) -> Result<()> {
+        match self.get(id) {
+            Some(_) => self.write(id, new_address),
+            None => Err("Address not found".into()),
+        }
+    }
+
+    fn delete(&mut self, id: Uuid) -> Result<()> {
+        match self.get(id) {
+            Some(_) => self.store.remove(&Self::key(id)),
+            None => Err("Address not found".into()),
+        }
+    }
+
+    fn get(&self, id: Uuid) -> Option<Address> {
+        let json = self.store.get(&Self::key(id))?;
+        serde_json::from_str(&json).ok() // Ignore values that are not addresses
+    }
+
+    fn list(&self) -> Vec<Address> {
+        self.entries()
+            .into_iter()
+            .map(|(_, address)| address)
+            .collect()
+    }
+
+    fn entries(&self) -> Vec<(Uuid, Address)> {
+        self.store
+            .keys()
+            .iter()
+            .filter_map(|key| key.strip_prefix(KEY_PREFIX))
+            .filter_map(|id| Uuid::parse_str(id).ok())
+            .filter_map(|id| Some((id, self.get(id)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_value_repository() {
+        let mut store = HashMap::new();
+        store.insert("theme".to_string(), "dark".to_string());
+        let mut repo = KeyValueRepository::new(store);
+
+        let address = Address {
+            post_code: "33500".to_string(),
+            town_name: "LIBOURNE".to_string(),
+            country: "FR".to_string(),
+            ..Default::default()
+        };
+        let id = repo.save(&address).expect("error");
+        assert_eq!(repo.get(id), Some(address.clone()));
+        assert_eq!(repo.entries(), vec![(id, address.clone())]);
+
+        let moved = Address {
+            town_name: "MIOS".to_string(),
+            ..address
+        };
+        repo.update(id, &moved).expect("error");
+        assert_eq!(repo.list(), vec![moved]);
+        assert!(repo.update(Uuid::new_v4(), &Address::default()).is_err());
+
+        repo.delete(id).expect("error");
+        assert!(repo.list().is_empty());
+        assert!(repo.delete(id).is_err());
+
+        // Other keys of the store are left as they are
+        assert_eq!(
+            repo.into_inner().get("theme").map(|x| x.as_str()),
+            Some("dark")
+        );
+    }
+}
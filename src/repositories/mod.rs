@@ -1,3 +1,7 @@
 pub mod address_repository;
+#[cfg(feature = "sqlite-repo")]
+pub mod database_repository;
 pub mod in_memory_repository;
+#[cfg(feature = "json-repo")]
 pub mod json_repository;
+pub mod key_value_repository;
@@ -42,4 +42,9 @@ fn test_repository() {
     // test List
     let list = repo.list();
     assert_eq!(vec![a1], list);
+
+    // test Entries
+    let entries = repo.entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(repo.get(entries[0].0), Some(entries[0].1.clone()));
 }